@@ -0,0 +1,72 @@
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const UTF8_STRING: u8 = 0x0C;
+pub const PRINTABLE_STRING: u8 = 0x13;
+pub const T61_STRING: u8 = 0x14;
+pub const IA5_STRING: u8 = 0x16;
+pub const UTC_TIME: u8 = 0x17;
+pub const GENERALIZED_TIME: u8 = 0x18;
+pub const BMP_STRING: u8 = 0x1E;
+pub const SEQUENCE: u8 = 0x30;
+pub const SET: u8 = 0x31;
+pub const CONTEXT_0: u8 = 0xA0;
+pub const CONTEXT_1: u8 = 0xA1;
+
+/// A DER element with a single byte tag
+#[derive(Clone, Copy)]
+pub struct Element<'data> {
+    pub tag: u8,
+    pub contents: &'data [u8],
+}
+
+/// Reads the DER elements in a sequence one after another
+#[derive(Clone)]
+pub struct Reader<'data> {
+    data: &'data [u8],
+}
+
+impl<'data> Reader<'data> {
+    pub const fn new(data: &'data [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Reads the next element if it has the given tag, leaving the reader as it was otherwise
+    pub fn read(&mut self, tag: u8) -> Option<&'data [u8]> {
+        let element = self.clone().next()?;
+        if element.tag != tag {
+            return None;
+        }
+        self.next();
+        Some(element.contents)
+    }
+}
+
+impl<'data> Iterator for Reader<'data> {
+    type Item = Element<'data>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&tag, rest) = self.data.split_first()?;
+        let (&first, rest) = rest.split_first()?;
+        let (length, rest) = if first & 0x80 == 0 {
+            (usize::from(first), rest)
+        } else {
+            // The long form gives the number of bytes that the length takes up
+            let count = usize::from(first & 0x7F);
+            if count == 0 || count > 4 || rest.len() < count {
+                return None;
+            }
+            let length = rest[..count]
+                .iter()
+                .fold(0, |length, &byte| length << 8 | usize::from(byte));
+            (length, &rest[count..])
+        };
+        if rest.len() < length {
+            self.data = &[];
+            return None;
+        }
+        let (contents, rest) = rest.split_at(length);
+        self.data = rest;
+        Some(Element { tag, contents })
+    }
+}
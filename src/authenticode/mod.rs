@@ -0,0 +1,640 @@
+mod der;
+
+use crate::authenticode::der::{
+    Element, Reader, BMP_STRING, CONTEXT_0, CONTEXT_1, GENERALIZED_TIME, IA5_STRING, INTEGER,
+    OBJECT_IDENTIFIER, OCTET_STRING, PRINTABLE_STRING, SEQUENCE, SET, T61_STRING, UTC_TIME,
+    UTF8_STRING,
+};
+use object::pe::IMAGE_DIRECTORY_ENTRY_SECURITY;
+use object::read::pe::{ImageNtHeaders, PeFile};
+use object::{LittleEndian, ReadRef};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::io::{Read, Seek};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+/// The `WIN_CERTIFICATE` type for a PKCS#7 `SignedData` structure
+const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 2;
+
+/// The length, revision and certificate type that precede the certificate in `WIN_CERTIFICATE`
+const WIN_CERTIFICATE_HEADER_SIZE: usize = 8;
+
+const MSI_DIGITAL_SIGNATURE: &str = "\u{5}DigitalSignature";
+
+/// 1.2.840.113549.1.7.2
+const SIGNED_DATA: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02];
+/// 1.2.840.113549.1.9.5
+const SIGNING_TIME: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x05];
+/// 1.2.840.113549.1.9.6
+const COUNTER_SIGNATURE: &[u8] = &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x06];
+/// 1.3.6.1.4.1.311.3.3.1
+const RFC_3161_TIMESTAMP: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x03, 0x03, 0x01];
+/// 2.5.4.3
+const COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+/// 2.5.4.10
+const ORGANIZATION_NAME: &[u8] = &[0x55, 0x04, 0x0A];
+
+/// The Authenticode signature of an installer. The signature is read but not verified.
+#[skip_serializing_none]
+#[derive(Default, Deserialize, Serialize)]
+pub struct Authenticode {
+    pub is_signed: bool,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub timestamp: Option<OffsetDateTime>,
+}
+
+impl Authenticode {
+    /// Reads the signature from the security directory of a PE file
+    pub fn from_pe<'data, Pe, R>(pe: &PeFile<'data, Pe, R>) -> Self
+    where
+        Pe: ImageNtHeaders,
+        R: ReadRef<'data>,
+    {
+        let Some(directory) = pe
+            .data_directory(IMAGE_DIRECTORY_ENTRY_SECURITY)
+            .filter(|directory| directory.size.get(LittleEndian) != 0)
+        else {
+            return Self::default();
+        };
+        // The address of the security directory is a file offset rather than a virtual address
+        let (offset, size) = directory.address_range();
+        let Ok(certificates) = pe.data().read_bytes_at(u64::from(offset), u64::from(size)) else {
+            return Self::default();
+        };
+        let pkcs7 = certificates
+            .get(..WIN_CERTIFICATE_HEADER_SIZE)
+            .filter(|header| {
+                u16::from_le_bytes([header[6], header[7]]) == WIN_CERT_TYPE_PKCS_SIGNED_DATA
+            })
+            .and_then(|header| {
+                let length = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+                certificates.get(WIN_CERTIFICATE_HEADER_SIZE..length as usize)
+            });
+        Self::from_pkcs7(pkcs7)
+    }
+
+    /// Reads the signature from the `\x05DigitalSignature` stream of an MSI
+    pub fn from_msi<R: Read + Seek>(reader: R) -> Self {
+        let Ok(mut compound_file) = cfb::CompoundFile::open(reader) else {
+            return Self::default();
+        };
+        let mut pkcs7 = Vec::new();
+        if compound_file
+            .open_stream(MSI_DIGITAL_SIGNATURE)
+            .and_then(|mut stream| stream.read_to_end(&mut pkcs7))
+            .is_err()
+        {
+            return Self::default();
+        }
+        Self::from_pkcs7(Some(&pkcs7))
+    }
+
+    /// A signature that is present but can't be read is still reported as signed
+    fn from_pkcs7(pkcs7: Option<&[u8]>) -> Self {
+        let Some(pkcs7) = pkcs7 else {
+            return Self::default();
+        };
+        let signer = SignedData::parse(pkcs7).and_then(|signed_data| signed_data.signer());
+        Self {
+            is_signed: true,
+            subject: signer.as_ref().and_then(|signer| signer.subject.clone()),
+            issuer: signer.as_ref().and_then(|signer| signer.issuer.clone()),
+            timestamp: signer.and_then(|signer| signer.timestamp),
+        }
+    }
+
+    /// Describes why an installer's signature needs a closer look, if it does
+    pub fn warning(&self, file_name: &str, previous_publisher: Option<&str>) -> Option<String> {
+        if !self.is_signed {
+            return Some(format!("{file_name} is not signed"));
+        }
+        let subject = self.subject.as_deref()?;
+        let publisher = previous_publisher.filter(|publisher| !self.is_signed_by(publisher))?;
+        Some(format!(
+            "{file_name} is signed by {subject}, which is different to the previous publisher, {publisher}"
+        ))
+    }
+
+    /// Whether the signer is the same as a publisher, ignoring case, punctuation and legal
+    /// suffixes such as `Inc.`
+    pub fn is_signed_by(&self, publisher: &str) -> bool {
+        let publisher = normalise_name(publisher);
+        self.subject
+            .as_deref()
+            .map(normalise_name)
+            .is_some_and(|subject| !subject.is_empty() && subject == publisher)
+    }
+}
+
+/// Legal suffixes of company names that are left out when comparing a signer to a publisher
+const LEGAL_SUFFIXES: [&str; 24] = [
+    "ab",
+    "ag",
+    "bv",
+    "co",
+    "company",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "kg",
+    "kk",
+    "limited",
+    "llc",
+    "llp",
+    "lp",
+    "ltd",
+    "nv",
+    "oy",
+    "plc",
+    "pty",
+    "sa",
+    "sarl",
+    "srl",
+];
+
+/// Lowercases a company name and removes its punctuation and legal suffixes, so that
+/// `Example Software, Inc.` becomes `examplesoftware`
+fn normalise_name(name: &str) -> String {
+    let name = name.replace('.', "").to_lowercase();
+    let mut words = name
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    while words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| LEGAL_SUFFIXES.contains(word))
+    {
+        words.pop();
+    }
+    words.concat()
+}
+
+struct Signer {
+    subject: Option<String>,
+    issuer: Option<String>,
+    timestamp: Option<OffsetDateTime>,
+}
+
+struct SignedData<'data> {
+    certificates: Vec<&'data [u8]>,
+    signer_infos: Vec<&'data [u8]>,
+}
+
+impl<'data> SignedData<'data> {
+    /// Parses a `ContentInfo` that contains `SignedData`
+    fn parse(content_info: &'data [u8]) -> Option<Self> {
+        let mut content_info = Reader::new(Reader::new(content_info).read(SEQUENCE)?);
+        if content_info.read(OBJECT_IDENTIFIER)? != SIGNED_DATA {
+            return None;
+        }
+        let content = content_info.read(CONTEXT_0)?;
+        let mut signed_data = Reader::new(Reader::new(content).read(SEQUENCE)?);
+        // Version, digest algorithms and the content that is signed
+        signed_data.read(INTEGER)?;
+        signed_data.read(SET)?;
+        signed_data.read(SEQUENCE)?;
+        let certificates = signed_data
+            .read(CONTEXT_0)
+            .map(|certificates| {
+                Reader::new(certificates)
+                    .filter(|certificate| certificate.tag == SEQUENCE)
+                    .map(|certificate| certificate.contents)
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Certificate revocation lists
+        signed_data.read(CONTEXT_1);
+        let signer_infos = Reader::new(signed_data.read(SET)?)
+            .filter(|signer_info| signer_info.tag == SEQUENCE)
+            .map(|signer_info| signer_info.contents)
+            .collect();
+        Some(Self {
+            certificates,
+            signer_infos,
+        })
+    }
+
+    /// Reads the generation time from the `TSTInfo` that an RFC 3161 timestamp token signs
+    fn parse_timestamp_token(content_info: &[u8]) -> Option<OffsetDateTime> {
+        let mut content_info = Reader::new(content_info);
+        if content_info.read(OBJECT_IDENTIFIER)? != SIGNED_DATA {
+            return None;
+        }
+        let mut signed_data =
+            Reader::new(Reader::new(content_info.read(CONTEXT_0)?).read(SEQUENCE)?);
+        signed_data.read(INTEGER)?;
+        signed_data.read(SET)?;
+        let mut encapsulated_content = Reader::new(signed_data.read(SEQUENCE)?);
+        encapsulated_content.read(OBJECT_IDENTIFIER)?;
+        let tst_info = Reader::new(encapsulated_content.read(CONTEXT_0)?).read(OCTET_STRING)?;
+        let mut tst_info = Reader::new(Reader::new(tst_info).read(SEQUENCE)?);
+        // Version, policy, message imprint and serial number
+        tst_info.read(INTEGER)?;
+        tst_info.read(OBJECT_IDENTIFIER)?;
+        tst_info.read(SEQUENCE)?;
+        tst_info.read(INTEGER)?;
+        parse_time(GENERALIZED_TIME, tst_info.read(GENERALIZED_TIME)?)
+    }
+
+    /// Gets the certificate of the first signer along with when it was signed
+    fn signer(&self) -> Option<Signer> {
+        let signer_info = SignerInfo::parse(self.signer_infos.first()?)?;
+        let certificate = self
+            .certificates
+            .iter()
+            .filter_map(|certificate| Certificate::parse(certificate))
+            .find(|certificate| {
+                certificate.issuer == signer_info.issuer && certificate.serial == signer_info.serial
+            });
+        Some(Signer {
+            subject: certificate.as_ref().and_then(|certificate| {
+                name_attribute(certificate.subject, COMMON_NAME)
+                    .or_else(|| name_attribute(certificate.subject, ORGANIZATION_NAME))
+            }),
+            issuer: name_attribute(signer_info.issuer, COMMON_NAME)
+                .or_else(|| name_attribute(signer_info.issuer, ORGANIZATION_NAME)),
+            timestamp: signer_info.timestamp(),
+        })
+    }
+}
+
+struct SignerInfo<'data> {
+    issuer: &'data [u8],
+    serial: &'data [u8],
+    authenticated_attributes: Option<&'data [u8]>,
+    unauthenticated_attributes: Option<&'data [u8]>,
+}
+
+impl<'data> SignerInfo<'data> {
+    fn parse(signer_info: &'data [u8]) -> Option<Self> {
+        let mut signer_info = Reader::new(signer_info);
+        signer_info.read(INTEGER)?;
+        let mut issuer_and_serial_number = Reader::new(signer_info.read(SEQUENCE)?);
+        let issuer = issuer_and_serial_number.read(SEQUENCE)?;
+        let serial = issuer_and_serial_number.read(INTEGER)?;
+        // Digest algorithm
+        signer_info.read(SEQUENCE)?;
+        let authenticated_attributes = signer_info.read(CONTEXT_0);
+        // Digest encryption algorithm and encrypted digest
+        signer_info.read(SEQUENCE)?;
+        signer_info.read(OCTET_STRING)?;
+        let unauthenticated_attributes = signer_info.read(CONTEXT_1);
+        Some(Self {
+            issuer,
+            serial,
+            authenticated_attributes,
+            unauthenticated_attributes,
+        })
+    }
+
+    /// Gets the time from a counter-signature or RFC 3161 timestamp, falling back to the time
+    /// that the signer claims to have signed at
+    fn timestamp(&self) -> Option<OffsetDateTime> {
+        let unauthenticated_attributes = self.unauthenticated_attributes.unwrap_or_default();
+        attribute(unauthenticated_attributes, COUNTER_SIGNATURE)
+            .and_then(|counter_signature| {
+                SignerInfo::parse(counter_signature.contents)?.signing_time()
+            })
+            .or_else(|| {
+                let token = attribute(unauthenticated_attributes, RFC_3161_TIMESTAMP)?;
+                SignedData::parse_timestamp_token(token.contents)
+            })
+            .or_else(|| self.signing_time())
+    }
+
+    fn signing_time(&self) -> Option<OffsetDateTime> {
+        let time = attribute(self.authenticated_attributes?, SIGNING_TIME)?;
+        parse_time(time.tag, time.contents)
+    }
+}
+
+struct Certificate<'data> {
+    serial: &'data [u8],
+    issuer: &'data [u8],
+    subject: &'data [u8],
+}
+
+impl<'data> Certificate<'data> {
+    fn parse(certificate: &'data [u8]) -> Option<Self> {
+        let mut tbs_certificate = Reader::new(Reader::new(certificate).read(SEQUENCE)?);
+        // The version is only present from version 2 onwards
+        tbs_certificate.read(CONTEXT_0);
+        let serial = tbs_certificate.read(INTEGER)?;
+        // Signature algorithm
+        tbs_certificate.read(SEQUENCE)?;
+        let issuer = tbs_certificate.read(SEQUENCE)?;
+        // Validity
+        tbs_certificate.read(SEQUENCE)?;
+        let subject = tbs_certificate.read(SEQUENCE)?;
+        Some(Self {
+            serial,
+            issuer,
+            subject,
+        })
+    }
+}
+
+/// Gets the first value of an attribute in a list of attributes
+fn attribute<'data>(attributes: &'data [u8], id: &[u8]) -> Option<Element<'data>> {
+    Reader::new(attributes)
+        .filter(|attribute| attribute.tag == SEQUENCE)
+        .find_map(|attribute| {
+            let mut attribute = Reader::new(attribute.contents);
+            if attribute.read(OBJECT_IDENTIFIER)? != id {
+                return None;
+            }
+            Reader::new(attribute.read(SET)?).next()
+        })
+}
+
+/// Gets the value of an attribute in a distinguished name, such as its common name
+fn name_attribute(name: &[u8], id: &[u8]) -> Option<String> {
+    Reader::new(name)
+        .filter(|relative_name| relative_name.tag == SET)
+        .flat_map(|relative_name| Reader::new(relative_name.contents))
+        .find_map(|attribute| {
+            let mut attribute = Reader::new(attribute.contents);
+            if attribute.read(OBJECT_IDENTIFIER)? != id {
+                return None;
+            }
+            let value = attribute.next()?;
+            match value.tag {
+                UTF8_STRING | PRINTABLE_STRING | IA5_STRING => {
+                    Some(String::from_utf8_lossy(value.contents).into_owned())
+                }
+                // Latin-1 is close enough for the teletex strings that are used in practice
+                T61_STRING => Some(value.contents.iter().copied().map(char::from).collect()),
+                BMP_STRING => String::from_utf16(
+                    &value
+                        .contents
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                        .collect::<Vec<_>>(),
+                )
+                .ok(),
+                _ => None,
+            }
+        })
+}
+
+/// Parses a `UTCTime` such as `240131120000Z` or a `GeneralizedTime` such as
+/// `20240131120000.5Z`
+fn parse_time(tag: u8, time: &[u8]) -> Option<OffsetDateTime> {
+    let time = std::str::from_utf8(time).ok()?;
+    let (year, rest) = match tag {
+        // Years from 50 to 99 are in the 1900s
+        UTC_TIME => {
+            let year = time.get(..2)?.parse::<i32>().ok()?;
+            (if year >= 50 { 1900 } else { 2000 } + year, &time[2..])
+        }
+        GENERALIZED_TIME => (time.get(..4)?.parse::<i32>().ok()?, &time[4..]),
+        _ => return None,
+    };
+    let number = |range: std::ops::Range<usize>| rest.get(range)?.parse::<u8>().ok();
+    let date =
+        Date::from_calendar_date(year, Month::try_from(number(0..2)?).ok()?, number(2..4)?).ok()?;
+    let time = Time::from_hms(number(4..6)?, number(6..8)?, number(8..10)?).ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::authenticode::der::{GENERALIZED_TIME, UTC_TIME};
+    use crate::authenticode::{parse_time, Authenticode, SignedData};
+    use time::{Date, Month, OffsetDateTime};
+
+    fn utc(year: i32, month: Month, day: u8, hour: u8, minute: u8, second: u8) -> OffsetDateTime {
+        Date::from_calendar_date(year, month, day)
+            .unwrap()
+            .with_hms(hour, minute, second)
+            .unwrap()
+            .assume_utc()
+    }
+
+    /// Encodes a DER element, using the long form for the length when needed
+    fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut element = vec![tag];
+        match contents.len() {
+            length @ 0..=0x7F => element.push(length as u8),
+            length @ 0x80..=0xFF => element.extend([0x81, length as u8]),
+            length => element.extend([0x82, (length >> 8) as u8, length as u8]),
+        }
+        element.extend(contents);
+        element
+    }
+
+    fn concat(elements: &[Vec<u8>]) -> Vec<u8> {
+        elements.concat()
+    }
+
+    fn name(common_name: &str) -> Vec<u8> {
+        der(
+            0x30,
+            &der(
+                0x31,
+                &der(
+                    0x30,
+                    &concat(&[
+                        der(0x06, &[0x55, 0x04, 0x03]),
+                        der(0x0C, common_name.as_bytes()),
+                    ]),
+                ),
+            ),
+        )
+    }
+
+    fn pkcs7() -> Vec<u8> {
+        let algorithm = der(
+            0x30,
+            &der(
+                0x06,
+                &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01],
+            ),
+        );
+        let certificate = der(
+            0x30,
+            &concat(&[
+                der(
+                    0x30,
+                    &concat(&[
+                        der(0xA0, &der(0x02, &[2])),
+                        der(0x02, &[0x12, 0x34]),
+                        algorithm.clone(),
+                        name("Example Code Signing CA"),
+                        der(0x30, &[]),
+                        name("Example Software Ltd"),
+                    ]),
+                ),
+                algorithm.clone(),
+            ]),
+        );
+        let signing_time = der(
+            0x30,
+            &concat(&[
+                der(
+                    0x06,
+                    &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x05],
+                ),
+                der(0x31, &der(0x17, b"240131120000Z")),
+            ]),
+        );
+        let counter_signature = der(
+            0x30,
+            &concat(&[
+                der(
+                    0x06,
+                    &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x09, 0x06],
+                ),
+                der(
+                    0x31,
+                    &der(
+                        0x30,
+                        &concat(&[
+                            der(0x02, &[1]),
+                            der(0x30, &concat(&[name("Timestamp CA"), der(0x02, &[1])])),
+                            algorithm.clone(),
+                            der(0xA0, &signing_time),
+                            algorithm.clone(),
+                            der(0x04, &[0]),
+                        ]),
+                    ),
+                ),
+            ]),
+        );
+        let signer_info = der(
+            0x30,
+            &concat(&[
+                der(0x02, &[1]),
+                der(
+                    0x30,
+                    &concat(&[name("Example Code Signing CA"), der(0x02, &[0x12, 0x34])]),
+                ),
+                algorithm.clone(),
+                algorithm.clone(),
+                der(0x04, &[0; 256]),
+                der(0xA1, &counter_signature),
+            ]),
+        );
+        let signed_data = der(
+            0x30,
+            &concat(&[
+                der(0x02, &[1]),
+                der(0x31, &algorithm),
+                der(
+                    0x30,
+                    &der(
+                        0x06,
+                        &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x02, 0x01, 0x04],
+                    ),
+                ),
+                der(0xA0, &certificate),
+                der(0x31, &signer_info),
+            ]),
+        );
+        der(
+            0x30,
+            &concat(&[
+                der(
+                    0x06,
+                    &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x07, 0x02],
+                ),
+                der(0xA0, &signed_data),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_signer() {
+        let signer = SignedData::parse(&pkcs7()).unwrap().signer().unwrap();
+        assert_eq!(Some("Example Software Ltd"), signer.subject.as_deref());
+        assert_eq!(Some("Example Code Signing CA"), signer.issuer.as_deref());
+        assert_eq!(
+            Some(utc(2024, Month::January, 31, 12, 0, 0)),
+            signer.timestamp
+        );
+    }
+
+    #[test]
+    fn test_unreadable_signature_is_signed() {
+        let authenticode = Authenticode::from_pkcs7(Some(&[0x30, 0x03, 0x02, 0x01, 0x01]));
+        assert!(authenticode.is_signed);
+        assert_eq!(None, authenticode.subject);
+        assert!(!Authenticode::from_pkcs7(None).is_signed);
+    }
+
+    #[test]
+    fn test_is_signed_by() {
+        let authenticode = Authenticode {
+            is_signed: true,
+            subject: Some("Mozilla Corporation".to_owned()),
+            ..Authenticode::default()
+        };
+        assert!(authenticode.is_signed_by("Mozilla"));
+        assert!(authenticode.is_signed_by("mozilla corporation"));
+        assert!(!authenticode.is_signed_by("Example Software"));
+        assert!(!Authenticode::default().is_signed_by("Mozilla"));
+
+        let authenticode = Authenticode {
+            is_signed: true,
+            subject: Some("Example Software Co., Ltd.".to_owned()),
+            ..Authenticode::default()
+        };
+        assert!(authenticode.is_signed_by("Example Software GmbH"));
+        assert!(authenticode.is_signed_by("Example-Software"));
+    }
+
+    #[test]
+    fn test_prefix_is_not_signed_by() {
+        let authenticode = Authenticode {
+            is_signed: true,
+            subject: Some("Googleplex Updates Ltd".to_owned()),
+            ..Authenticode::default()
+        };
+        assert!(!authenticode.is_signed_by("Google"));
+        assert!(!authenticode.is_signed_by("Google LLC"));
+        assert!(!Authenticode {
+            subject: Some("Google".to_owned()),
+            ..authenticode
+        }
+        .is_signed_by("Google Updates"));
+    }
+
+    #[test]
+    fn test_warning() {
+        let authenticode = Authenticode {
+            is_signed: true,
+            subject: Some("Example Software Ltd".to_owned()),
+            ..Authenticode::default()
+        };
+        assert_eq!(
+            None,
+            authenticode.warning("setup.exe", Some("Example Software"))
+        );
+        assert_eq!(None, authenticode.warning("setup.exe", None));
+        assert_eq!(
+            Some("setup.exe is signed by Example Software Ltd, which is different to the previous publisher, Other Corp".to_owned()),
+            authenticode.warning("setup.exe", Some("Other Corp"))
+        );
+        assert_eq!(
+            Some("setup.exe is not signed".to_owned()),
+            Authenticode::default().warning("setup.exe", Some("Other Corp"))
+        );
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            Some(utc(1999, Month::December, 31, 23, 59, 59)),
+            parse_time(UTC_TIME, b"991231235959Z")
+        );
+        assert_eq!(
+            Some(utc(2024, Month::February, 29, 8, 30, 15)),
+            parse_time(GENERALIZED_TIME, b"20240229083015.123Z")
+        );
+        assert_eq!(None, parse_time(UTC_TIME, b"241331000000Z"));
+    }
+}
@@ -0,0 +1,243 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use color_eyre::eyre::{bail, OptionExt, Result};
+use flate2::read::DeflateDecoder;
+use std::io::{BufRead, Cursor, Read, Seek, SeekFrom};
+
+const CABINET_SIGNATURE: &[u8; 4] = b"MSCF";
+
+const FLAG_PREV_CABINET: u16 = 0x0001;
+const FLAG_NEXT_CABINET: u16 = 0x0002;
+const FLAG_RESERVE_PRESENT: u16 = 0x0004;
+
+const COMPRESSION_MASK: u16 = 0x000F;
+const COMPRESSION_NONE: u16 = 0;
+const COMPRESSION_MSZIP: u16 = 1;
+
+const MSZIP_SIGNATURE: &[u8; 2] = b"CK";
+
+/// Each MSZIP block can refer back to the previous 32 KiB of output
+const MSZIP_WINDOW_SIZE: usize = 32 * 1024;
+
+struct Folder {
+    data_offset: u32,
+    data_count: u16,
+    compression: u16,
+}
+
+/// Extracts a file from a cabinet that is either uncompressed or compressed with MSZIP, which is
+/// what WiX uses for Burn's containers by default
+pub fn extract_file(cabinet: &[u8], name: &str) -> Result<Vec<u8>> {
+    let mut reader = Cursor::new(cabinet);
+    let mut signature = [0; 4];
+    reader.read_exact(&mut signature)?;
+    if &signature != CABINET_SIGNATURE {
+        bail!("The container is not a cabinet");
+    }
+    // Reserved, size of the cabinet, reserved
+    reader.seek(SeekFrom::Current(12))?;
+    let files_offset = reader.read_u32::<LittleEndian>()?;
+    // Reserved, minor version, major version
+    reader.seek(SeekFrom::Current(6))?;
+    let folder_count = reader.read_u16::<LittleEndian>()?;
+    let file_count = reader.read_u16::<LittleEndian>()?;
+    let flags = reader.read_u16::<LittleEndian>()?;
+    // Set ID, cabinet number
+    reader.seek(SeekFrom::Current(4))?;
+    let (folder_reserve, data_reserve) = if flags & FLAG_RESERVE_PRESENT != 0 {
+        let header_reserve = reader.read_u16::<LittleEndian>()?;
+        let folder_reserve = reader.read_u8()?;
+        let data_reserve = reader.read_u8()?;
+        reader.seek(SeekFrom::Current(i64::from(header_reserve)))?;
+        (folder_reserve, data_reserve)
+    } else {
+        (0, 0)
+    };
+    // The names of the previous and next cabinet and their disks
+    for flag in [FLAG_PREV_CABINET, FLAG_NEXT_CABINET] {
+        if flags & flag != 0 {
+            read_string(&mut reader)?;
+            read_string(&mut reader)?;
+        }
+    }
+
+    let mut folders = Vec::with_capacity(usize::from(folder_count));
+    for _ in 0..folder_count {
+        folders.push(Folder {
+            data_offset: reader.read_u32::<LittleEndian>()?,
+            data_count: reader.read_u16::<LittleEndian>()?,
+            compression: reader.read_u16::<LittleEndian>()?,
+        });
+        reader.seek(SeekFrom::Current(i64::from(folder_reserve)))?;
+    }
+
+    reader.set_position(u64::from(files_offset));
+    for _ in 0..file_count {
+        let size = reader.read_u32::<LittleEndian>()? as usize;
+        let offset = reader.read_u32::<LittleEndian>()? as usize;
+        let folder = reader.read_u16::<LittleEndian>()?;
+        // Date, time, attributes
+        reader.seek(SeekFrom::Current(6))?;
+        if read_string(&mut reader)? == name {
+            let folder = folders
+                .get(usize::from(folder))
+                .ok_or_eyre("The cabinet file is in a folder that doesn't exist")?;
+            let data = read_folder(cabinet, folder, data_reserve, offset + size)?;
+            return data
+                .get(offset..offset + size)
+                .map(<[u8]>::to_vec)
+                .ok_or_eyre("The cabinet file is truncated");
+        }
+    }
+    bail!("{name} was not found in the cabinet")
+}
+
+/// Reads the data blocks of a folder until at least `limit` bytes have been decompressed
+fn read_folder(cabinet: &[u8], folder: &Folder, data_reserve: u8, limit: usize) -> Result<Vec<u8>> {
+    let mut reader = Cursor::new(cabinet);
+    reader.set_position(u64::from(folder.data_offset));
+    let mut output = Vec::new();
+    for _ in 0..folder.data_count {
+        if output.len() >= limit {
+            break;
+        }
+        // Checksum
+        reader.seek(SeekFrom::Current(4))?;
+        let compressed_size = usize::from(reader.read_u16::<LittleEndian>()?);
+        let uncompressed_size = usize::from(reader.read_u16::<LittleEndian>()?);
+        reader.seek(SeekFrom::Current(i64::from(data_reserve)))?;
+        let start = reader.position() as usize;
+        let block = cabinet
+            .get(start..start + compressed_size)
+            .ok_or_eyre("The cabinet data is truncated")?;
+        reader.set_position((start + compressed_size) as u64);
+        match folder.compression & COMPRESSION_MASK {
+            COMPRESSION_NONE => output.extend_from_slice(block),
+            COMPRESSION_MSZIP => {
+                let block = block
+                    .strip_prefix(MSZIP_SIGNATURE)
+                    .ok_or_eyre("The MSZIP block has an invalid signature")?;
+                let window = &output[output.len().saturating_sub(MSZIP_WINDOW_SIZE)..];
+                let decompressed = inflate_with_window(block, window, uncompressed_size)?;
+                output.extend(decompressed);
+            }
+            compression => bail!("Cabinet compression type {compression} is not supported"),
+        }
+    }
+    Ok(output)
+}
+
+/// Inflates an MSZIP block by putting the previous output in a stored deflate block before it,
+/// which lets the block refer back to it without a decoder that supports preset dictionaries
+fn inflate_with_window(block: &[u8], window: &[u8], uncompressed_size: usize) -> Result<Vec<u8>> {
+    let window_size = window.len() as u16;
+    let mut stored_block = Vec::with_capacity(5 + window.len());
+    // Not the final block and not compressed
+    stored_block.push(0);
+    stored_block.extend(window_size.to_le_bytes());
+    stored_block.extend((!window_size).to_le_bytes());
+    stored_block.extend(window);
+
+    let mut output = Vec::with_capacity(window.len() + uncompressed_size);
+    DeflateDecoder::new(stored_block.as_slice().chain(block)).read_to_end(&mut output)?;
+    Ok(output.split_off(window.len()))
+}
+
+fn read_string(reader: &mut Cursor<&[u8]>) -> Result<String> {
+    let mut string = Vec::new();
+    reader.read_until(0, &mut string)?;
+    if string.pop() != Some(0) {
+        bail!("The cabinet is truncated");
+    }
+    Ok(String::from_utf8_lossy(&string).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::burn::cabinet::{extract_file, COMPRESSION_MSZIP, COMPRESSION_NONE};
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// Builds a cabinet with one folder that stores the files in blocks of 16 bytes
+    fn cabinet(files: &[(&str, &[u8])], compression: u16) -> Vec<u8> {
+        let contents = files
+            .iter()
+            .flat_map(|(_, data)| data.iter().copied())
+            .collect::<Vec<_>>();
+        let blocks = contents
+            .chunks(16)
+            .map(|chunk| {
+                let data = if compression == COMPRESSION_MSZIP {
+                    let mut encoder = DeflateEncoder::new(b"CK".to_vec(), Compression::default());
+                    encoder.write_all(chunk).unwrap();
+                    encoder.finish().unwrap()
+                } else {
+                    chunk.to_vec()
+                };
+                (data, chunk.len())
+            })
+            .collect::<Vec<_>>();
+
+        let header_size = 36;
+        let folder_size = 8;
+        let files_offset = header_size + folder_size;
+        let files_size = files
+            .iter()
+            .map(|(name, _)| 16 + name.len() + 1)
+            .sum::<usize>();
+        let data_offset = files_offset + files_size;
+
+        let mut cabinet = b"MSCF".to_vec();
+        cabinet.extend([0; 8]);
+        cabinet.extend([0; 4]);
+        cabinet.extend((files_offset as u32).to_le_bytes());
+        cabinet.extend([0; 4]);
+        cabinet.extend([3, 1]);
+        cabinet.extend(1_u16.to_le_bytes());
+        cabinet.extend((files.len() as u16).to_le_bytes());
+        cabinet.extend([0; 6]);
+        cabinet.extend((data_offset as u32).to_le_bytes());
+        cabinet.extend((blocks.len() as u16).to_le_bytes());
+        cabinet.extend(compression.to_le_bytes());
+        let mut offset = 0;
+        for (name, data) in files {
+            cabinet.extend((data.len() as u32).to_le_bytes());
+            cabinet.extend((offset as u32).to_le_bytes());
+            cabinet.extend([0; 8]);
+            cabinet.extend(name.as_bytes());
+            cabinet.push(0);
+            offset += data.len();
+        }
+        for (data, uncompressed_size) in blocks {
+            cabinet.extend([0; 4]);
+            cabinet.extend((data.len() as u16).to_le_bytes());
+            cabinet.extend((uncompressed_size as u16).to_le_bytes());
+            cabinet.extend(data);
+        }
+        cabinet
+    }
+
+    #[test]
+    fn test_extract_uncompressed_file() {
+        let cabinet = cabinet(
+            &[("0", b"<BurnManifest/>"), ("u0", b"BootstrapperCore.dll")],
+            COMPRESSION_NONE,
+        );
+        assert_eq!(
+            b"<BurnManifest/>",
+            extract_file(&cabinet, "0").unwrap().as_slice()
+        );
+        assert_eq!(
+            b"BootstrapperCore.dll",
+            extract_file(&cabinet, "u0").unwrap().as_slice()
+        );
+        assert!(extract_file(&cabinet, "u1").is_err());
+    }
+
+    #[test]
+    fn test_extract_mszip_file() {
+        let manifest = b"<BurnManifest><Registration Id=\"{00000000}\"/></BurnManifest>";
+        let cabinet = cabinet(&[("u0", b"thm.xml"), ("0", manifest)], COMPRESSION_MSZIP);
+        assert_eq!(manifest, extract_file(&cabinet, "0").unwrap().as_slice());
+    }
+}
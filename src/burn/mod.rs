@@ -0,0 +1,347 @@
+mod cabinet;
+
+use crate::manifests::installer_manifest::{AppsAndFeaturesEntry, Scope};
+use crate::types::architecture::Architecture;
+use crate::types::product_code::ProductCode;
+use crate::url_utils::find_architecture;
+use color_eyre::eyre::{bail, eyre, OptionExt, Result};
+use object::read::pe::{ImageNtHeaders, PeFile};
+use object::{LittleEndian, ReadRef, U32};
+use quick_xml::de::from_str;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use uuid::Uuid;
+
+const BURN_SECTION_NAME: &[u8; 8] = b".wixburn";
+const BURN_SECTION_MAGIC: u32 = 0x00F1_4300;
+
+/// Magic, version, bundle ID, stub size, original checksum, original signature offset, original
+/// signature size, container format and the container count
+const BURN_SECTION_HEADER_SIZE: usize = 4 + 4 + 16 + 4 + 4 + 4 + 4 + 4 + 4;
+
+/// The Burn manifest is always the first file of the UX container
+const BURN_MANIFEST_FILE_NAME: &str = "0";
+
+const YES: &str = "yes";
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
+pub struct Burn {
+    pub bundle_id: Uuid,
+    pub upgrade_code: Option<Uuid>,
+    pub version: Option<String>,
+    pub display_name: Option<String>,
+    pub display_version: Option<String>,
+    pub publisher: Option<String>,
+    pub per_machine: bool,
+    pub packages: Vec<BurnPackage>,
+}
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
+pub struct BurnPackage {
+    pub id: String,
+    pub package_type: BurnPackageType,
+    pub product_code: Option<String>,
+    pub upgrade_code: Option<String>,
+    pub version: Option<String>,
+    pub architecture: Option<Architecture>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BurnPackageType {
+    Exe,
+    Msi,
+    Msp,
+    Msu,
+    Bundle,
+}
+
+impl Burn {
+    pub fn new<'data, Pe, R>(pe: &PeFile<'data, Pe, R>, data: &[u8]) -> Result<Self>
+    where
+        Pe: ImageNtHeaders,
+        R: ReadRef<'data>,
+    {
+        let section = pe
+            .section_table()
+            .iter()
+            .find(|section| &section.name == BURN_SECTION_NAME)
+            .ok_or_eyre("No .wixburn section was found")?
+            .pe_data(pe.data())?;
+        let field = |offset: usize| {
+            section
+                .read_at::<U32<LittleEndian>>(offset as u64)
+                .map(|value| value.get(LittleEndian) as usize)
+                .map_err(|()| eyre!("The .wixburn section is truncated"))
+        };
+        if field(0)? != BURN_SECTION_MAGIC as usize {
+            bail!("The .wixburn section has an unknown magic number");
+        }
+        let bundle_id = Uuid::from_bytes_le(
+            section
+                .get(8..24)
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_eyre("The .wixburn section is truncated")?,
+        );
+        let stub_size = field(24)?;
+        if field(44)? == 0 {
+            bail!("The bundle has no containers");
+        }
+        let ux_container_size = field(BURN_SECTION_HEADER_SIZE)?;
+        let ux_container = data
+            .get(stub_size..stub_size + ux_container_size)
+            .ok_or_eyre("The UX container is outside of the file")?;
+
+        let manifest = cabinet::extract_file(ux_container, BURN_MANIFEST_FILE_NAME)?;
+        let manifest = from_str::<BurnManifest>(
+            String::from_utf8_lossy(&manifest).trim_start_matches('\u{FEFF}'),
+        )?;
+        Ok(Self::from_manifest(bundle_id, manifest))
+    }
+
+    fn from_manifest(bundle_id: Uuid, manifest: BurnManifest) -> Self {
+        let packages = manifest
+            .chain
+            .packages
+            .into_iter()
+            .filter_map(|item| {
+                let (package_type, package) = match item {
+                    ChainItem::ExePackage(package) => (BurnPackageType::Exe, package),
+                    ChainItem::MsiPackage(package) => (BurnPackageType::Msi, package),
+                    ChainItem::MspPackage(package) => (BurnPackageType::Msp, package),
+                    ChainItem::MsuPackage(package) => (BurnPackageType::Msu, package),
+                    ChainItem::BundlePackage(package) => (BurnPackageType::Bundle, package),
+                    ChainItem::Other => return None,
+                };
+                let architecture = if package.x64 == YES || package.win64 == YES {
+                    Some(Architecture::X64)
+                } else {
+                    package
+                        .payload_refs
+                        .iter()
+                        .filter_map(|payload_ref| {
+                            manifest
+                                .payloads
+                                .iter()
+                                .find(|payload| payload.id == payload_ref.id)
+                        })
+                        .find_map(|payload| find_architecture(&payload.file_path))
+                };
+                Some(BurnPackage {
+                    id: package.id,
+                    package_type,
+                    product_code: Some(package.product_code).filter(|code| !code.is_empty()),
+                    upgrade_code: Some(package.upgrade_code).filter(|code| !code.is_empty()),
+                    version: Some(package.version).filter(|version| !version.is_empty()),
+                    architecture,
+                })
+            })
+            .collect();
+        let registration = manifest.registration;
+        Self {
+            bundle_id: Uuid::parse_str(&registration.id).unwrap_or(bundle_id),
+            upgrade_code: manifest
+                .related_bundles
+                .iter()
+                .find(|related_bundle| related_bundle.action.eq_ignore_ascii_case("upgrade"))
+                .and_then(|related_bundle| Uuid::parse_str(&related_bundle.id).ok()),
+            version: Some(registration.version).filter(|version| !version.is_empty()),
+            display_name: Some(registration.arp.display_name).filter(|name| !name.is_empty()),
+            display_version: Some(registration.arp.display_version)
+                .filter(|version| !version.is_empty()),
+            publisher: Some(registration.arp.publisher).filter(|publisher| !publisher.is_empty()),
+            per_machine: registration.per_machine == YES,
+            packages,
+        }
+    }
+
+    /// Burn registers the bundle under its bundle ID in Apps and Features
+    pub fn product_code(&self) -> ProductCode {
+        ProductCode::from_uuid(&self.bundle_id)
+    }
+
+    pub const fn scope(&self) -> Scope {
+        if self.per_machine {
+            Scope::Machine
+        } else {
+            Scope::User
+        }
+    }
+
+    /// The bundle itself is always x86, so it's only x64 if every MSI package it chains is
+    pub fn architecture(&self) -> Architecture {
+        let mut msi_packages = self
+            .packages
+            .iter()
+            .filter(|package| package.package_type == BurnPackageType::Msi)
+            .peekable();
+        if msi_packages.peek().is_some()
+            && msi_packages.all(|package| package.architecture == Some(Architecture::X64))
+        {
+            Architecture::X64
+        } else {
+            Architecture::X86
+        }
+    }
+
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry {
+            display_name: self.display_name.clone(),
+            publisher: self.publisher.clone(),
+            display_version: self.display_version.clone(),
+            upgrade_code: self.upgrade_code,
+            ..AppsAndFeaturesEntry::default()
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct BurnManifest {
+    #[serde(rename = "RelatedBundle")]
+    related_bundles: Vec<RelatedBundle>,
+    registration: Registration,
+    chain: Chain,
+    #[serde(rename = "Payload")]
+    payloads: Vec<Payload>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RelatedBundle {
+    #[serde(rename = "@Id", alias = "@Code")]
+    id: String,
+    #[serde(rename = "@Action")]
+    action: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Registration {
+    #[serde(rename = "@Id", alias = "@Code")]
+    id: String,
+    #[serde(rename = "@Version")]
+    version: String,
+    #[serde(rename = "@PerMachine")]
+    per_machine: String,
+    #[serde(rename = "Arp")]
+    arp: Arp,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Arp {
+    #[serde(rename = "@DisplayName")]
+    display_name: String,
+    #[serde(rename = "@DisplayVersion")]
+    display_version: String,
+    #[serde(rename = "@Publisher")]
+    publisher: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Chain {
+    #[serde(rename = "$value")]
+    packages: Vec<ChainItem>,
+}
+
+#[derive(Deserialize)]
+enum ChainItem {
+    ExePackage(Package),
+    MsiPackage(Package),
+    MspPackage(Package),
+    MsuPackage(Package),
+    BundlePackage(Package),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Package {
+    #[serde(rename = "@Id")]
+    id: String,
+    #[serde(rename = "@ProductCode")]
+    product_code: String,
+    #[serde(rename = "@UpgradeCode")]
+    upgrade_code: String,
+    #[serde(rename = "@Version")]
+    version: String,
+    #[serde(rename = "@x64")]
+    x64: String,
+    #[serde(rename = "@Win64")]
+    win64: String,
+    #[serde(rename = "PayloadRef")]
+    payload_refs: Vec<PayloadRef>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct PayloadRef {
+    #[serde(rename = "@Id")]
+    id: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Payload {
+    #[serde(rename = "@Id")]
+    id: String,
+    #[serde(rename = "@FilePath")]
+    file_path: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::burn::{Burn, BurnManifest, BurnPackageType};
+    use crate::manifests::installer_manifest::Scope;
+    use crate::types::architecture::Architecture;
+    use quick_xml::de::from_str;
+    use uuid::Uuid;
+
+    const MANIFEST: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<BurnManifest xmlns="http://schemas.microsoft.com/wix/2008/Burn">
+    <Log PathVariable="WixBundleLog" Prefix="App" Extension=".log" />
+    <RelatedBundle Id="{6A2E6A6B-0B6F-4A9B-9F5B-9A1E1C7A2D10}" Action="Upgrade" />
+    <Variable Id="WixBundleName" Type="string" Value="App" Hidden="no" Persisted="yes" />
+    <UX><Payload Id="thm.xml" FilePath="thm.xml" Packaging="embedded" SourcePath="u0" /></UX>
+    <Registration Id="{3C6BB2B7-8F5C-4C4E-8A51-6F8D5B1A4C21}" ExecutableName="App.exe" PerMachine="yes" Tag="" Version="1.2.3.0" ProviderKey="{3C6BB2B7-8F5C-4C4E-8A51-6F8D5B1A4C21}">
+        <Arp Register="yes" DisplayName="App" DisplayVersion="1.2.3.0" Publisher="Example" />
+    </Registration>
+    <Chain>
+        <ExePackage Id="vcredist" Cache="yes" PerMachine="yes" Vital="yes"><PayloadRef Id="vcredist" /></ExePackage>
+        <RollbackBoundary Id="WixDefaultBoundary" Vital="yes" Transaction="no" />
+        <MsiPackage Id="App_x64" ProductCode="{0F8E3B4C-5A9D-4E1B-9C6F-2D7A8B3E1F40}" Language="1033" Version="1.2.3.0" UpgradeCode="{B1C2D3E4-F5A6-4B7C-8D9E-0F1A2B3C4D5E}">
+            <MsiProperty Id="ARPSYSTEMCOMPONENT" Value="1" />
+            <Provides Key="{0F8E3B4C-5A9D-4E1B-9C6F-2D7A8B3E1F40}" Version="1.2.3.0" />
+            <PayloadRef Id="App_x64" />
+        </MsiPackage>
+    </Chain>
+    <Payload Id="vcredist" FilePath="vc_redist.x64.exe" Packaging="external" />
+    <Payload Id="App_x64" FilePath="App-x64.msi" Packaging="embedded" Container="WixAttachedContainer" />
+</BurnManifest>"#;
+
+    #[test]
+    fn test_burn_manifest() {
+        let burn = Burn::from_manifest(Uuid::nil(), from_str::<BurnManifest>(MANIFEST).unwrap());
+        assert_eq!(
+            "{3C6BB2B7-8F5C-4C4E-8A51-6F8D5B1A4C21}",
+            burn.product_code().as_str()
+        );
+        assert_eq!(
+            Some(Uuid::parse_str("6A2E6A6B-0B6F-4A9B-9F5B-9A1E1C7A2D10").unwrap()),
+            burn.upgrade_code
+        );
+        assert_eq!(Some("App"), burn.display_name.as_deref());
+        assert_eq!(Some("Example"), burn.publisher.as_deref());
+        assert_eq!(Scope::Machine, burn.scope());
+        assert_eq!(2, burn.packages.len());
+        assert_eq!(BurnPackageType::Exe, burn.packages[0].package_type);
+        assert_eq!(Some(Architecture::X64), burn.packages[0].architecture);
+        assert_eq!(BurnPackageType::Msi, burn.packages[1].package_type);
+        assert_eq!(Some(Architecture::X64), burn.packages[1].architecture);
+        assert_eq!(Architecture::X64, burn.architecture());
+    }
+}
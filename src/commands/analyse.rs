@@ -0,0 +1,56 @@
+use crate::file_analyser::FileAnalyser;
+use crate::url_utils::find_architecture;
+use clap::{Parser, ValueEnum};
+use color_eyre::eyre::{Result, WrapErr};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+
+/// Analyse a local installer file without downloading it
+#[derive(Parser)]
+pub struct Analyse {
+    /// Path to the installer file
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    path: PathBuf,
+
+    /// Format to print the analysis in
+    #[arg(long, value_enum, default_value_t)]
+    format: AnalysisFormat,
+}
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum AnalysisFormat {
+    #[default]
+    Yaml,
+    Json,
+}
+
+impl Analyse {
+    pub fn run(self) -> Result<()> {
+        let mut file = File::open(&self.path)
+            .wrap_err_with(|| format!("Failed to open {}", self.path.display()))?;
+        let file_name = self
+            .path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy())
+            .unwrap_or_default();
+        let mut file_analyser = FileAnalyser::new(&file, Cow::Borrowed(&file_name), false)?;
+        file_analyser.architecture =
+            find_architecture(&file_name).unwrap_or(file_analyser.architecture);
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)?;
+        file_analyser.installer_sha_256 = base16ct::upper::encode_string(&hasher.finalize());
+
+        let stdout = io::stdout().lock();
+        match self.format {
+            AnalysisFormat::Yaml => serde_yaml::to_writer(stdout, &file_analyser)?,
+            AnalysisFormat::Json => {
+                serde_json::to_writer_pretty(stdout, &file_analyser)?;
+                println!();
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::credential::handle_token;
 use crate::github::github_client::GitHub;
 use crate::github::graphql::get_pull_request_from_branch::PullRequestState;
+use crate::github::repository::GitHubRepository;
 use clap::Parser;
 use color_eyre::Result;
 use crossterm::style::Stylize;
@@ -30,12 +31,15 @@ pub struct Cleanup {
 }
 
 impl Cleanup {
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
         let token = handle_token(self.token).await?;
-        let github = GitHub::new(token)?;
+        let github = GitHub::new(token, repository)?;
 
         // Get all winget-pkgs branches from the user's fork except the default one
-        let (branches, default_branch) = github.get_branches(&github.get_username().await?).await?;
+        let branches = github.get_branches(&github.get_username().await?).await?;
+
+        // Pull requests from the fork are made to the default branch of the upstream repository
+        let default_branch = github.get_winget_pkgs(None).await?.default_branch_name;
 
         let merge_state = match (self.only_merged, self.only_closed) {
             (true, false) => "merged",
@@ -0,0 +1,25 @@
+use crate::config::{get_value, path, read_table, split_key};
+use clap::Parser;
+use color_eyre::eyre::{eyre, Result};
+use toml::Value;
+
+/// Print the value of a configuration key
+#[derive(Parser)]
+pub struct GetConfig {
+    /// The key to print, such as concurrent_downloads or packages."Package.Identifier".installer_switches.Silent
+    key: String,
+}
+
+impl GetConfig {
+    pub fn run(self) -> Result<()> {
+        let table = read_table(&path()?)?;
+        let value = get_value(&table, &split_key(&self.key)?)
+            .ok_or_else(|| eyre!("{} has not been set", self.key))?;
+        match value {
+            Value::String(value) => println!("{value}"),
+            Value::Table(table) => print!("{table}"),
+            value => println!("{value}"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::config::{flatten, path, read_table};
+use clap::Parser;
+use color_eyre::eyre::Result;
+use crossterm::style::Stylize;
+use std::io;
+use std::io::Write;
+
+/// List every configuration key that has been set
+#[derive(Parser)]
+pub struct ListConfig;
+
+impl ListConfig {
+    pub fn run(self) -> Result<()> {
+        let path = path()?;
+        let table = read_table(&path)?;
+        let entries = flatten(&table);
+        if entries.is_empty() {
+            println!("No configuration has been set in {}", path.display());
+            return Ok(());
+        }
+
+        let mut stdout_lock = io::stdout().lock();
+        for (key, value) in entries {
+            writeln!(stdout_lock, "{} = {value}", key.blue())?;
+        }
+        Ok(())
+    }
+}
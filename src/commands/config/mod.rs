@@ -0,0 +1,25 @@
+pub mod get;
+pub mod list;
+pub mod set;
+pub mod unset;
+
+use crate::commands::config::get::GetConfig;
+use crate::commands::config::list::ListConfig;
+use crate::commands::config::set::SetConfig;
+use crate::commands::config::unset::UnsetConfig;
+use clap::{Args, Subcommand};
+
+/// Get, set or list values in the configuration file
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    Get(GetConfig),
+    Set(SetConfig),
+    Unset(UnsetConfig),
+    List(ListConfig),
+}
@@ -0,0 +1,30 @@
+use crate::config::{parse_value, path, read_table, set_value, split_key, write_table};
+use clap::Parser;
+use color_eyre::eyre::Result;
+use crossterm::style::Stylize;
+
+/// Set the value of a configuration key
+#[derive(Parser)]
+pub struct SetConfig {
+    /// The key to set, such as concurrent_downloads or packages."Package.Identifier".installer_switches.Silent
+    key: String,
+
+    /// The value to set the key to
+    value: String,
+}
+
+impl SetConfig {
+    pub fn run(self) -> Result<()> {
+        let path = path()?;
+        let mut table = read_table(&path)?;
+        set_value(&mut table, &split_key(&self.key)?, parse_value(&self.value))?;
+        write_table(&path, table)?;
+        println!(
+            "{} set {} in {}",
+            "Successfully".green(),
+            self.key.blue(),
+            path.display()
+        );
+        Ok(())
+    }
+}
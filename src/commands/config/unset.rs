@@ -0,0 +1,29 @@
+use crate::config::{path, read_table, remove_value, split_key, write_table};
+use clap::Parser;
+use color_eyre::eyre::{bail, Result};
+use crossterm::style::Stylize;
+
+/// Remove a configuration key
+#[derive(Parser)]
+pub struct UnsetConfig {
+    /// The key to remove
+    key: String,
+}
+
+impl UnsetConfig {
+    pub fn run(self) -> Result<()> {
+        let path = path()?;
+        let mut table = read_table(&path)?;
+        if remove_value(&mut table, &split_key(&self.key)?).is_none() {
+            bail!("{} has not been set", self.key);
+        }
+        write_table(&path, table)?;
+        println!(
+            "{} removed {} from {}",
+            "Successfully".green(),
+            self.key.blue(),
+            path.display()
+        );
+        Ok(())
+    }
+}
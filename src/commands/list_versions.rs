@@ -1,13 +1,15 @@
 use crate::credential::handle_token;
 use crate::github::github_client::GitHub;
-use crate::github::github_client::WINGET_PKGS_FULL_NAME;
-use crate::github::utils::get_package_path;
+use crate::github::repository::GitHubRepository;
+use crate::storage::local::LocalRepository;
+use crate::storage::Storage;
 use crate::types::package_identifier::PackageIdentifier;
 use clap::{Args, Parser};
 use color_eyre::eyre::WrapErr;
 use color_eyre::Result;
 use std::io;
 use std::io::Write;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct ListVersions {
@@ -17,6 +19,10 @@ pub struct ListVersions {
     #[command(flatten)]
     output_type: OutputType,
 
+    /// Local clone of winget-pkgs to read from instead of GitHub
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
     /// GitHub personal access token with the public_repo
     #[arg(short, long, env = "GITHUB_TOKEN")]
     token: Option<String>,
@@ -39,19 +45,21 @@ struct OutputType {
 }
 
 impl ListVersions {
-    pub async fn run(self) -> Result<()> {
-        let token = handle_token(self.token).await?;
-        let github = GitHub::new(token)?;
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        match &self.local_repository {
+            Some(path) => self.list_versions(&LocalRepository::open(path)?).await,
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                self.list_versions(&GitHub::new(token, repository)?).await
+            }
+        }
+    }
 
-        let versions = github
-            .get_versions(&get_package_path(&self.package_identifier, None))
+    async fn list_versions(&self, storage: &impl Storage) -> Result<()> {
+        let versions = storage
+            .get_versions(&self.package_identifier)
             .await
-            .wrap_err_with(|| {
-                format!(
-                    "{} does not exist in {WINGET_PKGS_FULL_NAME}",
-                    self.package_identifier
-                )
-            })?;
+            .wrap_err_with(|| format!("{} does not exist in {storage}", self.package_identifier))?;
 
         let mut stdout_lock = io::stdout().lock();
         match (
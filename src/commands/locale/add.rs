@@ -0,0 +1,158 @@
+use crate::commands::locale::LocaleAnswers;
+use crate::credential::handle_token;
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
+use crate::github::utils::{get_commit_title, get_package_path, get_pull_request_body};
+use crate::manifest::{build_manifest_string, print_changes, Manifest};
+use crate::manifest_diff::diff_manifests;
+use crate::prompts::prompt::required_prompt;
+use crate::prompts::{disable_prompts, prompts_disabled};
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage};
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::update_state::UpdateState;
+use clap::Parser;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use indicatif::ProgressBar;
+use inquire::Confirm;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Add a locale to an existing version
+#[derive(Parser)]
+pub struct AddLocale {
+    #[arg(short = 'i', long = "identifier")]
+    package_identifier: PackageIdentifier,
+
+    #[arg(short = 'v', long = "version")]
+    package_version: PackageVersion,
+
+    #[command(flatten)]
+    answers: LocaleAnswers,
+
+    /// Leave out values that weren't given as arguments instead of prompting for them
+    #[arg(long)]
+    no_prompt: bool,
+
+    /// Automatically submit a pull request
+    #[arg(short, long)]
+    submit: bool,
+
+    /// Local clone of winget-pkgs to read from and commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
+    /// GitHub personal access token with the public_repo and read_org scope
+    #[arg(short, long, env = "GITHUB_TOKEN")]
+    token: Option<String>,
+}
+
+impl AddLocale {
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        match self.local_repository.clone() {
+            Some(path) => self.add_locale(&LocalRepository::open(&path)?).await,
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                self.add_locale(&GitHub::new(token, repository)?).await
+            }
+        }
+    }
+
+    async fn add_locale(mut self, storage: &impl Storage) -> Result<()> {
+        if self.no_prompt {
+            disable_prompts();
+        }
+        let versions = storage
+            .get_versions(&self.package_identifier)
+            .await
+            .wrap_err_with(|| format!("{} does not exist in {storage}", self.package_identifier))?;
+        if !versions.contains(&self.package_version) {
+            bail!(
+                "{} version {} does not exist in {storage}",
+                self.package_identifier,
+                self.package_version,
+            );
+        }
+
+        let manifests = storage
+            .get_manifests(&self.package_identifier, &self.package_version)
+            .await?;
+        let package_locale = required_prompt(self.answers.package_locale.take())?;
+        if manifests.default_locale_manifest.package_locale == package_locale
+            || manifests
+                .locale_manifests
+                .iter()
+                .any(|locale_manifest| locale_manifest.package_locale == package_locale)
+        {
+            bail!(
+                "{} version {} already has a {package_locale} locale",
+                self.package_identifier,
+                self.package_version
+            );
+        }
+        let locale_manifest = LocaleAnswers {
+            package_locale: Some(package_locale.clone()),
+            ..self.answers
+        }
+        .prompt(&self.package_identifier, &self.package_version)?;
+
+        let changes = vec![(
+            format!(
+                "{}/{}.locale.{package_locale}.yaml",
+                get_package_path(&self.package_identifier, Some(&self.package_version)),
+                self.package_identifier
+            ),
+            build_manifest_string(&Manifest::Locale(&locale_manifest))?,
+        )];
+        print_changes(&changes);
+        let diff = diff_manifests(&[], &changes)?;
+
+        let should_submit = if self.submit {
+            true
+        } else if prompts_disabled() {
+            false
+        } else {
+            Confirm::new(&format!(
+                "Would you like to submit the {package_locale} locale of {} {} to {storage}?",
+                self.package_identifier, self.package_version
+            ))
+            .prompt()?
+        };
+        if !should_submit {
+            return Ok(());
+        }
+
+        let pr_progress = ProgressBar::new_spinner().with_message(format!(
+            "Submitting the {package_locale} locale of {} version {} to {storage}",
+            self.package_identifier, self.package_version
+        ));
+        pr_progress.enable_steady_tick(Duration::from_millis(50));
+
+        let commit_title = format!(
+            "{} ({package_locale})",
+            get_commit_title(
+                &self.package_identifier,
+                &self.package_version,
+                &UpdateState::AddLocale
+            )
+        );
+        let submission = storage
+            .commit_changes(Changes {
+                identifier: &self.package_identifier,
+                version: &self.package_version,
+                title: &commit_title,
+                body: &get_pull_request_body(&diff),
+                additions: &changes,
+                deleted_directory: None,
+                deleted_files: &[],
+            })
+            .await?;
+
+        pr_progress.finish_and_clear();
+
+        submission.print(storage);
+
+        Ok(())
+    }
+}
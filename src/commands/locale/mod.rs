@@ -0,0 +1,108 @@
+pub mod add;
+pub mod remove;
+
+use crate::commands::locale::add::AddLocale;
+use crate::commands::locale::remove::RemoveLocale;
+use crate::manifests::locale_manifest::LocaleManifest;
+use crate::prompts::list_prompt::list_prompt;
+use crate::prompts::prompt::{optional_prompt, required_prompt};
+use crate::prompts::prompts_disabled;
+use crate::types::description::Description;
+use crate::types::language_tag::LanguageTag;
+use crate::types::manifest_type::ManifestType;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use crate::types::short_description::ShortDescription;
+use crate::types::tag::Tag;
+use crate::types::urls::license_url::LicenseUrl;
+use crate::types::urls::package_url::PackageUrl;
+use crate::types::urls::publisher_url::PublisherUrl;
+use crate::types::urls::release_notes_url::ReleaseNotesUrl;
+use clap::{Args, Subcommand};
+use color_eyre::eyre::Result;
+use crossterm::style::Stylize;
+use serde::Deserialize;
+
+/// Add or remove additional locales of an existing version
+#[derive(Args)]
+pub struct LocaleArgs {
+    #[command(subcommand)]
+    pub command: LocaleCommands,
+}
+
+// Boxed to store on the heap instead as both commands are large structs
+#[derive(Subcommand)]
+pub enum LocaleCommands {
+    Add(Box<AddLocale>),
+    Remove(Box<RemoveLocale>),
+}
+
+// The values of an additional locale, from arguments or a file
+#[derive(Args, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "PascalCase")]
+pub struct LocaleAnswers {
+    /// The locale to add, such as de-DE
+    #[arg(long = "locale")]
+    pub package_locale: Option<LanguageTag>,
+
+    #[arg(long)]
+    publisher_url: Option<PublisherUrl>,
+
+    #[arg(long)]
+    package_url: Option<PackageUrl>,
+
+    #[arg(long)]
+    license_url: Option<LicenseUrl>,
+
+    #[arg(long)]
+    short_description: Option<ShortDescription>,
+
+    #[arg(long)]
+    description: Option<Description>,
+
+    #[arg(long, num_args = 1..)]
+    tags: Vec<Tag>,
+
+    #[arg(long)]
+    release_notes: Option<ReleaseNotes>,
+
+    #[arg(long)]
+    release_notes_url: Option<ReleaseNotesUrl>,
+}
+
+impl LocaleAnswers {
+    pub fn new(package_locale: LanguageTag) -> Self {
+        Self {
+            package_locale: Some(package_locale),
+            ..Self::default()
+        }
+    }
+
+    /// Creates the locale manifest, prompting for any values that weren't given
+    pub fn prompt(
+        self,
+        package_identifier: &PackageIdentifier,
+        package_version: &PackageVersion,
+    ) -> Result<LocaleManifest> {
+        let package_locale = required_prompt(self.package_locale)?;
+        if !prompts_disabled() {
+            println!("{}", format!("{package_locale} locale").bold());
+        }
+        Ok(LocaleManifest {
+            package_identifier: package_identifier.clone(),
+            package_version: package_version.clone(),
+            package_locale,
+            publisher_url: optional_prompt(self.publisher_url)?,
+            package_url: optional_prompt(self.package_url)?,
+            license_url: optional_prompt(self.license_url)?,
+            short_description: optional_prompt(self.short_description)?,
+            description: optional_prompt(self.description)?,
+            tags: list_prompt(self.tags)?,
+            release_notes: optional_prompt(self.release_notes)?,
+            release_notes_url: optional_prompt(self.release_notes_url)?,
+            manifest_type: ManifestType::Locale,
+            ..LocaleManifest::default()
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use crate::credential::handle_token;
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
+use crate::github::utils::{get_commit_title, get_package_path, get_pull_request_body};
+use crate::manifest_diff::{diff_manifests, print_diff, DiffFormat};
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage};
+use crate::types::language_tag::LanguageTag;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::update_state::UpdateState;
+use clap::Parser;
+use color_eyre::eyre::{bail, OptionExt, Result, WrapErr};
+use inquire::Confirm;
+use std::path::PathBuf;
+
+/// Remove a locale from an existing version
+#[derive(Parser)]
+pub struct RemoveLocale {
+    #[arg(short = 'i', long = "identifier")]
+    package_identifier: PackageIdentifier,
+
+    #[arg(short = 'v', long = "version")]
+    package_version: PackageVersion,
+
+    /// The locale to remove, such as de-DE
+    #[arg(long)]
+    locale: LanguageTag,
+
+    /// Automatically submit a pull request
+    #[arg(short, long)]
+    submit: bool,
+
+    /// Local clone of winget-pkgs to read from and commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
+    /// GitHub personal access token with the public_repo and read_org scope
+    #[arg(short, long, env = "GITHUB_TOKEN")]
+    token: Option<String>,
+}
+
+impl RemoveLocale {
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        match self.local_repository.clone() {
+            Some(path) => self.remove_locale(&LocalRepository::open(&path)?).await,
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                self.remove_locale(&GitHub::new(token, repository)?).await
+            }
+        }
+    }
+
+    async fn remove_locale(self, storage: &impl Storage) -> Result<()> {
+        let versions = storage
+            .get_versions(&self.package_identifier)
+            .await
+            .wrap_err_with(|| format!("{} does not exist in {storage}", self.package_identifier))?;
+        if !versions.contains(&self.package_version) {
+            bail!(
+                "{} version {} does not exist in {storage}",
+                self.package_identifier,
+                self.package_version,
+            );
+        }
+
+        let manifests = storage
+            .get_manifests(&self.package_identifier, &self.package_version)
+            .await?;
+        if manifests.default_locale_manifest.package_locale == self.locale {
+            bail!(
+                "{} is the default locale of {} version {} so it can't be removed",
+                self.locale,
+                self.package_identifier,
+                self.package_version
+            );
+        }
+        let locale_manifest = manifests
+            .locale_manifests
+            .iter()
+            .find(|locale_manifest| locale_manifest.package_locale == self.locale)
+            .ok_or_eyre(format!(
+                "{} version {} does not have a {} locale",
+                self.package_identifier, self.package_version, self.locale
+            ))?;
+        let diff = diff_manifests(&[serde_yaml::to_value(locale_manifest)?], &[])?;
+        print_diff(&diff, DiffFormat::Unified)?;
+
+        let should_submit = if self.submit {
+            true
+        } else {
+            Confirm::new(&format!(
+                "Would you like to submit the removal of the {} locale of {} {} to {storage}?",
+                self.locale, self.package_identifier, self.package_version
+            ))
+            .prompt()?
+        };
+        if !should_submit {
+            return Ok(());
+        }
+
+        let commit_title = format!(
+            "{} ({})",
+            get_commit_title(
+                &self.package_identifier,
+                &self.package_version,
+                &UpdateState::RemoveLocale
+            ),
+            self.locale
+        );
+        let submission = storage
+            .commit_changes(Changes {
+                identifier: &self.package_identifier,
+                version: &self.package_version,
+                title: &commit_title,
+                body: &get_pull_request_body(&diff),
+                additions: &[],
+                deleted_directory: None,
+                deleted_files: &[format!(
+                    "{}/{}.locale.{}.yaml",
+                    get_package_path(&self.package_identifier, Some(&self.package_version)),
+                    self.package_identifier,
+                    self.locale
+                )],
+            })
+            .await?;
+
+        submission.print(storage);
+
+        Ok(())
+    }
+}
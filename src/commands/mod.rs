@@ -1,6 +1,11 @@
+pub mod analyse;
 pub mod cleanup;
+pub mod config;
 pub mod list_versions;
+pub mod locale;
 pub mod new_version;
 pub mod remove_version;
+pub mod submit;
 pub mod token;
 pub mod update_version;
+pub mod validate;
@@ -1,12 +1,17 @@
+use crate::commands::locale::LocaleAnswers;
 use crate::commands::update_version::reorder_keys;
+use crate::config::Config;
 use crate::credential::{get_default_headers, handle_token};
 use crate::download_file::{download_urls, process_files};
-use crate::github::github_client::{GitHub, WINGET_PKGS_FULL_NAME};
-use crate::github::graphql::create_commit::{Base64String, FileAddition};
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
 use crate::github::utils::{
-    get_branch_name, get_commit_title, get_package_path, get_pull_request_body,
+    get_commit_title, get_package_path, get_pull_request_body, GitHubReleaseAsset,
 };
-use crate::manifest::{build_manifest_string, print_changes, Manifest};
+use crate::manifest::{
+    build_manifest_string, print_changes, write_manifests, Manifest, OutputLayout,
+};
+use crate::manifest_diff::{diff_manifests, manifest_values, print_diff, DiffFormat};
 use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
 use crate::manifests::installer_manifest::{
     InstallModes, Installer, InstallerManifest, InstallerSwitches, UpgradeBehavior,
@@ -16,6 +21,10 @@ use crate::manifests::version_manifest::VersionManifest;
 use crate::prompts::list_prompt::list_prompt;
 use crate::prompts::multi_prompt::{check_prompt, radio_prompt};
 use crate::prompts::prompt::{optional_prompt, required_prompt};
+use crate::prompts::{disable_prompts, missing_value, prompts_disabled};
+use crate::release_notes::{ReleaseNotesKind, ReleaseNotesSource};
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage};
 use crate::types::author::Author;
 use crate::types::command::Command;
 use crate::types::copyright::Copyright;
@@ -46,26 +55,85 @@ use crate::types::urls::release_notes_url::ReleaseNotesUrl;
 use crate::types::urls::url::Url;
 use crate::update_state::UpdateState;
 use crate::url_utils::find_scope;
-use base64ct::Encoding;
-use clap::Parser;
-use color_eyre::eyre::Result;
+use clap::{Args, Parser};
+use color_eyre::eyre::{bail, Result, WrapErr};
 use crossterm::style::Stylize;
 use futures_util::{stream, StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar};
 use inquire::{Confirm, CustomType};
 use ordinal::Ordinal;
 use reqwest::Client;
+use serde::Deserialize;
 use std::collections::BTreeSet;
 use std::mem;
 use std::num::NonZeroU8;
 use std::ops::Not;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::Duration;
 use strum::IntoEnumIterator;
 use tokio::fs;
 
 #[derive(Parser)]
 pub struct NewVersion {
+    #[command(flatten)]
+    answers: Answers,
+
+    /// YAML file with answers to use instead of prompting, such as `PackageIdentifier` and `Tags`.
+    /// Arguments take priority over the file.
+    #[arg(long = "answers", value_hint = clap::ValueHint::FilePath)]
+    answers_file: Option<PathBuf>,
+
+    /// Fail if a required value is missing instead of prompting for it
+    #[arg(long)]
+    no_prompt: bool,
+
+    /// Number of installers to download at the same time [default: 2]
+    #[arg(long)]
+    concurrent_downloads: Option<NonZeroU8>,
+
+    /// Automatically submit a pull request
+    #[arg(short, long)]
+    submit: bool,
+
+    /// How to show the changes from the previous version
+    #[arg(long, value_enum, default_value_t)]
+    diff_format: DiffFormat,
+
+    /// Changelog or release page to read the release notes from instead of the GitHub release,
+    /// such as a GitLab project or a CHANGELOG.md
+    #[arg(long)]
+    release_notes_source: Option<Url>,
+
+    /// How to read the release notes source. Detected from the URL if omitted
+    #[arg(long, value_enum, requires = "release_notes_source")]
+    release_notes_kind: Option<ReleaseNotesKind>,
+
+    /// Replace the previous version's repository metadata, such as tags, with the values from GitHub
+    #[arg(long)]
+    refresh_metadata: bool,
+
+    /// Directory to output the manifests to
+    #[arg(short, long, env = "OUTPUT_DIRECTORY", value_hint = clap::ValueHint::DirPath)]
+    output: Option<PathBuf>,
+
+    /// How to arrange the manifests in the output directory
+    #[arg(long, value_enum)]
+    output_layout: Option<OutputLayout>,
+
+    /// Local clone of winget-pkgs to read from and commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
+    /// GitHub personal access token with the public_repo and read_org scope
+    #[arg(short, long, env = "GITHUB_TOKEN")]
+    token: Option<String>,
+}
+
+// Every value that `komac new` would otherwise prompt for, from arguments or an answers file
+#[derive(Args, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "PascalCase")]
+struct Answers {
     /// The package's unique identifier
     #[arg(short = 'i', long = "identifier")]
     package_identifier: Option<PackageIdentifier>,
@@ -78,6 +146,42 @@ pub struct NewVersion {
     #[arg(short, long, num_args=1..)]
     urls: Vec<Url>,
 
+    /// Whether EXE installers are portable
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    portable: Option<bool>,
+
+    #[arg(long)]
+    silent_switch: Option<SilentSwitch>,
+
+    #[arg(long)]
+    silent_with_progress_switch: Option<SilentWithProgressSwitch>,
+
+    #[arg(long)]
+    custom_switch: Option<CustomSwitch>,
+
+    /// The files to use from zips that contain multiple installers. Each zip uses the paths it
+    /// contains
+    #[arg(long, num_args = 1..)]
+    nested_installer_files: Vec<String>,
+
+    #[arg(long, value_enum, num_args = 1..)]
+    install_modes: Vec<InstallModes>,
+
+    #[arg(long, num_args = 1..)]
+    installer_success_codes: Vec<InstallerSuccessCode>,
+
+    #[arg(long, value_enum)]
+    upgrade_behavior: Option<UpgradeBehavior>,
+
+    #[arg(long, num_args = 1..)]
+    commands: Vec<Command>,
+
+    #[arg(long, num_args = 1..)]
+    protocols: Vec<Protocol>,
+
+    #[arg(long, num_args = 1..)]
+    file_extensions: Vec<FileExtension>,
+
     #[arg(long)]
     package_locale: Option<LanguageTag>,
 
@@ -117,40 +221,111 @@ pub struct NewVersion {
     #[arg(long)]
     description: Option<Description>,
 
+    #[arg(long, num_args = 1..)]
+    tags: Vec<Tag>,
+
     #[arg(long)]
     release_notes_url: Option<ReleaseNotesUrl>,
 
-    /// Number of installers to download at the same time
-    #[arg(long, default_value_t = NonZeroU8::new(2).unwrap())]
-    concurrent_downloads: NonZeroU8,
+    /// Additional locales to create locale manifests for, such as de-DE
+    #[arg(long, num_args = 1..)]
+    #[serde(skip)]
+    add_locale: Vec<LanguageTag>,
 
-    /// Automatically submit a pull request
-    #[arg(short, long)]
-    submit: bool,
+    #[arg(skip)]
+    locales: Vec<LocaleAnswers>,
+}
 
-    /// Directory to output the manifests to
-    #[arg(short, long, env = "OUTPUT_DIRECTORY", value_hint = clap::ValueHint::DirPath)]
-    output: Option<PathBuf>,
+impl Answers {
+    /// Fills in the values that weren't given as arguments from an answers file
+    fn or(self, file: Self) -> Self {
+        fn or_vec<T>(values: Vec<T>, file: Vec<T>) -> Vec<T> {
+            if values.is_empty() {
+                file
+            } else {
+                values
+            }
+        }
 
-    /// GitHub personal access token with the public_repo and read_org scope
-    #[arg(short, long, env = "GITHUB_TOKEN")]
-    token: Option<String>,
+        Self {
+            package_identifier: self.package_identifier.or(file.package_identifier),
+            package_version: self.package_version.or(file.package_version),
+            urls: or_vec(self.urls, file.urls),
+            portable: self.portable.or(file.portable),
+            silent_switch: self.silent_switch.or(file.silent_switch),
+            silent_with_progress_switch: self
+                .silent_with_progress_switch
+                .or(file.silent_with_progress_switch),
+            custom_switch: self.custom_switch.or(file.custom_switch),
+            nested_installer_files: or_vec(
+                self.nested_installer_files,
+                file.nested_installer_files,
+            ),
+            install_modes: or_vec(self.install_modes, file.install_modes),
+            installer_success_codes: or_vec(
+                self.installer_success_codes,
+                file.installer_success_codes,
+            ),
+            upgrade_behavior: self.upgrade_behavior.or(file.upgrade_behavior),
+            commands: or_vec(self.commands, file.commands),
+            protocols: or_vec(self.protocols, file.protocols),
+            file_extensions: or_vec(self.file_extensions, file.file_extensions),
+            package_locale: self.package_locale.or(file.package_locale),
+            publisher: self.publisher.or(file.publisher),
+            publisher_url: self.publisher_url.or(file.publisher_url),
+            package_name: self.package_name.or(file.package_name),
+            package_url: self.package_url.or(file.package_url),
+            moniker: self.moniker.or(file.moniker),
+            author: self.author.or(file.author),
+            license: self.license.or(file.license),
+            license_url: self.license_url.or(file.license_url),
+            copyright: self.copyright.or(file.copyright),
+            copyright_url: self.copyright_url.or(file.copyright_url),
+            short_description: self.short_description.or(file.short_description),
+            description: self.description.or(file.description),
+            tags: or_vec(self.tags, file.tags),
+            release_notes_url: self.release_notes_url.or(file.release_notes_url),
+            add_locale: self.add_locale,
+            locales: or_vec(self.locales, file.locales),
+        }
+    }
 }
 
 impl NewVersion {
-    pub async fn run(self) -> Result<()> {
-        let token = handle_token(self.token).await?;
-        let github = GitHub::new(token)?;
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        let token = handle_token(self.token.clone()).await?;
+        let github = GitHub::new(token, repository)?;
+        match self.local_repository.clone() {
+            Some(path) => {
+                self.new_version(&LocalRepository::open(&path)?, &github)
+                    .await
+            }
+            None => self.new_version(&github, &github).await,
+        }
+    }
+
+    async fn new_version(self, storage: &impl Storage, github: &GitHub) -> Result<()> {
+        let mut answers = match &self.answers_file {
+            Some(path) => {
+                let file = fs::read_to_string(path)
+                    .await
+                    .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+                self.answers.or(serde_yaml::from_str(&file)
+                    .wrap_err_with(|| format!("Failed to parse {}", path.display()))?)
+            }
+            None => self.answers,
+        };
+        if self.no_prompt || self.answers_file.is_some() {
+            disable_prompts();
+        }
+
         let client = Client::builder()
             .default_headers(get_default_headers(None))
             .build()?;
 
-        let package_identifier = required_prompt(self.package_identifier)?;
+        let package_identifier = required_prompt(answers.package_identifier)?;
 
-        let versions = github
-            .get_versions(&get_package_path(&package_identifier, None))
-            .await
-            .ok();
+        let versions = storage.get_versions(&package_identifier).await.ok();
 
         let latest_version = versions.as_ref().and_then(|versions| versions.iter().max());
 
@@ -159,12 +334,14 @@ impl NewVersion {
         }
 
         let manifests =
-            latest_version.map(|version| github.get_manifests(&package_identifier, version));
+            latest_version.map(|version| storage.get_manifests(&package_identifier, version));
 
-        let package_version = required_prompt(self.package_version)?;
+        let package_version = required_prompt(answers.package_version)?;
 
-        let mut urls = self.urls;
-        if urls.is_empty() {
+        let mut urls = mem::take(&mut answers.urls);
+        if urls.is_empty() && prompts_disabled() {
+            return Err(missing_value("Installer URL"));
+        } else if urls.is_empty() {
             while urls.len() < 1024 {
                 let message = format!("{} Installer URL", Ordinal(urls.len() + 1));
                 let url_prompt =
@@ -186,32 +363,67 @@ impl NewVersion {
 
         let multi_progress = MultiProgress::new();
         let files = stream::iter(download_urls(&client, urls, &multi_progress))
-            .buffer_unordered(self.concurrent_downloads.get() as usize)
+            .buffer_unordered(
+                self.concurrent_downloads
+                    .or(Config::get().concurrent_downloads)
+                    .map_or(2, NonZeroU8::get) as usize,
+            )
             .try_collect::<Vec<_>>()
             .await?;
         multi_progress.clear()?;
+        let github_values = files
+            .iter()
+            .find_map(|download| GitHubReleaseAsset::parse(&download.url))
+            .map(|asset| {
+                github.get_all_values(
+                    asset.owner.to_owned(),
+                    asset.repo.to_owned(),
+                    asset.tag.to_owned(),
+                )
+            });
         let mut download_results = process_files(files).await?;
+        let mut github_values = match github_values {
+            Some(future) => Some(future.await?),
+            None => None,
+        };
+        let release_date = github_values
+            .as_ref()
+            .and_then(|values| values.release_date);
         let mut installers = BTreeSet::new();
         for (url, analyser) in &mut download_results {
             if analyser.installer_type == InstallerType::Exe
-                && Confirm::new(&format!("Is {} a portable exe?", analyser.file_name)).prompt()?
+                && match answers.portable {
+                    Some(portable) => portable,
+                    None if prompts_disabled() => false,
+                    None => Confirm::new(&format!("Is {} a portable exe?", analyser.file_name))
+                        .prompt()?,
+                }
             {
                 analyser.installer_type = InstallerType::Portable;
             }
             let mut installer_switches = InstallerSwitches::default();
-            if analyser.installer_type == InstallerType::Exe {
-                installer_switches.silent = optional_prompt::<SilentSwitch>(None)?;
-                installer_switches.silent_with_progress =
-                    optional_prompt::<SilentWithProgressSwitch>(None)?;
-            }
-            if analyser.installer_type != InstallerType::Portable {
-                installer_switches.custom = optional_prompt::<CustomSwitch>(None)?;
+            if let Some(switches) = Config::get()
+                .package(&package_identifier)
+                .and_then(|package| package.installer_switches.clone())
+            {
+                installer_switches = switches;
+            } else {
+                if analyser.installer_type == InstallerType::Exe {
+                    installer_switches.silent = optional_prompt(answers.silent_switch.clone())?;
+                    installer_switches.silent_with_progress =
+                        optional_prompt(answers.silent_with_progress_switch.clone())?;
+                }
+                if analyser.installer_type != InstallerType::Portable {
+                    installer_switches.custom = optional_prompt(answers.custom_switch.clone())?;
+                }
             }
             if let Some(zip) = &mut analyser.zip {
-                zip.prompt()?;
+                zip.prompt(&answers.nested_installer_files)?;
             }
-            installers.insert(Installer {
+            let installer = Installer {
                 platform: mem::take(&mut analyser.platform),
+                minimum_os_version: mem::take(&mut analyser.minimum_os_version),
+                release_date: release_date.or(analyser.last_modified),
                 architecture: analyser.architecture,
                 installer_type: Some(analyser.installer_type),
                 nested_installer_type: analyser
@@ -222,7 +434,7 @@ impl NewVersion {
                     .zip
                     .as_mut()
                     .and_then(|zip| mem::take(&mut zip.nested_installer_files)),
-                scope: find_scope(url.as_str()),
+                scope: find_scope(url.as_str()).or(analyser.scope),
                 installer_url: url.clone(),
                 installer_sha_256: mem::take(&mut analyser.installer_sha_256),
                 signature_sha_256: mem::take(&mut analyser.signature_sha_256),
@@ -230,15 +442,63 @@ impl NewVersion {
                     .are_all_none()
                     .not()
                     .then_some(installer_switches),
-                product_code: analyser.product_code,
+                product_code: analyser.product_code.clone(),
+                apps_and_features_entries: analyser
+                    .apps_and_features_entry
+                    .take()
+                    .and_then(|entry| {
+                        entry.deduplicate(
+                            analyser.package_name.as_deref().map(String::as_str),
+                            analyser
+                                .publisher
+                                .as_ref()
+                                .map(ToString::to_string)
+                                .as_deref(),
+                            &package_version.to_string(),
+                        )
+                    })
+                    .map(|entry| BTreeSet::from([entry])),
+                protocols: analyser.protocols.take(),
+                file_extensions: analyser.file_extensions.take(),
+                commands: analyser.commands.take(),
+                capabilities: analyser.capabilities.take(),
+                restricted_capabilities: analyser.restricted_capabilities.take(),
+                elevation_requirement: analyser.elevation_requirement.take(),
+                installation_metadata: analyser.installation_metadata.take(),
                 ..Installer::default()
-            });
+            };
+            match &analyser.msix_bundle {
+                // Each architecture in a bundle is its own installer with its own targets
+                Some(msix_bundle) => {
+                    for (architecture, targets) in msix_bundle.targets() {
+                        installers.insert(Installer {
+                            platform: targets.platform,
+                            minimum_os_version: targets.minimum_os_version,
+                            architecture,
+                            ..installer.clone()
+                        });
+                    }
+                }
+                None => {
+                    installers.insert(installer);
+                }
+            }
         }
-        let default_locale = required_prompt(self.package_locale)?;
+        let default_locale = required_prompt(answers.package_locale)?;
         let manifests = match manifests {
             Some(manifests) => Some(manifests.await?),
             None => None,
         };
+        let previous_publisher = manifests
+            .as_ref()
+            .map(|manifests| manifests.default_locale_manifest.publisher.to_string());
+        for warning in download_results
+            .values()
+            .flat_map(|analyser| analyser.warnings(previous_publisher.as_deref()))
+        {
+            println!("{} {warning}", "Warning:".yellow());
+        }
+        let previous_manifests = manifests.as_ref().map(manifest_values).transpose()?;
         let installer_manifest = InstallerManifest {
             package_identifier: package_identifier.clone(),
             package_version: package_version.clone(),
@@ -248,13 +508,40 @@ impl NewVersion {
             {
                 Some(InstallModes::iter().collect())
             } else {
-                check_prompt::<InstallModes>()?
+                check_prompt(answers.install_modes)?
+            },
+            installer_success_codes: list_prompt(answers.installer_success_codes)?,
+            upgrade_behavior: match answers.upgrade_behavior {
+                None if prompts_disabled() => installers
+                    .iter()
+                    .find_map(|installer| installer.installer_type)
+                    .and_then(UpgradeBehavior::get),
+                upgrade_behavior => Some(radio_prompt(upgrade_behavior)?),
+            },
+            commands: if installers
+                .iter()
+                .any(|installer| installer.commands.is_some())
+            {
+                None
+            } else {
+                list_prompt(answers.commands)?
+            },
+            protocols: if installers
+                .iter()
+                .any(|installer| installer.protocols.is_some())
+            {
+                None
+            } else {
+                list_prompt(answers.protocols)?
+            },
+            file_extensions: if installers
+                .iter()
+                .any(|installer| installer.file_extensions.is_some())
+            {
+                None
+            } else {
+                list_prompt(answers.file_extensions)?
             },
-            installer_success_codes: list_prompt::<InstallerSuccessCode>()?,
-            upgrade_behavior: Some(radio_prompt::<UpgradeBehavior>()?),
-            commands: list_prompt::<Command>()?,
-            protocols: list_prompt::<Protocol>()?,
-            file_extensions: list_prompt::<FileExtension>()?,
             manifest_type: ManifestType::Installer,
             ..InstallerManifest::default()
         };
@@ -264,6 +551,49 @@ impl NewVersion {
             installers,
             installer_manifest,
         );
+        // Values that were given are kept, then those of the previous version unless the metadata is
+        // being refreshed, and then those from GitHub
+        let previous_metadata = manifests
+            .as_ref()
+            .filter(|_| !self.refresh_metadata)
+            .map(|manifests| &manifests.default_locale_manifest);
+        let mut metadata = DefaultLocaleManifest {
+            publisher_url: answers
+                .publisher_url
+                .or_else(|| previous_metadata.and_then(|previous| previous.publisher_url.clone())),
+            publisher_support_url: previous_metadata
+                .and_then(|previous| previous.publisher_support_url.clone()),
+            package_url: answers
+                .package_url
+                .or_else(|| previous_metadata.and_then(|previous| previous.package_url.clone())),
+            documentations: previous_metadata.and_then(|previous| previous.documentations.clone()),
+            moniker: answers
+                .moniker
+                .or_else(|| previous_metadata.and_then(|previous| previous.moniker.clone())),
+            tags: (!answers.tags.is_empty())
+                .then(|| answers.tags.into_iter().collect())
+                .or_else(|| previous_metadata.and_then(|previous| previous.tags.clone())),
+            ..DefaultLocaleManifest::default()
+        };
+        if let Some(github_values) = &github_values {
+            github_values.fill_metadata(&mut metadata, false);
+        }
+        let release_notes_source = ReleaseNotesSource::for_package(
+            &package_identifier,
+            self.release_notes_source.map(Url::into_inner),
+            self.release_notes_kind,
+        );
+        let release_notes = match &release_notes_source {
+            Some(source) => source.get(&client, &package_version).await,
+            None => None,
+        };
+        // The release notes URL points to wherever the release notes were read from
+        let release_notes_url = match (&release_notes, &release_notes_source) {
+            (Some(_), Some(source)) => source.release_notes_url(),
+            _ => github_values
+                .as_ref()
+                .map(|values| values.release_notes_url.clone()),
+        };
         let default_locale_manifest = DefaultLocaleManifest {
             package_identifier: package_identifier.clone(),
             package_version: package_version.clone(),
@@ -272,31 +602,74 @@ impl NewVersion {
                 .values_mut()
                 .find(|analyser| analyser.publisher.is_some())
                 .and_then(|analyser| mem::take(&mut analyser.publisher))
-                .unwrap_or_else(|| required_prompt(self.publisher).unwrap_or_default()),
-            publisher_url: optional_prompt(self.publisher_url)?,
-            author: optional_prompt(self.author)?,
+                .map_or_else(|| required_prompt(answers.publisher), Ok)?,
+            publisher_url: optional_prompt(metadata.publisher_url)?,
+            publisher_support_url: metadata.publisher_support_url,
+            author: optional_prompt(answers.author)?,
             package_name: download_results
                 .values_mut()
                 .find(|analyser| analyser.package_name.is_some())
                 .and_then(|analyser| mem::take(&mut analyser.package_name))
-                .unwrap_or_else(|| required_prompt(self.package_name).unwrap_or_default()),
-            package_url: optional_prompt(self.package_url)?,
-            license: required_prompt(self.license)?,
-            license_url: optional_prompt(self.license_url)?,
+                .map_or_else(|| required_prompt(answers.package_name), Ok)?,
+            package_url: optional_prompt(metadata.package_url)?,
+            license: required_prompt(answers.license.or_else(|| {
+                github_values
+                    .as_mut()
+                    .and_then(|values| values.license.take())
+            }))?,
+            license_url: optional_prompt(answers.license_url.or_else(|| {
+                github_values
+                    .as_mut()
+                    .and_then(|values| values.license_url.take())
+            }))?,
             copyright: download_results
                 .values_mut()
                 .find(|analyser| analyser.copyright.is_some())
                 .and_then(|analyser| mem::take(&mut analyser.copyright))
-                .or_else(|| optional_prompt(self.copyright).ok()?),
-            copyright_url: optional_prompt(self.copyright_url)?,
-            short_description: required_prompt(self.short_description)?,
-            description: optional_prompt(self.description)?,
-            moniker: optional_prompt(self.moniker)?,
-            tags: list_prompt::<Tag>()?,
-            release_notes_url: optional_prompt(self.release_notes_url)?,
+                .map_or_else(
+                    || optional_prompt(answers.copyright),
+                    |copyright| Ok(Some(copyright)),
+                )?,
+            copyright_url: optional_prompt(answers.copyright_url)?,
+            short_description: required_prompt(answers.short_description.or_else(|| {
+                github_values
+                    .as_ref()
+                    .and_then(|values| ShortDescription::from_str(&values.short_description).ok())
+            }))?,
+            description: optional_prompt(answers.description)?,
+            moniker: optional_prompt(metadata.moniker)?,
+            tags: list_prompt(metadata.tags.into_iter().flatten().collect())?,
+            release_notes: release_notes.or_else(|| {
+                github_values
+                    .as_mut()
+                    .and_then(|values| values.release_notes.take())
+            }),
+            release_notes_url: optional_prompt(answers.release_notes_url.or(release_notes_url))?,
+            documentations: metadata.documentations,
             manifest_type: ManifestType::DefaultLocale,
             ..DefaultLocaleManifest::default()
         };
+        let mut additional_locales = answers.locales;
+        for package_locale in answers.add_locale {
+            if !additional_locales
+                .iter()
+                .any(|locale| locale.package_locale.as_ref() == Some(&package_locale))
+            {
+                additional_locales.push(LocaleAnswers::new(package_locale));
+            }
+        }
+        let locale_manifests = additional_locales
+            .into_iter()
+            .map(|locale| locale.prompt(&package_identifier, &package_version))
+            .collect::<Result<Vec<_>>>()?;
+        if locale_manifests
+            .iter()
+            .any(|locale_manifest| locale_manifest.package_locale == default_locale)
+        {
+            bail!(
+                "{default_locale} is the default locale so it can't also be an additional locale"
+            );
+        }
         let version_manifest = VersionManifest {
             package_identifier: package_identifier.clone(),
             package_version: package_version.clone(),
@@ -319,26 +692,33 @@ impl NewVersion {
                 ),
                 build_manifest_string(&Manifest::DefaultLocale(&default_locale_manifest))?,
             ));
-            if let Some(locale_manifests) = manifests.map(|manifests| manifests.locale_manifests) {
-                locale_manifests
-                    .into_iter()
-                    .map(|locale_manifest| LocaleManifest {
-                        manifest_version: ManifestVersion::default(),
-                        ..locale_manifest
-                    })
-                    .for_each(|locale_manifest| {
-                        if let Ok(yaml) = build_manifest_string(&Manifest::Locale(&locale_manifest))
-                        {
-                            path_content_map.push((
-                                format!(
-                                    "{full_package_path}/{}.locale.{}.yaml",
-                                    package_identifier, locale_manifest.package_locale
-                                ),
-                                yaml,
-                            ));
-                        }
-                    });
-            }
+            // Previous locales are kept unless they've been given again
+            let mut previous_locale_manifests = manifests
+                .map(|manifests| manifests.locale_manifests)
+                .unwrap_or_default();
+            previous_locale_manifests.retain(|previous| {
+                !locale_manifests.iter().any(|locale_manifest| {
+                    locale_manifest.package_locale == previous.package_locale
+                })
+            });
+            previous_locale_manifests
+                .into_iter()
+                .map(|locale_manifest| LocaleManifest {
+                    manifest_version: ManifestVersion::default(),
+                    ..locale_manifest
+                })
+                .chain(locale_manifests)
+                .for_each(|locale_manifest| {
+                    if let Ok(yaml) = build_manifest_string(&Manifest::Locale(&locale_manifest)) {
+                        path_content_map.push((
+                            format!(
+                                "{full_package_path}/{}.locale.{}.yaml",
+                                package_identifier, locale_manifest.package_locale
+                            ),
+                            yaml,
+                        ));
+                    }
+                });
             path_content_map.push((
                 format!("{full_package_path}/{package_identifier}.yaml"),
                 build_manifest_string(&Manifest::Version(&version_manifest))?,
@@ -347,16 +727,21 @@ impl NewVersion {
         };
 
         print_changes(&changes);
+        let diff = match &previous_manifests {
+            Some(previous_manifests) => {
+                let diff = diff_manifests(previous_manifests, &changes)?;
+                print_diff(&diff, self.diff_format)?;
+                diff
+            }
+            None => Vec::new(),
+        };
 
-        if let Some(output) = self.output {
-            stream::iter(
-                changes
-                    .iter()
-                    .map(|(_, content)| fs::write(&output, content)),
-            )
-            .buffer_unordered(2)
-            .try_collect::<Vec<_>>()
-            .await?;
+        if let Some(output) = self.output.or_else(|| Config::get().output.clone()) {
+            let layout = self
+                .output_layout
+                .or(Config::get().output_layout)
+                .unwrap_or_default();
+            write_manifests(&changes, &output, layout).await?;
             println!(
                 "{} written all manifest files to {}",
                 "Successfully".green(),
@@ -366,9 +751,11 @@ impl NewVersion {
 
         let should_remove_manifest = if self.submit {
             true
+        } else if prompts_disabled() {
+            false
         } else {
             Confirm::new(&format!(
-                "Would you like to make a pull request for {package_identifier} {package_version}?"
+                "Would you like to submit {package_identifier} {package_version} to {storage}?"
             ))
             .prompt()?
         };
@@ -376,62 +763,83 @@ impl NewVersion {
             return Ok(());
         }
 
-        // Create an indeterminate progress bar to show as a pull request is being created
+        // Create an indeterminate progress bar to show as the changes are being submitted
         let pr_progress = ProgressBar::new_spinner().with_message(format!(
-            "Creating a pull request for {package_identifier} version {package_version}"
+            "Submitting {package_identifier} version {package_version} to {storage}"
         ));
         pr_progress.enable_steady_tick(Duration::from_millis(50));
 
-        let current_user = github.get_username().await?;
-        let winget_pkgs = github.get_winget_pkgs(None).await?;
-        let fork = github.get_winget_pkgs(Some(&current_user)).await?;
-        let branch_name = get_branch_name(&package_identifier, &package_version);
-        let pull_request_branch = github
-            .create_branch(&fork.id, &branch_name, &winget_pkgs.default_branch_oid.0)
-            .await?;
         let commit_title = get_commit_title(
             &package_identifier,
             &package_version,
             &UpdateState::get(&package_version, versions.as_ref(), latest_version),
         );
-        let changes = changes
-            .iter()
-            .map(|(path, content)| FileAddition {
-                contents: Base64String(base64ct::Base64::encode_string(content.as_bytes())),
-                path,
+        let submission = storage
+            .commit_changes(Changes {
+                identifier: &package_identifier,
+                version: &package_version,
+                title: &commit_title,
+                body: &get_pull_request_body(&diff),
+                additions: &changes,
+                deleted_directory: None,
+                deleted_files: &[],
             })
-            .collect::<Vec<_>>();
-        let _commit_url = github
-            .create_commit(
-                &pull_request_branch.id,
-                &pull_request_branch
-                    .target
-                    .map(|target| target.oid.0)
-                    .unwrap(),
-                &commit_title,
-                Some(changes),
-                None,
-            )
-            .await?;
-        let pull_request_url = github
-            .create_pull_request(
-                &winget_pkgs.id,
-                &fork.id,
-                &format!("{current_user}:{}", pull_request_branch.name),
-                &winget_pkgs.default_branch_name,
-                &commit_title,
-                &get_pull_request_body(),
-            )
             .await?;
 
         pr_progress.finish_and_clear();
 
-        println!(
-            "{} created a pull request to {WINGET_PKGS_FULL_NAME}",
-            "Successfully".green()
-        );
-        println!("{}", pull_request_url.as_str());
+        submission.print(storage);
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::new_version::Answers;
+    use crate::manifests::installer_manifest::InstallModes;
+    use crate::types::language_tag::LanguageTag;
+    use crate::types::publisher::Publisher;
+    use crate::types::tag::Tag;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_answers_file() {
+        let file = serde_yaml::from_str::<Answers>(
+            "\
+PackageIdentifier: Package.Identifier
+Publisher: File Publisher
+InstallModes: [silent, silentWithProgress]
+Tags: [tag]
+Locales:
+  - PackageLocale: de-DE
+    ShortDescription: Eine kurze Beschreibung
+",
+        )
+        .unwrap();
+        let answers = Answers {
+            publisher: Some(Publisher::from_str("Argument Publisher").unwrap()),
+            ..Answers::default()
+        }
+        .or(file);
+        assert_eq!(
+            answers.package_identifier.unwrap().to_string(),
+            "Package.Identifier"
+        );
+        assert_eq!(answers.publisher.unwrap().to_string(), "Argument Publisher");
+        assert_eq!(
+            answers.install_modes,
+            [InstallModes::Silent, InstallModes::SilentWithProgress]
+        );
+        assert!(answers.tags == [Tag::from_str("tag").unwrap()]);
+        assert_eq!(
+            answers.locales[0].package_locale,
+            Some(LanguageTag::from_str("de-DE").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_unknown_answer() {
+        assert!(serde_yaml::from_str::<Answers>("Unknown: value").is_err());
+    }
+}
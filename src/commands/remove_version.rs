@@ -1,7 +1,9 @@
 use crate::credential::handle_token;
-use crate::github::github_client::{GitHub, WINGET_PKGS_FULL_NAME};
-use crate::github::graphql::create_commit::FileDeletion;
-use crate::github::utils::{get_branch_name, get_commit_title, get_package_path};
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
+use crate::github::utils::{get_commit_title, get_package_path};
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage};
 use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
 use crate::update_state::UpdateState;
@@ -10,6 +12,7 @@ use color_eyre::eyre::{bail, Result, WrapErr};
 use crossterm::style::Stylize;
 use inquire::validator::{MaxLengthValidator, MinLengthValidator};
 use inquire::{Confirm, Text};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct RemoveVersion {
@@ -25,6 +28,10 @@ pub struct RemoveVersion {
     #[arg(short, long)]
     submit: bool,
 
+    /// Local clone of winget-pkgs to commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
     /// GitHub personal access token with the public_repo and read_org scope
     #[arg(short, long, env = "GITHUB_TOKEN")]
     token: Option<String>,
@@ -34,27 +41,30 @@ impl RemoveVersion {
     const MIN_REASON_LENGTH: usize = 4;
     const MAX_REASON_LENGTH: usize = 1000;
 
-    pub async fn run(self) -> Result<()> {
-        let token = handle_token(self.token).await?;
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        match self.local_repository.clone() {
+            Some(path) => self.remove_version(&LocalRepository::open(&path)?).await,
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                self.remove_version(&GitHub::new(token, repository)?).await
+            }
+        }
+    }
+
+    async fn remove_version(self, storage: &impl Storage) -> Result<()> {
         println!(
             "{}",
             "Packages should only be removed when necessary".yellow()
         );
         println!();
-        let github = GitHub::new(token)?;
-        let versions = github
-            .get_versions(&get_package_path(&self.package_identifier, None))
+        let versions = storage
+            .get_versions(&self.package_identifier)
             .await
-            .wrap_err_with(|| {
-                format!(
-                    "{} does not exist in {WINGET_PKGS_FULL_NAME}",
-                    self.package_identifier
-                )
-            })?;
+            .wrap_err_with(|| format!("{} does not exist in {storage}", self.package_identifier))?;
 
         if !versions.contains(&self.package_version) {
             bail!(
-                "{} version {} does not exist in {WINGET_PKGS_FULL_NAME}",
+                "{} version {} does not exist in {storage}",
                 self.package_identifier,
                 self.package_version,
             );
@@ -79,7 +89,7 @@ impl RemoveVersion {
             true
         } else {
             Confirm::new(&format!(
-                "Would you like to make a pull request to remove {} {}?",
+                "Would you like to submit the removal of {} {} to {storage}?",
                 self.package_identifier, self.package_version
             ))
             .prompt()?
@@ -87,59 +97,27 @@ impl RemoveVersion {
         if !should_remove_manifest {
             return Ok(());
         }
-        let current_user = github.get_username().await?;
-        let winget_pkgs = github.get_winget_pkgs(None).await?;
-        let fork = github.get_winget_pkgs(Some(&current_user)).await?;
-        let branch_name = get_branch_name(&self.package_identifier, &self.package_version);
-        let pull_request_branch = github
-            .create_branch(&fork.id, &branch_name, &winget_pkgs.default_branch_oid.0)
-            .await?;
         let commit_title = get_commit_title(
             &self.package_identifier,
             &self.package_version,
             &UpdateState::RemoveVersion,
         );
-        let directory_content = github
-            .get_directory_content(
-                &current_user,
-                &branch_name,
-                &get_package_path(&self.package_identifier, Some(&self.package_version)),
-            )
-            .await?
-            .collect::<Vec<_>>();
-        let deletions = directory_content
-            .iter()
-            .map(|path| FileDeletion { path })
-            .collect::<Vec<_>>();
-        let _commit_url = github
-            .create_commit(
-                &pull_request_branch.id,
-                &pull_request_branch
-                    .target
-                    .map(|object| object.oid.0)
-                    .unwrap(),
-                &commit_title,
-                None,
-                Some(deletions),
-            )
+        let submission = storage
+            .commit_changes(Changes {
+                identifier: &self.package_identifier,
+                version: &self.package_version,
+                title: &commit_title,
+                body: &format!("## {deletion_reason}"),
+                additions: &[],
+                deleted_directory: Some(&get_package_path(
+                    &self.package_identifier,
+                    Some(&self.package_version),
+                )),
+                deleted_files: &[],
+            })
             .await?;
-        let pull_request_url = github
-            .create_pull_request(
-                &winget_pkgs.id,
-                &fork.id,
-                &format!("{current_user}:{}", pull_request_branch.name),
-                &winget_pkgs.default_branch_name,
-                &commit_title,
-                &format!("## {deletion_reason}"),
-            )
-            .await?;
-        println!(
-            "{} created a pull request to delete {} version {}",
-            "Successfully".green(),
-            self.package_identifier,
-            self.package_version
-        );
-        println!("{}", pull_request_url.as_str());
+
+        submission.print(storage);
 
         Ok(())
     }
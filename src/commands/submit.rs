@@ -0,0 +1,168 @@
+use crate::credential::handle_token;
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
+use crate::github::utils::{get_commit_title, get_package_path, get_pull_request_body};
+use crate::manifest::{print_changes, read_manifest_files};
+use crate::manifest_diff::{diff_manifests, manifest_values, print_diff, DiffFormat};
+use crate::manifests::version_manifest::VersionManifest;
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage};
+use crate::types::manifest_type::ManifestType;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::update_state::UpdateState;
+use crate::validation::validate_directory;
+use clap::Parser;
+use color_eyre::eyre::{bail, eyre, Result};
+use indicatif::ProgressBar;
+use inquire::Confirm;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Submit manifests that have already been written to a directory
+#[derive(Parser)]
+pub struct Submit {
+    /// Directory containing the manifests of a single package version
+    #[arg(short, long, value_hint = clap::ValueHint::DirPath)]
+    path: PathBuf,
+
+    /// How to show the changes from the latest version
+    #[arg(long, value_enum, default_value_t)]
+    diff_format: DiffFormat,
+
+    /// Skip the confirmation prompt to create a pull request
+    #[arg(short = 'y', long = "yes")]
+    skip_prompt: bool,
+
+    /// Local clone of winget-pkgs to commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
+    /// GitHub personal access token with the public_repo and read_org scope
+    #[arg(short, long, env = "GITHUB_TOKEN")]
+    token: Option<String>,
+}
+
+impl Submit {
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
+        let violations = validate_directory(&self.path)?;
+        if !violations.is_empty() {
+            for violation in &violations {
+                println!("{violation}");
+            }
+            bail!(
+                "The manifests in {} must be valid before they can be submitted",
+                self.path.display()
+            );
+        }
+
+        let manifests = read_manifest_files(&self.path)?;
+        let version_manifest = manifests
+            .iter()
+            .filter_map(|(_, content)| serde_yaml::from_str::<VersionManifest>(content).ok())
+            .find(|manifest| manifest.manifest_type == ManifestType::Version)
+            .ok_or_else(|| eyre!("No version manifest was found in {}", self.path.display()))?;
+        let identifier = version_manifest.package_identifier;
+        let version = version_manifest.package_version;
+
+        match &self.local_repository {
+            Some(path) => {
+                self.submit(
+                    &LocalRepository::open(path)?,
+                    manifests,
+                    identifier,
+                    version,
+                )
+                .await
+            }
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                self.submit(
+                    &GitHub::new(token, repository)?,
+                    manifests,
+                    identifier,
+                    version,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn submit(
+        &self,
+        storage: &impl Storage,
+        manifests: Vec<(String, String)>,
+        identifier: PackageIdentifier,
+        version: PackageVersion,
+    ) -> Result<()> {
+        let versions = storage.get_versions(&identifier).await.ok();
+        let latest_version = versions.as_ref().and_then(|versions| versions.iter().max());
+        if let Some(latest_version) = latest_version {
+            println!("Latest version of {identifier}: {latest_version}");
+        }
+
+        let full_package_path = get_package_path(&identifier, Some(&version));
+        let changes = manifests
+            .into_iter()
+            .map(|(file_name, content)| (format!("{full_package_path}/{file_name}"), content))
+            .collect::<Vec<_>>();
+
+        print_changes(&changes);
+        let previous_manifests = match latest_version {
+            Some(latest_version) => storage
+                .get_manifests(&identifier, latest_version)
+                .await
+                .ok(),
+            None => None,
+        };
+        let diff = match previous_manifests {
+            Some(previous_manifests) => {
+                let diff = diff_manifests(&manifest_values(&previous_manifests)?, &changes)?;
+                print_diff(&diff, self.diff_format)?;
+                diff
+            }
+            None => Vec::new(),
+        };
+
+        let should_submit = if self.skip_prompt {
+            true
+        } else {
+            Confirm::new(&format!(
+                "Would you like to submit {identifier} {version} to {storage}?"
+            ))
+            .prompt()?
+        };
+        if !should_submit {
+            return Ok(());
+        }
+
+        // Create an indeterminate progress bar to show as the changes are being submitted
+        let pr_progress = ProgressBar::new_spinner().with_message(format!(
+            "Submitting {identifier} version {version} to {storage}"
+        ));
+        pr_progress.enable_steady_tick(Duration::from_millis(50));
+
+        let commit_title = get_commit_title(
+            &identifier,
+            &version,
+            &UpdateState::get(&version, versions.as_ref(), latest_version),
+        );
+        let submission = storage
+            .commit_changes(Changes {
+                identifier: &identifier,
+                version: &version,
+                title: &commit_title,
+                body: &get_pull_request_body(&diff),
+                additions: &changes,
+                deleted_directory: None,
+                deleted_files: &[],
+            })
+            .await?;
+
+        pr_progress.finish_and_clear();
+
+        submission.print(storage);
+
+        Ok(())
+    }
+}
@@ -1,122 +1,328 @@
+use crate::config::Config;
 use crate::credential::{get_default_headers, handle_token};
 use crate::download_file::{download_urls, process_files};
-use crate::github::github_client::{GitHub, WINGET_PKGS_FULL_NAME};
-use crate::github::graphql::create_commit::{Base64String, FileAddition};
+use crate::github::github_client::GitHub;
+use crate::github::repository::GitHubRepository;
 use crate::github::utils::{
-    get_branch_name, get_commit_title, get_package_path, get_pull_request_body,
+    get_commit_title, get_package_path, get_pull_request_body, get_version_from_tag,
+    GitHubReleaseAsset,
 };
-use crate::manifest::{build_manifest_string, print_changes, Manifest};
-use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
-use crate::manifests::installer_manifest::{
-    AppsAndFeaturesEntry, Installer, InstallerManifest, UpgradeBehavior,
+use crate::manifest::{
+    build_manifest_string, print_changes, write_manifests, Manifest, OutputLayout,
 };
+use crate::manifest_diff::{diff_manifests, manifest_values, print_diff, DiffFormat};
+use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
+use crate::manifests::installer_manifest::{Installer, InstallerManifest, UpgradeBehavior};
 use crate::manifests::locale_manifest::LocaleManifest;
 use crate::manifests::version_manifest::VersionManifest;
 use crate::match_installers::match_installers;
+use crate::release_notes::{ReleaseNotesKind, ReleaseNotesSource};
+use crate::storage::local::LocalRepository;
+use crate::storage::{Changes, Storage, Submission};
 use crate::types::manifest_version::ManifestVersion;
 use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
 use crate::types::urls::url::Url;
 use crate::update_state::UpdateState;
 use crate::url_utils::find_scope;
-use base64ct::Encoding;
 use clap::Parser;
-use color_eyre::eyre::{Result, WrapErr};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
 use crossterm::style::Stylize;
-use futures_util::{stream, StreamExt, TryStreamExt};
+use futures_util::future::Either;
+use futures_util::{future, stream, FutureExt, StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar};
 use inquire::Confirm;
 use itertools::Itertools;
 use reqwest::Client;
+use serde::Deserialize;
 use std::collections::BTreeSet;
 use std::mem;
 use std::num::NonZeroU8;
 use std::ops::Not;
-use std::path::PathBuf;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::Duration;
 use tokio::fs;
 
 #[derive(Parser)]
 pub struct UpdateVersion {
     /// The package's unique identifier
-    #[arg(id = "package_identifier", short = 'i', long = "identifier")]
-    identifier: PackageIdentifier,
+    #[arg(
+        id = "package_identifier",
+        short = 'i',
+        long = "identifier",
+        required_unless_present = "batch"
+    )]
+    identifier: Option<PackageIdentifier>,
 
-    /// The package's version
+    /// The package's version. Detected from the latest GitHub release if omitted
     #[arg(id = "package_version", short = 'v', long = "version")]
-    version: PackageVersion,
+    version: Option<PackageVersion>,
 
-    /// The list of package installers
-    #[arg(short, long, num_args=1.., required = true)]
+    /// The list of package installers. Detected from the latest GitHub release if omitted
+    #[arg(short, long, num_args=1.., requires = "package_version")]
     urls: Vec<Url>,
 
-    /// Number of installers to download at the same time
-    #[arg(long, default_value_t = NonZeroU8::new(2).unwrap())]
-    concurrent_downloads: NonZeroU8,
+    /// YAML file listing the identifier and optionally the version and URLs of each package to update
+    #[arg(
+        long,
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["package_identifier", "package_version", "urls"]
+    )]
+    batch: Option<PathBuf>,
+
+    /// Number of installers to download at the same time [default: 2]
+    #[arg(long)]
+    concurrent_downloads: Option<NonZeroU8>,
 
     /// Automatically submit a pull request
     #[arg(short, long)]
     submit: bool,
 
+    /// How to show the changes from the previous version
+    #[arg(long, value_enum, default_value_t)]
+    diff_format: DiffFormat,
+
+    /// Changelog or release page to read the release notes from instead of the GitHub release,
+    /// such as a GitLab project or a CHANGELOG.md
+    #[arg(long, conflicts_with = "batch")]
+    release_notes_source: Option<Url>,
+
+    /// How to read the release notes source. Detected from the URL if omitted
+    #[arg(long, value_enum, requires = "release_notes_source")]
+    release_notes_kind: Option<ReleaseNotesKind>,
+
+    /// Replace the previous version's repository metadata, such as tags, with the values from GitHub
+    #[arg(long)]
+    refresh_metadata: bool,
+
     /// Directory to output the manifests to
     #[arg(short, long, env = "OUTPUT_DIRECTORY", value_hint = clap::ValueHint::DirPath)]
     output: Option<PathBuf>,
 
+    /// How to arrange the manifests in the output directory
+    #[arg(long, value_enum)]
+    output_layout: Option<OutputLayout>,
+
+    /// Local clone of winget-pkgs to read from and commit to instead of creating a pull request
+    #[arg(long, env = "KOMAC_LOCAL_REPOSITORY", value_hint = clap::ValueHint::DirPath)]
+    local_repository: Option<PathBuf>,
+
     /// GitHub personal access token with the public_repo and read_org scope
     #[arg(short, long, env = "GITHUB_TOKEN")]
     token: Option<String>,
 }
 
 impl UpdateVersion {
-    pub async fn run(self) -> Result<()> {
-        let token = handle_token(self.token).await?;
-        let github = GitHub::new(token)?;
+    pub async fn run(self, repository: GitHubRepository) -> Result<()> {
         let client = Client::builder()
             .default_headers(get_default_headers(None))
             .build()?;
 
-        let versions = github
-            .get_versions(&get_package_path(&self.identifier, None))
+        match &self.local_repository {
+            Some(path) => {
+                // GitHub is only used for release metadata when committing locally, so a token
+                // isn't asked for
+                let github = self
+                    .token
+                    .clone()
+                    .map(|token| GitHub::new(token, repository))
+                    .transpose()?;
+                self.run_with(&LocalRepository::open(path)?, github.as_ref(), &client)
+                    .await
+            }
+            None => {
+                let token = handle_token(self.token.clone()).await?;
+                let github = GitHub::new(token, repository)?;
+                self.run_with(&github, Some(&github), &client).await
+            }
+        }
+    }
+
+    async fn run_with(
+        &self,
+        storage: &impl Storage,
+        github: Option<&GitHub>,
+        client: &Client,
+    ) -> Result<()> {
+        if let Some(batch) = &self.batch {
+            return self.run_batch(batch, storage, github, client).await;
+        }
+
+        let Some(identifier) = &self.identifier else {
+            bail!("A package identifier is required when not running a batch");
+        };
+        self.update_package(
+            storage,
+            github,
+            client,
+            identifier,
+            self.version.as_ref(),
+            self.urls.clone(),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn run_batch(
+        &self,
+        batch: &Path,
+        storage: &impl Storage,
+        github: Option<&GitHub>,
+        client: &Client,
+    ) -> Result<()> {
+        let entries = serde_yaml::from_str::<Vec<BatchEntry>>(&fs::read_to_string(batch).await?)
+            .wrap_err_with(|| format!("Failed to parse the batch file {}", batch.display()))?;
+
+        let total = entries.len();
+        let mut results = Vec::with_capacity(total);
+        for (index, entry) in entries.into_iter().enumerate() {
+            match &entry.version {
+                Some(version) => println!(
+                    "{} Updating {} to {version}",
+                    format!("[{}/{total}]", index + 1).blue(),
+                    entry.identifier
+                ),
+                None => println!(
+                    "{} Updating {} to its latest release",
+                    format!("[{}/{total}]", index + 1).blue(),
+                    entry.identifier
+                ),
+            }
+            // A panic while analysing one package's installers shouldn't abort the whole batch
+            let result = AssertUnwindSafe(self.update_package(
+                storage,
+                github,
+                client,
+                &entry.identifier,
+                entry.version.as_ref(),
+                entry.urls,
+            ))
+            .catch_unwind()
             .await
-            .wrap_err_with(|| {
-                format!(
-                    "{} does not exist in {WINGET_PKGS_FULL_NAME}",
-                    self.identifier
-                )
-            })?;
+            .unwrap_or_else(|_| Err(eyre!("Panicked while updating {}", entry.identifier)));
+            if let Err(error) = &result {
+                println!(
+                    "{} to update {}: {error:?}",
+                    "Failed".red(),
+                    entry.identifier
+                );
+            }
+            results.push((entry.identifier, entry.version, result));
+        }
+
+        print_batch_summary(&results);
+
+        let failed = results
+            .iter()
+            .filter(|(.., result)| result.is_err())
+            .count();
+        if failed > 0 {
+            bail!("{failed} of {} packages failed to update", results.len());
+        }
+        Ok(())
+    }
+
+    async fn update_package(
+        &self,
+        storage: &impl Storage,
+        github: Option<&GitHub>,
+        client: &Client,
+        identifier: &PackageIdentifier,
+        version: Option<&PackageVersion>,
+        urls: Vec<Url>,
+    ) -> Result<Option<Submission>> {
+        let versions = storage
+            .get_versions(identifier)
+            .await
+            .wrap_err_with(|| format!("{identifier} does not exist in {storage}"))?;
 
         let latest_version = versions.iter().max().unwrap();
-        println!("Latest version of {}: {latest_version}", self.identifier);
-        let manifests = github.get_manifests(&self.identifier, latest_version);
+        println!("Latest version of {identifier}: {latest_version}");
+        let manifests = storage.get_manifests(identifier, latest_version);
+        let (version, urls, manifests) = match (version, urls.is_empty()) {
+            (Some(version), false) => (version.clone(), urls, Either::Left(manifests)),
+            (version, _) => {
+                let manifests = manifests.await?;
+                let github = github.ok_or_else(|| {
+                    eyre!("A GitHub token is needed to find the latest release of {identifier}")
+                })?;
+                let (version, urls) =
+                    get_latest_release(github, identifier, version, &manifests.installer_manifest)
+                        .await?;
+                if versions.contains(&version) {
+                    bail!("{identifier} {version} already exists in {storage}");
+                }
+                println!("Found {identifier} {version} in its latest GitHub release");
+                (version, urls, Either::Right(future::ok(manifests)))
+            }
+        };
+        let version = &version;
         let multi_progress = MultiProgress::new();
-        let files = stream::iter(download_urls(&client, self.urls, &multi_progress))
-            .buffer_unordered(self.concurrent_downloads.get() as usize)
+        let files = stream::iter(download_urls(client, urls, &multi_progress))
+            .buffer_unordered(
+                self.concurrent_downloads
+                    .or(Config::get().concurrent_downloads)
+                    .map_or(2, NonZeroU8::get) as usize,
+            )
             .try_collect::<Vec<_>>()
             .await?;
         multi_progress.clear()?;
-        let github_values = files
-            .iter()
-            .find(|download| download.url.host_str() == Some("github.com"))
-            .map(|download| {
-                let parts = download.url.path_segments().unwrap().collect::<Vec<_>>();
+        let github_values = github
+            .zip(
+                files
+                    .iter()
+                    .find_map(|download| GitHubReleaseAsset::parse(&download.url)),
+            )
+            .map(|(github, asset)| {
                 github.get_all_values(
-                    parts[0].to_owned(),
-                    parts[1].to_owned(),
-                    parts[4..parts.len() - 1].join("/"),
+                    asset.owner.to_owned(),
+                    asset.repo.to_owned(),
+                    asset.tag.to_owned(),
                 )
             });
         let download_results = process_files(files).await?;
+        let mut github_values = match github_values {
+            Some(future) => Some(future.await?),
+            None => None,
+        };
+        let release_date = github_values
+            .as_ref()
+            .and_then(|values| values.release_date);
         let installer_results = download_results
             .iter()
-            .map(|(url, download)| Installer {
-                architecture: download.architecture,
-                installer_type: Some(download.installer_type),
-                scope: find_scope(url.as_str()),
-                installer_url: url.clone(),
-                ..Installer::default()
+            .flat_map(|(url, download)| {
+                let installer = Installer {
+                    architecture: download.architecture,
+                    installer_type: Some(download.installer_type),
+                    scope: find_scope(url.as_str()),
+                    installer_url: url.clone(),
+                    ..Installer::default()
+                };
+                // Each architecture in a bundle is matched to the previous installers separately
+                match &download.msix_bundle {
+                    Some(msix_bundle) => msix_bundle
+                        .targets()
+                        .into_keys()
+                        .map(|architecture| Installer {
+                            architecture,
+                            ..installer.clone()
+                        })
+                        .collect(),
+                    None => vec![installer],
+                }
             })
             .collect::<Vec<_>>();
         let manifests = manifests.await?;
+        let previous_publisher = manifests.default_locale_manifest.publisher.to_string();
+        for warning in download_results
+            .values()
+            .flat_map(|analyser| analyser.warnings(Some(&previous_publisher)))
+        {
+            println!("{} {warning}", "Warning:".yellow());
+        }
+        let previous_manifests = manifest_values(&manifests)?;
         let mut previous_installer_manifest = manifests.installer_manifest;
         let previous_installers = mem::take(&mut previous_installer_manifest.installers)
             .into_iter()
@@ -129,86 +335,122 @@ impl UpdateVersion {
             })
             .collect::<Vec<_>>();
         let matched_installers = match_installers(previous_installers, &installer_results);
+        let config_switches = Config::get()
+            .package(identifier)
+            .and_then(|package| package.installer_switches.as_ref());
         let installers = matched_installers
             .into_iter()
             .map(|(previous_installer, new_installer)| {
                 let analyser = download_results.get(&new_installer.installer_url).unwrap();
+                let targets = analyser.msix_bundle.as_ref().and_then(|msix_bundle| {
+                    msix_bundle.targets().remove(&new_installer.architecture)
+                });
                 Installer {
                     installer_locale: analyser
                         .product_language
                         .clone()
                         .or(previous_installer.installer_locale),
-                    platform: analyser.platform.clone().or(previous_installer.platform),
-                    minimum_os_version: analyser
-                        .minimum_os_version
-                        .clone()
+                    platform: targets
+                        .as_ref()
+                        .map_or_else(
+                            || analyser.platform.clone(),
+                            |targets| targets.platform.clone(),
+                        )
+                        .or(previous_installer.platform),
+                    minimum_os_version: targets
+                        .map_or_else(
+                            || analyser.minimum_os_version.clone(),
+                            |targets| targets.minimum_os_version,
+                        )
                         .or(previous_installer.minimum_os_version)
                         .filter(|minimum_os_version| &**minimum_os_version != "10.0.0.0"),
                     architecture: previous_installer.architecture,
                     installer_type: new_installer.installer_type,
                     scope: new_installer
                         .scope
+                        .or(analyser.scope)
                         .or(previous_installer.scope)
                         .or(previous_installer_manifest.scope),
                     installer_url: new_installer.installer_url.clone(),
                     installer_sha_256: analyser.installer_sha_256.clone(),
                     signature_sha_256: analyser.signature_sha_256.clone(),
                     install_modes: previous_installer.install_modes,
-                    installer_switches: previous_installer.installer_switches,
+                    installer_switches: config_switches
+                        .cloned()
+                        .or(previous_installer.installer_switches),
                     installer_success_codes: previous_installer.installer_success_codes,
                     upgrade_behavior: UpgradeBehavior::get(analyser.installer_type)
                         .or(previous_installer.upgrade_behavior),
-                    commands: previous_installer.commands,
-                    protocols: previous_installer.protocols,
-                    file_extensions: previous_installer.file_extensions,
+                    commands: analyser.commands.clone().or(previous_installer.commands),
+                    protocols: analyser.protocols.clone().or(previous_installer.protocols),
+                    file_extensions: analyser
+                        .file_extensions
+                        .clone()
+                        .or(previous_installer.file_extensions),
                     package_family_name: analyser.package_family_name.clone(),
-                    product_code: analyser.product_code,
-                    release_date: analyser.last_modified,
-                    apps_and_features_entries: analyser.msi.as_ref().map(|msi| {
-                        BTreeSet::from([AppsAndFeaturesEntry {
-                            display_name: if msi.product_name
-                                == manifests.default_locale_manifest.package_name.as_str()
-                            {
-                                None
-                            } else {
-                                Some(msi.product_name.clone())
-                            },
-                            display_version: if msi.product_version == self.version.to_string() {
-                                None
-                            } else {
-                                Some(msi.product_version.clone())
-                            },
-                            upgrade_code: Some(msi.upgrade_code),
-                            ..AppsAndFeaturesEntry::default()
-                        }])
-                    }),
+                    product_code: analyser.product_code.clone(),
+                    release_date: release_date.or(analyser.last_modified),
+                    apps_and_features_entries: analyser
+                        .apps_and_features_entry
+                        .clone()
+                        .and_then(|entry| {
+                            entry.deduplicate(
+                                Some(&manifests.default_locale_manifest.package_name),
+                                Some(&manifests.default_locale_manifest.publisher.to_string()),
+                                &version.to_string(),
+                            )
+                        })
+                        .map(|entry| BTreeSet::from([entry])),
+                    elevation_requirement: analyser
+                        .elevation_requirement
+                        .clone()
+                        .or(previous_installer.elevation_requirement),
+                    installation_metadata: analyser
+                        .installation_metadata
+                        .clone()
+                        .or(previous_installer.installation_metadata),
+                    capabilities: analyser
+                        .capabilities
+                        .clone()
+                        .or(previous_installer.capabilities),
+                    restricted_capabilities: analyser
+                        .restricted_capabilities
+                        .clone()
+                        .or(previous_installer.restricted_capabilities),
                     ..previous_installer
                 }
             })
             .collect::<BTreeSet<_>>();
 
         let mut installer_manifest = reorder_keys(
-            self.identifier.clone(),
-            self.version.clone(),
+            identifier.clone(),
+            version.clone(),
             installers,
             previous_installer_manifest,
         );
         installer_manifest.minimum_os_version = installer_manifest
             .minimum_os_version
             .filter(|minimum_os_version| &**minimum_os_version != "10.0.0.0");
-        let previous_default_locale_manifest = manifests.default_locale_manifest;
-        let mut github_values = match github_values {
-            Some(future) => Some(future.await?),
+        let release_notes_source = ReleaseNotesSource::for_package(
+            identifier,
+            self.release_notes_source.clone().map(Url::into_inner),
+            self.release_notes_kind,
+        );
+        let release_notes = match &release_notes_source {
+            Some(source) => source.get(client, version).await,
             None => None,
         };
-        let default_locale_manifest = DefaultLocaleManifest {
-            package_identifier: self.identifier.clone(),
-            package_version: self.version.clone(),
-            publisher_url: previous_default_locale_manifest.publisher_url.or_else(|| {
-                github_values
-                    .as_mut()
-                    .map(|values| mem::take(&mut values.publisher_url))
-            }),
+        // The release notes URL points to wherever the release notes were read from
+        let release_notes_url = match (&release_notes, &release_notes_source) {
+            (Some(_), Some(source)) => source.release_notes_url(),
+            _ => github_values
+                .as_ref()
+                .map(|values| values.release_notes_url.clone()),
+        };
+        let previous_default_locale_manifest = manifests.default_locale_manifest;
+        let mut default_locale_manifest = DefaultLocaleManifest {
+            package_identifier: identifier.clone(),
+            package_version: version.clone(),
             license: github_values
                 .as_mut()
                 .and_then(|values| mem::take(&mut values.license))
@@ -217,31 +459,36 @@ impl UpdateVersion {
                 .as_mut()
                 .and_then(|values| mem::take(&mut values.license_url))
                 .or(previous_default_locale_manifest.license_url),
-            release_notes: github_values
-                .as_mut()
-                .and_then(|values| mem::take(&mut values.release_notes)),
-            release_notes_url: github_values.map(|values| values.release_notes_url),
+            release_notes: release_notes.or_else(|| {
+                github_values
+                    .as_mut()
+                    .and_then(|values| mem::take(&mut values.release_notes))
+            }),
+            release_notes_url,
             manifest_version: ManifestVersion::default(),
             ..previous_default_locale_manifest
         };
+        if let Some(github_values) = &github_values {
+            github_values.fill_metadata(&mut default_locale_manifest, self.refresh_metadata);
+        }
         let version_manifest = VersionManifest {
-            package_identifier: self.identifier.clone(),
-            package_version: self.version.clone(),
+            package_identifier: identifier.clone(),
+            package_version: version.clone(),
             manifest_version: ManifestVersion::default(),
             ..manifests.version_manifest
         };
 
         let changes = {
-            let full_package_path = get_package_path(&self.identifier, Some(&self.version));
+            let full_package_path = get_package_path(identifier, Some(version));
             let mut path_content_map = Vec::new();
             path_content_map.push((
-                format!("{full_package_path}/{}.installer.yaml", self.identifier),
+                format!("{full_package_path}/{}.installer.yaml", identifier),
                 build_manifest_string(&Manifest::Installer(&installer_manifest))?,
             ));
             path_content_map.push((
                 format!(
                     "{full_package_path}/{}.locale.{}.yaml",
-                    self.identifier, version_manifest.default_locale
+                    identifier, version_manifest.default_locale
                 ),
                 build_manifest_string(&Manifest::DefaultLocale(&default_locale_manifest))?,
             ));
@@ -257,30 +504,29 @@ impl UpdateVersion {
                         path_content_map.push((
                             format!(
                                 "{full_package_path}/{}.locale.{}.yaml",
-                                self.identifier, locale_manifest.package_locale
+                                identifier, locale_manifest.package_locale
                             ),
                             yaml,
                         ));
                     }
                 });
             path_content_map.push((
-                format!("{full_package_path}/{}.yaml", self.identifier),
+                format!("{full_package_path}/{}.yaml", identifier),
                 build_manifest_string(&Manifest::Version(&version_manifest))?,
             ));
             path_content_map
         };
 
         print_changes(&changes);
+        let diff = diff_manifests(&previous_manifests, &changes)?;
+        print_diff(&diff, self.diff_format)?;
 
-        if let Some(output) = self.output {
-            stream::iter(
-                changes
-                    .iter()
-                    .map(|(_, content)| fs::write(&output, content)),
-            )
-            .buffer_unordered(2)
-            .try_collect::<Vec<_>>()
-            .await?;
+        if let Some(output) = self.output.as_ref().or(Config::get().output.as_ref()) {
+            let layout = self
+                .output_layout
+                .or(Config::get().output_layout)
+                .unwrap_or_default();
+            write_manifests(&changes, output, layout).await?;
             println!(
                 "{} written all manifest files to {}",
                 "Successfully".green(),
@@ -292,73 +538,155 @@ impl UpdateVersion {
             true
         } else {
             Confirm::new(&format!(
-                "Would you like to make a pull request for {} {}?",
-                self.identifier, self.version
+                "Would you like to submit {identifier} {version} to {storage}?"
             ))
             .prompt()?
         };
         if !should_remove_manifest {
-            return Ok(());
+            return Ok(None);
         }
 
-        // Create an indeterminate progress bar to show as a pull request is being created
+        // Create an indeterminate progress bar to show as the changes are being submitted
         let pr_progress = ProgressBar::new_spinner().with_message(format!(
-            "Creating a pull request for {} version {}",
-            self.identifier, self.version
+            "Submitting {identifier} version {version} to {storage}"
         ));
         pr_progress.enable_steady_tick(Duration::from_millis(50));
 
-        let current_user = github.get_username().await?;
-        let winget_pkgs = github.get_winget_pkgs(None).await?;
-        let fork = github.get_winget_pkgs(Some(&current_user)).await?;
-        let branch_name = get_branch_name(&self.identifier, &self.version);
-        let pull_request_branch = github
-            .create_branch(&fork.id, &branch_name, &winget_pkgs.default_branch_oid.0)
-            .await?;
         let commit_title = get_commit_title(
-            &self.identifier,
-            &self.version,
-            &UpdateState::get(&self.version, Some(&versions), Some(latest_version)),
+            identifier,
+            version,
+            &UpdateState::get(version, Some(&versions), Some(latest_version)),
         );
-        let changes = changes
-            .iter()
-            .map(|(path, content)| FileAddition {
-                contents: Base64String(base64ct::Base64::encode_string(content.as_bytes())),
-                path,
+        let submission = storage
+            .commit_changes(Changes {
+                identifier,
+                version,
+                title: &commit_title,
+                body: &get_pull_request_body(&diff),
+                additions: &changes,
+                deleted_directory: None,
+                deleted_files: &[],
             })
-            .collect::<Vec<_>>();
-        let _commit_url = github
-            .create_commit(
-                &pull_request_branch.id,
-                &pull_request_branch
-                    .target
-                    .map(|object| object.oid.0)
-                    .unwrap(),
-                &commit_title,
-                Some(changes),
-                None,
-            )
-            .await?;
-        let pull_request_url = github
-            .create_pull_request(
-                &winget_pkgs.id,
-                &fork.id,
-                &format!("{current_user}:{}", pull_request_branch.name),
-                &winget_pkgs.default_branch_name,
-                &commit_title,
-                &get_pull_request_body(),
-            )
             .await?;
 
         pr_progress.finish_and_clear();
 
+        submission.print(storage);
+
+        Ok(Some(submission))
+    }
+}
+
+/// Finds the installer URLs of a package's latest GitHub release from the URLs of its previous
+/// installers, taking the version from the release's tag if one isn't given
+async fn get_latest_release(
+    github: &GitHub,
+    identifier: &PackageIdentifier,
+    version: Option<&PackageVersion>,
+    installer_manifest: &InstallerManifest,
+) -> Result<(PackageVersion, Vec<Url>)> {
+    let assets = installer_manifest
+        .installers
+        .iter()
+        .map(|installer| &*installer.installer_url)
+        .unique()
+        .map(GitHubReleaseAsset::parse)
+        .collect::<Option<Vec<_>>>()
+        .filter(|assets| {
+            assets
+                .iter()
+                .map(|asset| (asset.owner, asset.repo, asset.tag))
+                .all_equal()
+        })
+        .ok_or_else(|| {
+            eyre!(
+                "The installers of {identifier} are not all from the same GitHub release, so the version and URLs must be given"
+            )
+        })?;
+    let previous_release = assets
+        .first()
+        .ok_or_else(|| eyre!("{identifier} does not have any installers"))?;
+
+    let tag = github
+        .get_latest_release_tag(previous_release.owner, previous_release.repo)
+        .await?;
+    let version = release_version(&tag, version)?;
+    let previous_version = get_version_from_tag(previous_release.tag);
+    let urls = assets
+        .iter()
+        .map(|asset| {
+            Url::from_str(&asset.to_release(&tag, previous_version, get_version_from_tag(&tag)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((version, urls))
+}
+
+/// The version of a release from its tag, which has to match the version that was asked for as
+/// the release's assets are named after the tag
+fn release_version(tag: &str, version: Option<&PackageVersion>) -> Result<PackageVersion> {
+    let tag_version = PackageVersion::new(get_version_from_tag(tag)).wrap_err_with(|| {
+        format!("Failed to get a version from the latest release's tag of {tag}")
+    })?;
+    match version {
+        Some(version) if *version != tag_version => bail!(
+            "The latest release is {tag} rather than version {version}, so the URLs must be given"
+        ),
+        _ => Ok(tag_version),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchEntry {
+    identifier: PackageIdentifier,
+    version: Option<PackageVersion>,
+    #[serde(default)]
+    urls: Vec<Url>,
+}
+
+type BatchResult = (
+    PackageIdentifier,
+    Option<PackageVersion>,
+    Result<Option<Submission>>,
+);
+
+fn print_batch_summary(results: &[BatchResult]) {
+    let identifier_width = results
+        .iter()
+        .map(|(identifier, ..)| identifier.len())
+        .max()
+        .unwrap_or_default()
+        .max("Package".len());
+    let version_width = results
+        .iter()
+        .map(|(_, version, _)| {
+            version
+                .as_ref()
+                .map_or("Latest".len(), |version| version.to_string().len())
+        })
+        .max()
+        .unwrap_or_default()
+        .max("Version".len());
+
+    println!();
+    println!(
+        "{:identifier_width$}  {:version_width$}  {:7}  Details",
+        "Package", "Version", "Result"
+    );
+    for (identifier, version, result) in results {
+        let (status, details) = match result {
+            // Styled content ignores the width, so the text is padded before it's styled
+            Ok(Some(submission)) => (format!("{:7}", "Success").green(), submission.to_string()),
+            Ok(None) => (format!("{:7}", "Skipped").yellow(), String::new()),
+            Err(error) => (format!("{:7}", "Failed").red(), error.to_string()),
+        };
         println!(
-            "{} created a pull request to {WINGET_PKGS_FULL_NAME}",
-            "Successfully".green()
+            "{:identifier_width$}  {:version_width$}  {status}  {details}",
+            identifier.as_str(),
+            version
+                .as_ref()
+                .map_or_else(|| "Latest".to_owned(), ToString::to_string),
         );
-        println!("{}", pull_request_url.as_str());
-
-        Ok(())
     }
 }
 
@@ -477,3 +805,57 @@ pub fn reorder_keys(
         ..installer_manifest
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::update_version::{release_version, BatchEntry};
+    use crate::types::package_version::PackageVersion;
+    use crate::types::urls::url::Url;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_release_version() {
+        let version = PackageVersion::new("1.2.3").unwrap();
+        assert_eq!(version, release_version("v1.2.3", None).unwrap());
+        assert_eq!(version, release_version("v1.2.3", Some(&version)).unwrap());
+        assert!(release_version("v1.2.4", Some(&version)).is_err());
+    }
+
+    #[test]
+    fn test_batch_entries() {
+        let entries = serde_yaml::from_str::<Vec<BatchEntry>>(
+            "
+- identifier: Package.Identifier
+- identifier: Package.Other
+  version: 1.2.3
+  urls:
+  - https://www.example.com/setup.exe
+",
+        )
+        .unwrap();
+        let [latest, listed] = entries.as_slice() else {
+            panic!("Expected two batch entries");
+        };
+        assert_eq!("Package.Identifier", latest.identifier.as_str());
+        assert_eq!(None, latest.version);
+        assert!(latest.urls.is_empty());
+        assert_eq!("Package.Other", listed.identifier.as_str());
+        assert_eq!(Some(PackageVersion::new("1.2.3").unwrap()), listed.version);
+        assert_eq!(
+            vec![Url::from_str("https://www.example.com/setup.exe").unwrap()],
+            listed.urls
+        );
+    }
+
+    #[test]
+    fn test_unknown_batch_key() {
+        assert!(serde_yaml::from_str::<Vec<BatchEntry>>(
+            "
+- identifier: Package.Identifier
+  version: 1.2.3
+  url: https://www.example.com/setup.exe
+",
+        )
+        .is_err());
+    }
+}
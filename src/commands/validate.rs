@@ -0,0 +1,55 @@
+use crate::validation::validate_directory;
+use clap::Parser;
+use color_eyre::eyre::{bail, Result};
+use crossterm::style::Stylize;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Validate a directory of manifests offline against the manifest schema rules
+#[derive(Parser)]
+pub struct Validate {
+    /// Directory containing the manifests of a single package version
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    path: PathBuf,
+}
+
+impl Validate {
+    pub fn run(self) -> Result<()> {
+        let violations = validate_directory(&self.path)?;
+
+        if violations.is_empty() {
+            println!(
+                "{} validated all manifests in {}",
+                "Successfully".green(),
+                self.path.display()
+            );
+            return Ok(());
+        }
+
+        let mut stdout_lock = io::stdout().lock();
+        for violation in &violations {
+            writeln!(
+                stdout_lock,
+                "{} {}",
+                violation.file.as_str().blue(),
+                if violation.path.is_empty() {
+                    violation.reason.clone()
+                } else {
+                    format!("{}: {}", violation.path.as_str().yellow(), violation.reason)
+                }
+            )?;
+        }
+
+        bail!(
+            "{} schema violation{} found in {}",
+            violations.len(),
+            if violations.len() == 1 {
+                " was"
+            } else {
+                "s were"
+            },
+            self.path.display()
+        )
+    }
+}
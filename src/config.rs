@@ -0,0 +1,283 @@
+use crate::github::repository::GitHubRepository;
+use crate::manifest::OutputLayout;
+use crate::manifests::installer_manifest::InstallerSwitches;
+use crate::release_notes::ReleaseNotesKind;
+use crate::types::package_identifier::PackageIdentifier;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
+use std::collections::BTreeMap;
+use std::num::NonZeroU8;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fs};
+use toml::{Table, Value};
+use url::Url;
+
+const KOMAC_CONFIG: &str = "KOMAC_CONFIG";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Defaults that are used when a value isn't given as an argument or environment variable
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub concurrent_downloads: Option<NonZeroU8>,
+    pub output: Option<PathBuf>,
+    pub output_layout: Option<OutputLayout>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub repo: Option<GitHubRepository>,
+    pub fork_owner: Option<String>,
+    pub created_with: Option<String>,
+    pub created_with_url: Option<String>,
+    pub cache: CacheConfig,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub packages: BTreeMap<String, PackageConfig>,
+}
+
+/// Where downloaded installers are kept and how much space they can use
+#[skip_serializing_none]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    pub directory: Option<PathBuf>,
+    /// The maximum size in MiB, where 0 disables the cache
+    pub max_size: Option<u64>,
+}
+
+/// Preferences for a single package identifier
+#[skip_serializing_none]
+#[derive(Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PackageConfig {
+    pub installer_switches: Option<InstallerSwitches>,
+    /// Where to read the package's release notes from instead of its GitHub release
+    pub release_notes_source: Option<Url>,
+    pub release_notes_kind: Option<ReleaseNotesKind>,
+}
+
+impl Config {
+    /// Returns the loaded config, or the defaults if no config has been loaded
+    pub fn get() -> &'static Self {
+        CONFIG.get_or_init(Self::default)
+    }
+
+    /// Loads the config file so that it's returned by [`Config::get`]
+    pub fn load() -> Result<&'static Self> {
+        let table = read_table(&path()?)?;
+        let config = Self::from_table(table)?;
+        Ok(CONFIG.get_or_init(|| config))
+    }
+
+    fn from_table(table: Table) -> Result<Self> {
+        Self::deserialize(Value::Table(table)).map_err(|error| eyre!(error.to_string()))
+    }
+
+    pub fn package(&self, identifier: &PackageIdentifier) -> Option<&PackageConfig> {
+        self.packages.get(identifier.as_str())
+    }
+
+    pub fn fork_owner(&self) -> Option<String> {
+        env::var("KOMAC_FORK_OWNER")
+            .ok()
+            .or_else(|| self.fork_owner.clone())
+    }
+
+    pub fn created_with(&self) -> Option<String> {
+        env::var("KOMAC_CREATED_WITH")
+            .ok()
+            .or_else(|| self.created_with.clone())
+    }
+
+    pub fn created_with_url(&self) -> Option<String> {
+        env::var("KOMAC_CREATED_WITH_URL")
+            .ok()
+            .or_else(|| self.created_with_url.clone())
+    }
+}
+
+/// The config file path, which can be overridden with the `KOMAC_CONFIG` environment variable
+pub fn path() -> Result<PathBuf> {
+    if let Some(path) = env::var_os(KOMAC_CONFIG) {
+        return Ok(PathBuf::from(path));
+    }
+    dirs::config_dir()
+        .map(|directory| directory.join("komac").join("config.toml"))
+        .ok_or_else(|| eyre!("Could not find the configuration directory"))
+}
+
+pub fn read_table(path: &Path) -> Result<Table> {
+    match fs::read_to_string(path) {
+        Ok(content) => content
+            .parse::<Table>()
+            .wrap_err_with(|| format!("Failed to parse {}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Table::new()),
+        Err(error) => Err(error).wrap_err_with(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Validates the table against the config's schema before writing it
+pub fn write_table(path: &Path, table: Table) -> Result<()> {
+    let content = toml::to_string_pretty(&table)?;
+    Config::from_table(table).wrap_err("The configuration is invalid")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Splits a dotted key such as `packages."Package.Identifier".installer_switches.Silent`
+pub fn split_key(key: &str) -> Result<Vec<String>> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut in_quotes = false;
+    for character in key.chars() {
+        match character {
+            '"' => in_quotes = !in_quotes,
+            '.' if !in_quotes => parts.push(std::mem::take(&mut part)),
+            _ => part.push(character),
+        }
+    }
+    parts.push(part);
+    if in_quotes || parts.iter().any(String::is_empty) {
+        bail!("{key} is not a valid key");
+    }
+    Ok(parts)
+}
+
+pub fn get_value<'table>(table: &'table Table, key: &[String]) -> Option<&'table Value> {
+    let (last, parents) = key.split_last()?;
+    let mut table = table;
+    for part in parents {
+        table = table.get(part)?.as_table()?;
+    }
+    table.get(last)
+}
+
+pub fn set_value(table: &mut Table, key: &[String], value: Value) -> Result<()> {
+    let Some((last, parents)) = key.split_last() else {
+        bail!("No key was given");
+    };
+    let mut table = table;
+    for part in parents {
+        table = table
+            .entry(part.as_str())
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| eyre!("{part} is not a table"))?;
+    }
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+pub fn remove_value(table: &mut Table, key: &[String]) -> Option<Value> {
+    let (last, parents) = key.split_last()?;
+    let mut table = table;
+    for part in parents {
+        table = table.get_mut(part)?.as_table_mut()?;
+    }
+    table.remove(last)
+}
+
+/// Parses a value as TOML so that numbers stay numbers, falling back to a plain string
+pub fn parse_value(value: &str) -> Value {
+    format!("value = {value}")
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(value.to_owned()))
+}
+
+/// Flattens a table into dotted keys with their values
+pub fn flatten(table: &Table) -> Vec<(String, &Value)> {
+    let mut entries = Vec::new();
+    for (key, value) in table {
+        let key = if key.contains('.') {
+            format!("\"{key}\"")
+        } else {
+            key.clone()
+        };
+        match value {
+            Value::Table(child) => entries.extend(
+                flatten(child)
+                    .into_iter()
+                    .map(|(child_key, value)| (format!("{key}.{child_key}"), value)),
+            ),
+            _ => entries.push((key, value)),
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{flatten, get_value, parse_value, set_value, split_key, Config};
+    use toml::{Table, Value};
+
+    #[test]
+    fn test_split_quoted_key() {
+        assert_eq!(
+            vec![
+                "packages",
+                "Package.Identifier",
+                "installer_switches",
+                "Silent"
+            ],
+            split_key(r#"packages."Package.Identifier".installer_switches.Silent"#).unwrap()
+        );
+        assert!(split_key("packages..Silent").is_err());
+    }
+
+    #[test]
+    fn test_set_and_get_package_value() {
+        let mut table = Table::new();
+        let key = split_key(r#"packages."Package.Identifier".installer_switches.Silent"#).unwrap();
+        set_value(&mut table, &key, parse_value("/S")).unwrap();
+        set_value(
+            &mut table,
+            &["concurrent_downloads".to_owned()],
+            parse_value("4"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Some(&Value::String("/S".to_owned())),
+            get_value(&table, &key)
+        );
+        assert_eq!(
+            vec![
+                "concurrent_downloads".to_owned(),
+                r#"packages."Package.Identifier".installer_switches.Silent"#.to_owned()
+            ],
+            flatten(&table)
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>()
+        );
+
+        let config = Config::from_table(table).unwrap();
+        assert_eq!(4, config.concurrent_downloads.unwrap().get());
+        assert_eq!(
+            Some("/S".to_owned()),
+            config.packages["Package.Identifier"]
+                .installer_switches
+                .as_ref()
+                .and_then(|switches| switches.silent.as_ref())
+                .map(ToString::to_string)
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut table = Table::new();
+        set_value(&mut table, &["unknown".to_owned()], parse_value("true")).unwrap();
+        assert!(Config::from_table(table).is_err());
+
+        let mut table = Table::new();
+        set_value(&mut table, &["repo".to_owned()], parse_value("winget-pkgs")).unwrap();
+        assert!(Config::from_table(table).is_err());
+    }
+}
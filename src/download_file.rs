@@ -1,82 +1,273 @@
 use crate::file_analyser::FileAnalyser;
+use crate::installer_cache::{CacheEntry, InstallerCache};
 use crate::types::urls::url::Url;
 use crate::url_utils::find_architecture;
-use color_eyre::eyre::{eyre, Result, WrapErr};
+use color_eyre::eyre::{bail, Result, WrapErr};
 use futures_util::{stream, StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use itertools::Itertools;
-use reqwest::header::{HeaderValue, CONTENT_DISPOSITION, LAST_MODIFIED};
-use reqwest::Client;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_RANGE, ETAG, IF_RANGE,
+    LAST_MODIFIED, RANGE,
+};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use sha2::{Digest, Sha256};
 use std::borrow::Cow;
 use std::cmp::min;
 use std::collections::HashMap;
 use std::fs::File;
 use std::future::Future;
+use std::io::SeekFrom;
+use std::time::Duration;
 use time::format_description::well_known::Rfc2822;
 use time::{Date, OffsetDateTime};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::time::sleep;
 use uuid::Uuid;
 
+/// How many times a request is attempted before the download fails
+const MAX_ATTEMPTS: u32 = 5;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The delay before the next attempt, doubling after each failed attempt
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF * 2_u32.pow(attempt - 1)
+}
+
+/// Whether a failed request is worth trying again, such as when it couldn't be sent or the server
+/// is temporarily unavailable
+fn is_retryable(error: &reqwest::Error) -> bool {
+    error
+        .status()
+        .is_none_or(|status| status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// Sends a request, retrying with exponential backoff if it couldn't be sent or the server had an
+/// error, and failing on any other error status
+async fn send_with_retry(request: impl Fn() -> RequestBuilder, url: &Url) -> Result<Response> {
+    let mut attempt = 1;
+    loop {
+        match request().send().await.and_then(Response::error_for_status) {
+            Ok(response) => return Ok(response),
+            Err(error) if is_retryable(&error) && attempt < MAX_ATTEMPTS => {
+                sleep(backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(error) if is_retryable(&error) => {
+                return Err(error).wrap_err_with(|| {
+                    format!("Failed to GET from '{url}' after {MAX_ATTEMPTS} attempts")
+                })
+            }
+            Err(error) => {
+                return Err(error).wrap_err_with(|| format!("Failed to GET from '{url}'"))
+            }
+        }
+    }
+}
+
+/// Parses the first byte position from a Content-Range header such as `bytes 100-199/200`
+fn content_range_start(content_range: &str) -> Option<u64> {
+    content_range
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Whether a response continues the download from the given byte
+fn resumes_at(response: &Response, position: u64) -> bool {
+    response.status() == StatusCode::PARTIAL_CONTENT
+        && response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|content_range| content_range.to_str().ok())
+            .and_then(content_range_start)
+            == Some(position)
+}
+
 async fn download_file(
     client: &Client,
     url: Url,
     multi_progress: &MultiProgress,
 ) -> Result<DownloadedFile> {
-    let res = client
-        .get(url.as_str())
-        .send()
-        .await
-        .wrap_err_with(|| format!("Failed to GET from '{url}'"))?;
+    let cache = InstallerCache::open();
+    let cached = cache
+        .as_ref()
+        .and_then(|cache| cache.entry(url.as_str()))
+        .filter(CacheEntry::is_revalidatable);
+    let res = send_with_retry(
+        || {
+            client.get(url.as_str()).headers(
+                cached
+                    .as_ref()
+                    .map(CacheEntry::conditional_headers)
+                    .unwrap_or_default(),
+            )
+        },
+        &url,
+    )
+    .await?;
+
+    if let (Some(cache), Some(entry)) = (&cache, cached) {
+        if res.status() == StatusCode::NOT_MODIFIED {
+            return Ok(DownloadedFile {
+                sha_256: entry.sha_256.clone(),
+                file_name: entry.file_name.clone(),
+                last_modified: parse_last_modified(entry.last_modified.as_deref()),
+                file: cache.reuse(entry)?,
+                url,
+            });
+        }
+    }
+
+    if res.status() != StatusCode::OK {
+        bail!("Unexpected {} response from '{url}'", res.status());
+    }
 
     let content_disposition = res.headers().get(CONTENT_DISPOSITION);
     let file_name = get_file_name(&url, content_disposition);
-    let total_size = res
-        .content_length()
-        .ok_or_else(|| eyre!("Failed to get content length from '{url}'"))?;
+    let total_size = res.content_length();
 
-    let last_modified = res
+    let etag = header_string(res.headers(), ETAG);
+    let last_modified_header = header_string(res.headers(), LAST_MODIFIED);
+    let last_modified = parse_last_modified(last_modified_header.as_deref());
+    let accepts_ranges = res
         .headers()
-        .get(LAST_MODIFIED)
-        .and_then(|last_modified| last_modified.to_str().ok())
-        .and_then(|last_modified| OffsetDateTime::parse(last_modified, &Rfc2822).ok())
-        .map(OffsetDateTime::date);
+        .get(ACCEPT_RANGES)
+        .is_some_and(|accept_ranges| accept_ranges == "bytes");
+    // If-Range makes the server send the whole file again if it has changed since the first request
+    let if_range = etag
+        .as_deref()
+        .filter(|etag| !etag.starts_with("W/"))
+        .or(last_modified_header.as_deref());
 
-    let pb = multi_progress.add(ProgressBar::new(total_size)
-        .with_style(ProgressStyle::default_bar()
-            .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")?
-            .progress_chars("#>-")
-        )
-        .with_message(format!("Downloading {url}"))
-    );
+    let pb = multi_progress.add(match total_size {
+        Some(total_size) => ProgressBar::new(total_size)
+            .with_style(ProgressStyle::default_bar()
+                .template("{msg}\n{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")?
+                .progress_chars("#>-")
+            ),
+        // Without a content length, only the amount downloaded so far can be shown
+        None => ProgressBar::new_spinner()
+            .with_style(ProgressStyle::default_spinner()
+                .template("{msg}\n{spinner:.green} [{elapsed_precise}] {bytes} ({bytes_per_sec})")?
+            ),
+    }
+    .with_message(format!("Downloading {url}")));
+    if total_size.is_none() {
+        pb.enable_steady_tick(Duration::from_millis(100));
+    }
 
     // Download chunks
-    let temp_file = tempfile::tempfile()?;
+    let cache_file = cache.as_ref().map(InstallerCache::temp_file).transpose()?;
+    let temp_file = match &cache_file {
+        Some(cache_file) => cache_file.as_file().try_clone()?,
+        None => tempfile::tempfile()?,
+    };
     let mut file = tokio::fs::File::from_std(temp_file.try_clone()?);
     let mut downloaded = 0;
     let mut stream = res.bytes_stream();
 
     let mut hasher = Sha256::new();
+    let mut attempt = 1;
     while let Some(item) = stream.next().await {
-        let chunk = item?;
+        let chunk = match item {
+            Ok(chunk) => chunk,
+            Err(error) if attempt >= MAX_ATTEMPTS => {
+                return Err(error).wrap_err_with(|| {
+                    format!("Failed to download '{url}' after {MAX_ATTEMPTS} attempts")
+                })
+            }
+            Err(_) => {
+                sleep(backoff(attempt)).await;
+                attempt += 1;
+                let resume = accepts_ranges && downloaded > 0;
+                let response = send_with_retry(
+                    || {
+                        let request = client.get(url.as_str());
+                        match (resume, if_range) {
+                            (true, Some(if_range)) => request
+                                .header(RANGE, format!("bytes={downloaded}-"))
+                                .header(IF_RANGE, if_range),
+                            (true, None) => request.header(RANGE, format!("bytes={downloaded}-")),
+                            (false, _) => request,
+                        }
+                    },
+                    &url,
+                )
+                .await?;
+                if !(resume && resumes_at(&response, downloaded)) {
+                    if response.status() != StatusCode::OK {
+                        bail!(
+                            "Unexpected {} response when resuming the download from '{url}'",
+                            response.status()
+                        );
+                    }
+                    // The server sent the whole file so the download and hash start again
+                    file.set_len(0).await?;
+                    file.seek(SeekFrom::Start(0)).await?;
+                    hasher = Sha256::new();
+                    downloaded = 0;
+                    pb.set_position(0);
+                }
+                stream = response.bytes_stream();
+                continue;
+            }
+        };
+        attempt = 1;
         let write = file.write_all(&chunk);
         hasher.update(&chunk); // Hash file as it's downloading
-        let new = min(downloaded + (chunk.len() as u64), total_size);
-        downloaded = new;
-        pb.set_position(new);
+        downloaded += chunk.len() as u64;
+        pb.set_position(total_size.map_or(downloaded, |total_size| min(downloaded, total_size)));
         write.await?;
     }
+    file.flush().await?;
     pb.finish_and_clear();
 
+    let sha_256 = base16ct::upper::encode_string(&hasher.finalize());
+    // Only a download of the whole file is cached so that a cut off download isn't reused later
+    let is_complete = total_size.is_none_or(|total_size| downloaded == total_size);
+    let file = match (&cache, cache_file) {
+        (Some(cache), Some(cache_file)) if is_complete => {
+            let entry = CacheEntry::new(
+                url.as_str(),
+                etag,
+                last_modified_header,
+                sha_256.clone(),
+                file_name.clone(),
+                downloaded,
+            );
+            cache
+                .insert(cache_file, &entry)
+                .wrap_err_with(|| format!("Failed to cache the download from '{url}'"))?
+        }
+        _ => temp_file,
+    };
+
     Ok(DownloadedFile {
         url,
-        file: temp_file,
-        sha_256: base16ct::upper::encode_string(&hasher.finalize()),
+        file,
+        sha_256,
         file_name,
         last_modified,
     })
 }
 
+fn header_string(headers: &HeaderMap, name: impl reqwest::header::AsHeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned)
+}
+
+fn parse_last_modified(last_modified: Option<&str>) -> Option<Date> {
+    last_modified
+        .and_then(|last_modified| OffsetDateTime::parse(last_modified, &Rfc2822).ok())
+        .map(OffsetDateTime::date)
+}
+
 fn get_file_name(url: &Url, content_disposition: Option<&HeaderValue>) -> String {
     if let Some(content_disposition) = content_disposition.and_then(|value| value.to_str().ok()) {
         let mut sections = content_disposition.split(';');
@@ -127,11 +318,22 @@ pub async fn process_files<'a>(
              file_name,
              last_modified,
          }| async move {
+            let cache = InstallerCache::open();
+            if let Some(mut file_analyser) = cache
+                .as_ref()
+                .and_then(|cache| cache.analysis(url.as_str(), &sha_256))
+            {
+                file_analyser.last_modified = last_modified;
+                return Ok((url, file_analyser));
+            }
             let mut file_analyser = FileAnalyser::new(&file, Cow::Owned(file_name), false)?;
             file_analyser.architecture =
                 find_architecture(url.as_str()).unwrap_or(file_analyser.architecture);
             file_analyser.installer_sha_256 = sha_256;
             file_analyser.last_modified = last_modified;
+            if let Some(cache) = &cache {
+                cache.set_analysis(url.as_str(), &file_analyser)?;
+            }
             Ok((url, file_analyser))
         },
     ))
@@ -139,3 +341,37 @@ pub async fn process_files<'a>(
     .try_collect::<HashMap<_, _>>()
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::download_file::{backoff, content_range_start, send_with_retry};
+    use crate::test_utils::serve;
+    use crate::types::urls::url::Url;
+    use reqwest::Client;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    #[test]
+    fn test_content_range_start() {
+        assert_eq!(Some(100), content_range_start("bytes 100-199/200"));
+        assert_eq!(Some(0), content_range_start("bytes 0-199/*"));
+        assert_eq!(None, content_range_start("bytes */200"));
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(Duration::from_secs(1), backoff(1));
+        assert_eq!(Duration::from_secs(2), backoff(2));
+        assert_eq!(Duration::from_secs(8), backoff(4));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_is_not_downloaded() {
+        let url = Url::from_str(serve(&[]).join("setup.exe").unwrap().as_str()).unwrap();
+        let client = Client::new();
+        let error = send_with_retry(|| client.get(url.as_str()), &url)
+            .await
+            .unwrap_err();
+        assert!(format!("{error:?}").contains("404 Not Found"));
+    }
+}
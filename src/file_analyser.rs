@@ -1,14 +1,24 @@
+use crate::authenticode::Authenticode;
+use crate::burn::Burn;
 use crate::exe::vs_version_info::VSVersionInfo;
-use crate::manifests::installer_manifest::Platform;
+use crate::inno::Inno;
+use crate::manifests::installer_manifest::{
+    AppsAndFeaturesEntry, ElevationRequirement, InstallationMetadata, Platform, Scope,
+};
 use crate::msi::Msi;
-use crate::msix_family::msix::Msix;
+use crate::msix_family::msix::{Msix, TargetDeviceFamily};
 use crate::msix_family::msixbundle::MsixBundle;
+use crate::nsis::Nsis;
 use crate::types::architecture::Architecture;
+use crate::types::command::Command;
 use crate::types::copyright::Copyright;
+use crate::types::file_extension::FileExtension;
 use crate::types::installer_type::InstallerType;
 use crate::types::language_tag::LanguageTag;
 use crate::types::minimum_os_version::MinimumOSVersion;
 use crate::types::package_name::PackageName;
+use crate::types::product_code::ProductCode;
+use crate::types::protocol::Protocol;
 use crate::types::publisher::Publisher;
 use crate::zip::Zip;
 use color_eyre::eyre::{OptionExt, Result};
@@ -16,15 +26,15 @@ use memmap2::Mmap;
 use object::pe::{ImageNtHeaders64, RT_RCDATA};
 use object::read::pe::{ImageNtHeaders, PeFile, PeFile32, PeFile64, ResourceDirectoryEntryData};
 use object::{FileKind, LittleEndian, ReadRef};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
 use std::borrow::Cow;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsStr;
 use std::fs::File;
 use std::io::Cursor;
-use std::mem;
 use std::path::Path;
 use time::Date;
-use uuid::Uuid;
 
 pub const EXE: &str = "exe";
 pub const MSI: &str = "msi";
@@ -34,6 +44,8 @@ pub const MSIX_BUNDLE: &str = "msixbundle";
 pub const APPX_BUNDLE: &str = "appxbundle";
 pub const ZIP: &str = "zip";
 
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
 pub struct FileAnalyser<'a> {
     pub platform: Option<BTreeSet<Platform>>,
     pub minimum_os_version: Option<MinimumOSVersion>,
@@ -42,14 +54,30 @@ pub struct FileAnalyser<'a> {
     pub installer_sha_256: String,
     pub signature_sha_256: Option<String>,
     pub package_family_name: Option<String>,
-    pub product_code: Option<Uuid>,
+    pub product_code: Option<ProductCode>,
     pub product_language: Option<LanguageTag>,
     pub last_modified: Option<Date>,
     pub file_name: Cow<'a, str>,
     pub copyright: Option<Copyright>,
     pub package_name: Option<PackageName>,
     pub publisher: Option<Publisher>,
+    pub scope: Option<Scope>,
+    pub elevation_requirement: Option<ElevationRequirement>,
+    pub installation_metadata: Option<InstallationMetadata>,
+    pub file_extensions: Option<BTreeSet<FileExtension>>,
+    pub protocols: Option<BTreeSet<Protocol>>,
+    pub commands: Option<BTreeSet<Command>>,
+    pub capabilities: Option<BTreeSet<String>>,
+    pub restricted_capabilities: Option<BTreeSet<String>>,
+    pub apps_and_features_entry: Option<AppsAndFeaturesEntry>,
+    pub authenticode: Option<Authenticode>,
+    pub version_info: Option<BTreeMap<String, String>>,
     pub msi: Option<Msi>,
+    pub burn: Option<Burn>,
+    pub inno: Option<Inno>,
+    pub nsis: Option<Nsis>,
+    pub msix: Option<Msix>,
+    pub msix_bundle: Option<MsixBundle>,
     pub zip: Option<Zip>,
 }
 
@@ -66,8 +94,15 @@ impl<'a> FileAnalyser<'a> {
             MSI => Some(Msi::new(Cursor::new(map.as_ref()))?),
             _ => None,
         };
+        let mut authenticode = match extension.as_str() {
+            MSI => Some(Authenticode::from_msi(Cursor::new(map.as_ref()))),
+            _ => None,
+        };
         let mut pe_arch = None;
         let mut string_map = None;
+        let mut burn = None;
+        let mut inno = None;
+        let mut nsis = None;
         match (extension == EXE)
             .then(|| FileKind::parse(map.as_ref()).ok())
             .flatten()
@@ -80,9 +115,16 @@ impl<'a> FileAnalyser<'a> {
                     &extension,
                     msi.as_ref(),
                 )?);
-                if installer_type == Some(InstallerType::Burn) {
-                    msi = Some(extract_msi(&pe_file)?);
+                match installer_type {
+                    Some(InstallerType::Burn) => match Burn::new(&pe_file, map.as_ref()) {
+                        Ok(bundle) => burn = Some(bundle),
+                        Err(_) => msi = extract_msi(&pe_file).ok(),
+                    },
+                    Some(InstallerType::Inno) => inno = Inno::new(&pe_file, map.as_ref()).ok(),
+                    Some(InstallerType::Nullsoft) => nsis = Nsis::new(map.as_ref()).ok(),
+                    _ => {}
                 }
+                authenticode = Some(Authenticode::from_pe(&pe_file));
                 pe_arch = Some(Architecture::get_from_exe(&pe_file)?);
                 string_map = VSVersionInfo::parse(&pe_file, map.as_ref())?
                     .string_file_info
@@ -98,9 +140,16 @@ impl<'a> FileAnalyser<'a> {
                     &extension,
                     msi.as_ref(),
                 )?);
-                if installer_type == Some(InstallerType::Burn) {
-                    msi = Some(extract_msi(&pe_file)?);
+                match installer_type {
+                    Some(InstallerType::Burn) => match Burn::new(&pe_file, map.as_ref()) {
+                        Ok(bundle) => burn = Some(bundle),
+                        Err(_) => msi = extract_msi(&pe_file).ok(),
+                    },
+                    Some(InstallerType::Inno) => inno = Inno::new(&pe_file, map.as_ref()).ok(),
+                    Some(InstallerType::Nullsoft) => nsis = Nsis::new(map.as_ref()).ok(),
+                    _ => {}
                 }
+                authenticode = Some(Authenticode::from_pe(&pe_file));
                 pe_arch = Some(Architecture::get_from_exe(&pe_file)?);
                 string_map = VSVersionInfo::parse(&pe_file, map.as_ref())?
                     .string_file_info
@@ -110,11 +159,11 @@ impl<'a> FileAnalyser<'a> {
             }
             _ => {}
         }
-        let mut msix = match extension.as_str() {
+        let msix = match extension.as_str() {
             MSIX | APPX => Some(Msix::new(Cursor::new(map.as_ref()))?),
             _ => None,
         };
-        let mut msix_bundle = match extension.as_str() {
+        let msix_bundle = match extension.as_str() {
             MSIX_BUNDLE | APPX_BUNDLE => Some(MsixBundle::new(Cursor::new(map.as_ref()))?),
             _ => None,
         };
@@ -135,40 +184,149 @@ impl<'a> FileAnalyser<'a> {
                 msi.as_ref(),
             )?);
         }
+        let target_device_families = msix
+            .iter()
+            .flat_map(|msix| &msix.target_device_families)
+            .chain(
+                msix_bundle
+                    .iter()
+                    .flat_map(|msix_bundle| &msix_bundle.packages)
+                    .flat_map(|package| &package.target_device_families),
+            );
+        let version_info = string_map
+            .as_ref()
+            .map(|string_map| string_map.clone().into_iter().collect());
         Ok(Self {
-            platform: msix
-                .as_ref()
-                .map(|msix| BTreeSet::from([msix.target_device_family])),
-            minimum_os_version: msix.as_mut().map(|msix| mem::take(&mut msix.min_version)),
+            platform: Some(TargetDeviceFamily::platforms(
+                target_device_families.clone(),
+            ))
+            .filter(|platform| !platform.is_empty()),
+            minimum_os_version: TargetDeviceFamily::lowest_min_version(target_device_families),
             architecture: msi
                 .as_ref()
-                .map(|msi| msi.architecture)
+                .and_then(|msi| msi.architecture)
                 .or_else(|| msix.as_ref().map(|msix| msix.processor_architecture))
-                .unwrap_or_else(|| pe_arch.unwrap()),
+                .or_else(|| {
+                    // A bundle only has one architecture if all of its packages share it
+                    let targets = msix_bundle.as_ref()?.targets();
+                    let mut architectures = targets.into_keys();
+                    architectures
+                        .next()
+                        .filter(|_| architectures.next().is_none())
+                })
+                .or_else(|| burn.as_ref().map(Burn::architecture))
+                .or_else(|| inno.as_ref().map(Inno::architecture))
+                .or(pe_arch)
+                .unwrap_or_default(),
             installer_type: installer_type.unwrap(),
             installer_sha_256: String::new(),
             signature_sha_256: msix
-                .as_mut()
-                .map(|msix| mem::take(&mut msix.signature_sha_256))
+                .as_ref()
+                .map(|msix| msix.signature_sha_256.clone())
                 .or_else(|| {
                     msix_bundle
-                        .as_mut()
-                        .map(|msix_bundle| mem::take(&mut msix_bundle.signature_sha_256))
+                        .as_ref()
+                        .map(|msix_bundle| msix_bundle.signature_sha_256.clone())
                 }),
             package_family_name: msix
-                .map(|msix| msix.package_family_name)
-                .or_else(|| msix_bundle.map(|msix_bundle| msix_bundle.package_family_name)),
-            product_code: msi.as_mut().map(|msi| mem::take(&mut msi.product_code)),
-            product_language: msi.as_mut().map(|msi| mem::take(&mut msi.product_language)),
+                .as_ref()
+                .map(|msix| msix.package_family_name.clone())
+                .or_else(|| {
+                    msix_bundle
+                        .as_ref()
+                        .map(|msix_bundle| msix_bundle.package_family_name.clone())
+                }),
+            product_code: msi
+                .as_ref()
+                .and_then(|msi| msi.product_code.as_ref())
+                .map(ProductCode::from_uuid)
+                .or_else(|| burn.as_ref().map(Burn::product_code))
+                .or_else(|| inno.as_ref().and_then(Inno::product_code))
+                .or_else(|| nsis.as_ref().and_then(Nsis::product_code)),
+            product_language: msi.as_ref().and_then(|msi| msi.product_language.clone()),
             last_modified: None,
             file_name,
             copyright: string_map.as_mut().and_then(Copyright::get_from_exe),
-            package_name: string_map.as_mut().and_then(PackageName::get_from_exe),
-            publisher: string_map.as_mut().and_then(Publisher::get_from_exe),
+            package_name: string_map
+                .as_mut()
+                .and_then(PackageName::get_from_exe)
+                .or_else(|| {
+                    inno.as_ref()
+                        .and_then(|inno| PackageName::new(inno.app_name.clone()?).ok())
+                }),
+            publisher: string_map
+                .as_mut()
+                .and_then(Publisher::get_from_exe)
+                .or_else(|| {
+                    inno.as_ref()
+                        .and_then(|inno| inno.app_publisher.clone())
+                        .or_else(|| nsis.as_ref().and_then(|nsis| nsis.publisher.clone()))
+                        .and_then(|publisher| Publisher::new(publisher).ok())
+                }),
+            scope: msi
+                .as_ref()
+                .and_then(|msi| msi.scope)
+                .or_else(|| burn.as_ref().map(Burn::scope))
+                .or_else(|| inno.as_ref().and_then(Inno::scope))
+                .or_else(|| nsis.as_ref().and_then(|nsis| nsis.scope)),
+            elevation_requirement: inno.as_ref().and_then(Inno::elevation_requirement),
+            installation_metadata: msi
+                .as_ref()
+                .and_then(Msi::installation_metadata)
+                .or_else(|| inno.as_ref().and_then(Inno::installation_metadata)),
+            file_extensions: msi
+                .as_ref()
+                .and_then(|msi| msi.file_extensions.clone())
+                .or_else(|| msix.as_ref().and_then(|msix| msix.file_extensions.clone())),
+            protocols: msi
+                .as_ref()
+                .and_then(|msi| msi.protocols.clone())
+                .or_else(|| msix.as_ref().and_then(|msix| msix.protocols.clone())),
+            commands: msix.as_ref().and_then(|msix| msix.commands.clone()),
+            capabilities: msix.as_ref().and_then(|msix| msix.capabilities.clone()),
+            restricted_capabilities: msix
+                .as_ref()
+                .and_then(|msix| msix.restricted_capabilities.clone()),
+            // System components are hidden from Apps and Features
+            apps_and_features_entry: msi
+                .as_ref()
+                .filter(|msi| !msi.arp_system_component)
+                .map(|msi| AppsAndFeaturesEntry {
+                    display_name: msi.product_name.clone(),
+                    publisher: msi.manufacturer.clone(),
+                    display_version: msi.product_version.clone(),
+                    upgrade_code: msi.upgrade_code,
+                    ..AppsAndFeaturesEntry::default()
+                })
+                .or_else(|| burn.as_ref().map(Burn::apps_and_features_entry))
+                .or_else(|| inno.as_ref().map(Inno::apps_and_features_entry))
+                .or_else(|| nsis.as_ref().map(Nsis::apps_and_features_entry)),
+            authenticode,
+            version_info,
             msi,
+            burn,
+            inno,
+            nsis,
+            msix,
+            msix_bundle,
             zip,
         })
     }
+
+    /// Problems with the installer that the user should check, such as a changed signer or MSI
+    /// properties that couldn't be read
+    pub fn warnings(&self, previous_publisher: Option<&str>) -> Vec<String> {
+        self.authenticode
+            .as_ref()
+            .and_then(|authenticode| authenticode.warning(&self.file_name, previous_publisher))
+            .into_iter()
+            .chain(self.msi.iter().flat_map(|msi| {
+                msi.errors
+                    .iter()
+                    .map(|error| format!("{}: {error}", self.file_name))
+            }))
+            .collect()
+    }
 }
 
 pub fn extract_msi<'data, Pe, R>(pe: &PeFile<'data, Pe, R>) -> Result<Msi>
@@ -1,6 +1,7 @@
+use crate::config::Config;
 use crate::credential::get_default_headers;
 use crate::github::graphql::create_commit::{
-    CommitMessage, CommittableBranch, CreateCommit, CreateCommitOnBranchInput,
+    Base64String, CommitMessage, CommittableBranch, CreateCommit, CreateCommitOnBranchInput,
     CreateCommitVariables, FileAddition, FileChanges, FileDeletion,
 };
 use crate::github::graphql::create_pull_request::{
@@ -20,55 +21,62 @@ use crate::github::graphql::get_directory_content::{
 use crate::github::graphql::get_directory_content_with_text::{
     GetDirectoryContentWithText, GitObject,
 };
+use crate::github::graphql::get_latest_release::{GetLatestRelease, GetLatestReleaseVariables};
 use crate::github::graphql::get_pull_request_from_branch::{
     GetPullRequestFromBranch, GetPullRequestFromBranchVariables, PullRequest,
 };
 use crate::github::graphql::get_repository_info::{
     GetRepositoryInfo, GitObjectId, RepositoryVariables,
 };
-use crate::github::utils::get_package_path;
-use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
+use crate::github::repository::GitHubRepository;
+use crate::github::utils::{get_branch_name, get_package_path};
+use crate::manifests::default_locale_manifest::{DefaultLocaleManifest, Documentation};
 use crate::manifests::installer_manifest::InstallerManifest;
 use crate::manifests::locale_manifest::LocaleManifest;
 use crate::manifests::version_manifest::VersionManifest;
+use crate::prompts::list_prompt::ListPrompt;
+use crate::storage::{Changes, Storage, Submission};
 use crate::types::license::License;
+use crate::types::moniker::Moniker;
 use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
 use crate::types::release_notes::ReleaseNotes;
+use crate::types::tag::Tag;
 use crate::types::urls::license_url::LicenseUrl;
 use crate::types::urls::package_url::PackageUrl;
 use crate::types::urls::publisher_url::PublisherUrl;
 use crate::types::urls::release_notes_url::ReleaseNotesUrl;
+use base64ct::Encoding;
 use color_eyre::eyre::{bail, eyre, OptionExt, Result};
-use const_format::formatcp;
 use cynic::http::ReqwestExt;
 use cynic::{Id, MutationBuilder, QueryBuilder};
 use reqwest::Client;
 use std::collections::BTreeSet;
-use std::env;
+use std::fmt::{Display, Formatter};
 use std::ops::Not;
 use std::path::Path;
 use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
 use url::Url;
 
 pub const MICROSOFT: &str = "Microsoft";
 pub const WINGET_PKGS: &str = "winget-pkgs";
-pub const WINGET_PKGS_FULL_NAME: &str = formatcp!("{MICROSOFT}/{WINGET_PKGS}");
 const GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
 
-pub struct GitHub(Client);
+pub struct GitHub {
+    client: Client,
+    repository: GitHubRepository,
+}
 
 impl GitHub {
-    pub fn new(token: String) -> Result<Self> {
-        Ok(Self(
-            Client::builder()
+    pub fn new(token: String, repository: GitHubRepository) -> Result<Self> {
+        Ok(Self {
+            client: Client::builder()
                 .default_headers(get_default_headers(Some(&token)))
                 .build()?,
-        ))
-    }
-
-    pub async fn get_versions(&self, path: &str) -> Result<BTreeSet<PackageVersion>> {
-        Self::get_all_versions(&self.0, MICROSOFT, WINGET_PKGS, path).await
+            repository,
+        })
     }
 
     async fn get_all_versions(
@@ -116,68 +124,6 @@ impl GitHub {
         Ok(files)
     }
 
-    pub async fn get_manifests(
-        &self,
-        identifier: &PackageIdentifier,
-        latest_version: &PackageVersion,
-    ) -> Result<Manifests> {
-        let full_package_path = get_package_path(identifier, Some(latest_version));
-        let content = Self::get_directory_content_with_text(
-            &self.0,
-            MICROSOFT,
-            WINGET_PKGS,
-            &full_package_path,
-        )
-        .await?
-        .collect::<Vec<_>>();
-
-        let version_manifest = content
-            .iter()
-            .find(|file| file.name == format!("{identifier}.yaml"))
-            .map(|file| serde_yaml::from_str::<VersionManifest>(&file.text))
-            .transpose()?
-            .ok_or_else(|| eyre!("No version manifest was found in {full_package_path}"))?;
-
-        let locale_manifests = content
-            .iter()
-            .filter(|file| {
-                file.name.starts_with(&format!("{identifier}.locale."))
-                    && !file.name.contains(version_manifest.default_locale.as_str())
-                    && Path::new(&file.name)
-                        .extension()
-                        .map_or(false, |ext| ext.eq_ignore_ascii_case("yaml"))
-            })
-            .map(|file| serde_yaml::from_str::<LocaleManifest>(&file.text).unwrap())
-            .collect::<Vec<_>>();
-
-        let default_locale_manifest = content
-            .iter()
-            .find(|file| {
-                file.name
-                    == format!(
-                        "{identifier}.locale.{}.yaml",
-                        version_manifest.default_locale
-                    )
-            })
-            .map(|file| serde_yaml::from_str::<DefaultLocaleManifest>(&file.text))
-            .transpose()?
-            .ok_or_else(|| eyre!("No default locale manifest was found in {full_package_path}"))?;
-
-        let installer_manifest = content
-            .into_iter()
-            .find(|file| file.name == format!("{identifier}.installer.yaml"))
-            .map(|file| serde_yaml::from_str::<InstallerManifest>(&file.text))
-            .transpose()?
-            .ok_or_else(|| eyre!("No installer manifest was found in {full_package_path}"))?;
-
-        Ok(Manifests {
-            installer_manifest,
-            default_locale_manifest,
-            version_manifest,
-            locale_manifests,
-        })
-    }
-
     async fn get_directory_content_with_text(
         client: &Client,
         owner: &str,
@@ -224,11 +170,10 @@ impl GitHub {
     }
 
     pub async fn get_username(&self) -> Result<String> {
-        const KOMAC_FORK_OWNER: &str = "KOMAC_FORK_OWNER";
-        match env::var(KOMAC_FORK_OWNER) {
-            Ok(login) => Ok(login),
-            _ => self
-                .0
+        match Config::get().fork_owner() {
+            Some(login) => Ok(login),
+            None => self
+                .client
                 .post(GITHUB_GRAPHQL_URL)
                 .run_graphql(GetCurrentUserLogin::build(()))
                 .await?
@@ -239,7 +184,12 @@ impl GitHub {
     }
 
     pub async fn get_winget_pkgs(&self, username: Option<&str>) -> Result<RepositoryData> {
-        Self::get_repository_info(&self.0, username.unwrap_or(MICROSOFT), WINGET_PKGS).await
+        Self::get_repository_info(
+            &self.client,
+            username.unwrap_or(&self.repository.owner),
+            &self.repository.name,
+        )
+        .await
     }
 
     async fn get_repository_info(
@@ -284,7 +234,7 @@ impl GitHub {
         oid: &str,
     ) -> Result<CreateBranchRef> {
         let r#ref = self
-            .0
+            .client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(CreateRef::build(CreateRefVariables {
                 name: &format!("refs/heads/{branch_name}"),
@@ -308,7 +258,7 @@ impl GitHub {
         additions: Option<Vec<FileAddition<'_>>>,
         deletions: Option<Vec<FileDeletion<'_>>>,
     ) -> Result<Url> {
-        self.0
+        self.client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(CreateCommit::build(CreateCommitVariables {
                 input: CreateCommitOnBranchInput {
@@ -344,11 +294,11 @@ impl GitHub {
         path: &str,
     ) -> Result<impl Iterator<Item = String> + Sized> {
         let entries = self
-            .0
+            .client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(GetDirectoryContent::build(GetDirectoryContentVariables {
                 expression: &format!("{branch_name}:{path}"),
-                name: WINGET_PKGS,
+                name: &self.repository.name,
                 owner,
             }))
             .await?
@@ -374,14 +324,14 @@ impl GitHub {
         let operation = GetPullRequestFromBranch::build(GetPullRequestFromBranchVariables {
             base_ref_name: default_branch_name,
             head_ref_name: branch_name,
-            name: WINGET_PKGS,
-            owner: MICROSOFT,
+            name: &self.repository.name,
+            owner: &self.repository.owner,
         });
-        let mut nodes = self.0.post(GITHUB_GRAPHQL_URL).run_graphql(operation).await?
+        let mut nodes = self.client.post(GITHUB_GRAPHQL_URL).run_graphql(operation).await?
             .data
             .and_then(|data| data.repository)
             .map(|repository| repository.pull_requests.nodes)
-            .ok_or_else(|| eyre!("No data was returned when getting an associated pull request for {branch_name} to {MICROSOFT}/{WINGET_PKGS}"))?;
+            .ok_or_else(|| eyre!("No data was returned when getting an associated pull request for {branch_name} to {}", self.repository))?;
 
         if nodes.is_empty() {
             Ok(None)
@@ -390,19 +340,20 @@ impl GitHub {
         }
     }
 
-    pub async fn get_branches(&self, user: &str) -> Result<(Vec<GetBranchRef>, String)> {
+    pub async fn get_branches(&self, user: &str) -> Result<Vec<GetBranchRef>> {
+        let name = &self.repository.name;
         let repository = self
-            .0
+            .client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(GetBranches::build(RepositoryVariables {
-                name: WINGET_PKGS,
+                name,
                 owner: user,
             }))
             .await?
             .data
             .and_then(|data| data.repository)
             .ok_or_else(|| {
-                eyre!("No repository was returned when getting branches for {user}/{WINGET_PKGS}")
+                eyre!("No repository was returned when getting branches for {user}/{name}")
             })?;
 
         let default_branch_name = repository
@@ -410,12 +361,12 @@ impl GitHub {
             .map(|default_branch_ref| default_branch_ref.name)
             .ok_or_else(|| {
                 eyre!(
-                "No default branch reference was returned when getting branches for {user}/{WINGET_PKGS}"
+                "No default branch reference was returned when getting branches for {user}/{name}"
             )
             })?;
 
         let refs = repository.refs.map(|refs| refs.nodes).ok_or_else(|| {
-            eyre!("No references were returned when getting branches for {user}/{WINGET_PKGS}")
+            eyre!("No references were returned when getting branches for {user}/{name}")
         })?;
 
         let branches = refs
@@ -423,7 +374,7 @@ impl GitHub {
             .filter(|branch| branch.name != default_branch_name)
             .collect();
 
-        Ok((branches, default_branch_name))
+        Ok(branches)
     }
 
     pub async fn create_pull_request(
@@ -448,7 +399,7 @@ impl GitHub {
                 title,
             },
         });
-        self.0
+        self.client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(operation)
             .await?
@@ -465,7 +416,7 @@ impl GitHub {
 
     pub async fn delete_branch(&self, branch_id: &Id) -> Result<()> {
         let response = self
-            .0
+            .client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(DeleteRef::build(DeleteRefVariables { ref_: branch_id }))
             .await?;
@@ -483,7 +434,7 @@ impl GitHub {
         tag_name: String,
     ) -> Result<GitHubValues> {
         let data = self
-            .0
+            .client
             .post(GITHUB_GRAPHQL_URL)
             .run_graphql(GetAllValues::build(GetAllValuesVariables {
                 name: &repo,
@@ -504,37 +455,64 @@ impl GitHub {
     )
         })?;
 
-        let license_url = match object {
-            GetAllValuesGitObject::Tree(tree) => tree
-                .entries
-                .into_iter()
-                .filter_map(|entry| (entry.type_ == "blob").then_some(entry.name))
-                .find(|name| {
-                    name.rfind('.')
-                        .map_or(name.to_ascii_lowercase(), |dot_index| {
-                            name[..dot_index].to_ascii_lowercase()
-                        })
-                        == "license"
-                })
-                .map(|name| {
-                    LicenseUrl::from_str(&format!(
-                        "https://github.com/{owner}/{repo}/blob/HEAD/{name}"
-                    ))
-                }),
-            GetAllValuesGitObject::Unknown => None,
+        let entries = match object {
+            GetAllValuesGitObject::Tree(tree) => tree.entries,
+            GetAllValuesGitObject::Unknown => Vec::new(),
+        };
+
+        let license_url = entries
+            .iter()
+            .filter_map(|entry| (entry.type_ == "blob").then_some(&entry.name))
+            .find(|name| {
+                name.rfind('.')
+                    .map_or(name.to_ascii_lowercase(), |dot_index| {
+                        name[..dot_index].to_ascii_lowercase()
+                    })
+                    == "license"
+            })
+            .map(|name| {
+                LicenseUrl::from_str(&format!(
+                    "https://github.com/{owner}/{repo}/blob/HEAD/{name}"
+                ))
+            })
+            .transpose()?;
+
+        let mut documentations = BTreeSet::new();
+        if repository.has_wiki_enabled {
+            documentations.insert(Documentation {
+                document_label: Some(String::from("Wiki")),
+                document_url: Some(Url::parse(&format!(
+                    "https://github.com/{owner}/{repo}/wiki"
+                ))?),
+            });
+        }
+        if let Some(docs) = entries.iter().find(|entry| {
+            entry.type_ == "tree"
+                && matches!(
+                    entry.name.to_ascii_lowercase().as_str(),
+                    "doc" | "docs" | "documentation"
+                )
+        }) {
+            documentations.insert(Documentation {
+                document_label: Some(String::from("Documentation")),
+                document_url: Some(Url::parse(&format!(
+                    "https://github.com/{owner}/{repo}/tree/HEAD/{}",
+                    docs.name
+                ))?),
+            });
         }
-        .transpose()?;
 
         let release = repository
             .release
             .ok_or_else(|| eyre!("No release was found with the tag of {tag_name}"))?;
 
-        let topics = repository
+        let tags = repository
             .repository_topics
             .nodes
             .into_iter()
-            .map(|topic_node| topic_node.topic.name)
-            .collect::<Vec<_>>();
+            .filter_map(|topic_node| Tag::new(topic_node.topic.name).ok())
+            .take(Tag::MAX_ITEMS as usize)
+            .collect::<BTreeSet<_>>();
 
         let publisher_url = if repository.is_in_organization {
             data.organization
@@ -546,7 +524,8 @@ impl GitHub {
 
         let publisher_support_url = repository
             .has_issues_enabled
-            .then(|| format!("https://github.com/{owner}/{repo}/issues"));
+            .then(|| Url::parse(&format!("https://github.com/{owner}/{repo}/issues")))
+            .transpose()?;
 
         Ok(GitHubValues {
             publisher_url: PublisherUrl::from_str(publisher_url.as_str())?,
@@ -569,10 +548,128 @@ impl GitHub {
                 .description
                 .and_then(|body| ReleaseNotes::format(&body, &owner, &repo)),
             release_notes_url: ReleaseNotesUrl::from_str(release.url.as_str())?,
-            has_issues_enabled: repository.has_issues_enabled,
-            topics,
+            release_date: release.published_at.and_then(|published_at| {
+                OffsetDateTime::parse(&published_at.0, &Rfc3339)
+                    .ok()
+                    .map(OffsetDateTime::date)
+            }),
+            documentations: (!documentations.is_empty()).then_some(documentations),
+            moniker: Moniker::from_str(&repo.to_ascii_lowercase()).ok(),
+            tags: (!tags.is_empty()).then_some(tags),
         })
     }
+
+    pub async fn get_latest_release_tag(&self, owner: &str, repo: &str) -> Result<String> {
+        self.client
+            .post(GITHUB_GRAPHQL_URL)
+            .run_graphql(GetLatestRelease::build(GetLatestReleaseVariables {
+                owner,
+                name: repo,
+            }))
+            .await?
+            .data
+            .and_then(|data| data.repository)
+            .ok_or_else(|| {
+                eyre!(
+                    "No repository was returned when getting the latest release of {owner}/{repo}"
+                )
+            })?
+            .latest_release
+            .map(|release| release.tag_name)
+            .ok_or_else(|| eyre!("{owner}/{repo} does not have a latest release"))
+    }
+}
+
+impl Storage for GitHub {
+    async fn get_versions(
+        &self,
+        identifier: &PackageIdentifier,
+    ) -> Result<BTreeSet<PackageVersion>> {
+        Self::get_all_versions(
+            &self.client,
+            &self.repository.owner,
+            &self.repository.name,
+            &get_package_path(identifier, None),
+        )
+        .await
+    }
+
+    async fn get_manifests(
+        &self,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+    ) -> Result<Manifests> {
+        let full_package_path = get_package_path(identifier, Some(version));
+        let content = Self::get_directory_content_with_text(
+            &self.client,
+            &self.repository.owner,
+            &self.repository.name,
+            &full_package_path,
+        )
+        .await?
+        .collect::<Vec<_>>();
+
+        Manifests::from_files(identifier, &full_package_path, content)
+    }
+
+    async fn commit_changes(&self, changes: Changes<'_>) -> Result<Submission> {
+        let current_user = self.get_username().await?;
+        let winget_pkgs = self.get_winget_pkgs(None).await?;
+        let fork = self.get_winget_pkgs(Some(&current_user)).await?;
+        let branch_name = get_branch_name(changes.identifier, changes.version);
+        let pull_request_branch = self
+            .create_branch(&fork.id, &branch_name, &winget_pkgs.default_branch_oid.0)
+            .await?;
+        let mut deleted_files = match changes.deleted_directory {
+            Some(directory) => self
+                .get_directory_content(&current_user, &branch_name, directory)
+                .await?
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        deleted_files.extend_from_slice(changes.deleted_files);
+        let additions = changes
+            .additions
+            .iter()
+            .map(|(path, content)| FileAddition {
+                contents: Base64String(base64ct::Base64::encode_string(content.as_bytes())),
+                path,
+            })
+            .collect::<Vec<_>>();
+        let deletions = deleted_files
+            .iter()
+            .map(|path| FileDeletion { path })
+            .collect::<Vec<_>>();
+        let _commit_url = self
+            .create_commit(
+                &pull_request_branch.id,
+                &pull_request_branch
+                    .target
+                    .map(|target| target.oid.0)
+                    .ok_or_eyre("The created branch does not point to a commit")?,
+                changes.title,
+                (!additions.is_empty()).then_some(additions),
+                (!deletions.is_empty()).then_some(deletions),
+            )
+            .await?;
+        let pull_request_url = self
+            .create_pull_request(
+                &winget_pkgs.id,
+                &fork.id,
+                &format!("{current_user}:{}", pull_request_branch.name),
+                &winget_pkgs.default_branch_name,
+                changes.title,
+                changes.body,
+            )
+            .await?;
+        Ok(Submission::PullRequest(pull_request_url))
+    }
+}
+
+impl Display for GitHub {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.repository.fmt(f)
+    }
 }
 
 pub struct Manifests {
@@ -582,17 +679,106 @@ pub struct Manifests {
     pub locale_manifests: Vec<LocaleManifest>,
 }
 
+impl Manifests {
+    pub fn from_files(
+        identifier: &PackageIdentifier,
+        full_package_path: &str,
+        content: Vec<GitHubFile>,
+    ) -> Result<Self> {
+        let version_manifest = content
+            .iter()
+            .find(|file| file.name == format!("{identifier}.yaml"))
+            .map(|file| serde_yaml::from_str::<VersionManifest>(&file.text))
+            .transpose()?
+            .ok_or_else(|| eyre!("No version manifest was found in {full_package_path}"))?;
+
+        let locale_manifests = content
+            .iter()
+            .filter(|file| {
+                file.name.starts_with(&format!("{identifier}.locale."))
+                    && !file.name.contains(version_manifest.default_locale.as_str())
+                    && Path::new(&file.name)
+                        .extension()
+                        .map_or(false, |ext| ext.eq_ignore_ascii_case("yaml"))
+            })
+            .map(|file| serde_yaml::from_str::<LocaleManifest>(&file.text).unwrap())
+            .collect::<Vec<_>>();
+
+        let default_locale_manifest = content
+            .iter()
+            .find(|file| {
+                file.name
+                    == format!(
+                        "{identifier}.locale.{}.yaml",
+                        version_manifest.default_locale
+                    )
+            })
+            .map(|file| serde_yaml::from_str::<DefaultLocaleManifest>(&file.text))
+            .transpose()?
+            .ok_or_else(|| eyre!("No default locale manifest was found in {full_package_path}"))?;
+
+        let installer_manifest = content
+            .into_iter()
+            .find(|file| file.name == format!("{identifier}.installer.yaml"))
+            .map(|file| serde_yaml::from_str::<InstallerManifest>(&file.text))
+            .transpose()?
+            .ok_or_else(|| eyre!("No installer manifest was found in {full_package_path}"))?;
+
+        Ok(Self {
+            installer_manifest,
+            default_locale_manifest,
+            version_manifest,
+            locale_manifests,
+        })
+    }
+}
+
 pub struct GitHubValues {
     pub publisher_url: PublisherUrl,
-    pub publisher_support_url: Option<String>,
+    pub publisher_support_url: Option<Url>,
     pub short_description: String,
     pub license: Option<License>,
     pub license_url: Option<LicenseUrl>,
     pub package_url: PackageUrl,
     pub release_notes: Option<ReleaseNotes>,
     pub release_notes_url: ReleaseNotesUrl,
-    pub has_issues_enabled: bool,
-    pub topics: Vec<String>,
+    pub release_date: Option<Date>,
+    pub documentations: Option<BTreeSet<Documentation>>,
+    pub moniker: Option<Moniker>,
+    pub tags: Option<BTreeSet<Tag>>,
+}
+
+impl GitHubValues {
+    /// Fills in the repository's metadata that isn't specific to a release. Values the manifest
+    /// already has are kept unless `refresh` is true.
+    pub fn fill_metadata(&self, manifest: &mut DefaultLocaleManifest, refresh: bool) {
+        fn fill<T: Clone>(value: &mut Option<T>, github_value: Option<&T>, refresh: bool) {
+            if refresh || value.is_none() {
+                if let Some(github_value) = github_value {
+                    *value = Some(github_value.clone());
+                }
+            }
+        }
+
+        fill(
+            &mut manifest.publisher_url,
+            Some(&self.publisher_url),
+            refresh,
+        );
+        fill(
+            &mut manifest.publisher_support_url,
+            self.publisher_support_url.as_ref(),
+            refresh,
+        );
+        fill(&mut manifest.package_url, Some(&self.package_url), refresh);
+        fill(
+            &mut manifest.documentations,
+            self.documentations.as_ref(),
+            refresh,
+        );
+        fill(&mut manifest.moniker, self.moniker.as_ref(), refresh);
+        fill(&mut manifest.tags, self.tags.as_ref(), refresh);
+    }
 }
 
 pub struct GitHubFile {
@@ -605,3 +791,63 @@ pub struct RepositoryData {
     pub default_branch_name: String,
     pub default_branch_oid: GitObjectId,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::github::github_client::GitHubValues;
+    use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
+    use crate::types::moniker::Moniker;
+    use crate::types::tag::Tag;
+    use crate::types::urls::package_url::PackageUrl;
+    use crate::types::urls::publisher_url::PublisherUrl;
+    use crate::types::urls::release_notes_url::ReleaseNotesUrl;
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+    use url::Url;
+
+    fn github_values() -> GitHubValues {
+        GitHubValues {
+            publisher_url: PublisherUrl::from_str("https://github.com/owner").unwrap(),
+            publisher_support_url: Url::parse("https://github.com/owner/repo/issues").ok(),
+            short_description: String::new(),
+            license: None,
+            license_url: None,
+            package_url: PackageUrl::from_str("https://github.com/owner/repo").unwrap(),
+            release_notes: None,
+            release_notes_url: ReleaseNotesUrl::from_str(
+                "https://github.com/owner/repo/releases/tag/v1.0.0",
+            )
+            .unwrap(),
+            release_date: None,
+            documentations: None,
+            moniker: Moniker::from_str("repo").ok(),
+            tags: Some(BTreeSet::from([Tag::from_str("topic").unwrap()])),
+        }
+    }
+
+    fn manifest() -> DefaultLocaleManifest {
+        DefaultLocaleManifest {
+            moniker: Moniker::from_str("previous").ok(),
+            ..DefaultLocaleManifest::default()
+        }
+    }
+
+    #[test]
+    fn test_fill_metadata_keeps_previous_values() {
+        let mut manifest = manifest();
+        github_values().fill_metadata(&mut manifest, false);
+        assert_eq!(manifest.moniker.unwrap().to_string(), "previous");
+        assert_eq!(
+            manifest.publisher_support_url.unwrap().as_str(),
+            "https://github.com/owner/repo/issues"
+        );
+        assert!(manifest.tags == Some(BTreeSet::from([Tag::from_str("topic").unwrap()])));
+    }
+
+    #[test]
+    fn test_fill_metadata_refresh() {
+        let mut manifest = manifest();
+        github_values().fill_metadata(&mut manifest, true);
+        assert_eq!(manifest.moniker.unwrap().to_string(), "repo");
+    }
+}
@@ -6,6 +6,7 @@ query GetAllValues($owner: String!, $name: String!, $tagName: String!) {
   repository(owner: $owner, name: $name) {
     description
     hasIssuesEnabled
+    hasWikiEnabled
     isInOrganization
     licenseInfo {
       key
@@ -23,6 +24,7 @@ query GetAllValues($owner: String!, $name: String!, $tagName: String!) {
     }
     release(tagName: $tagName) {
       description
+      publishedAt
       url
     }
     repositoryTopics(first: 16) {
@@ -85,6 +87,7 @@ pub struct User {
 pub struct Repository {
     pub description: Option<String>,
     pub has_issues_enabled: bool,
+    pub has_wiki_enabled: bool,
     pub is_in_organization: bool,
     pub license_info: Option<License>,
     #[arguments(expression: "HEAD:")]
@@ -115,9 +118,13 @@ pub struct Topic {
 #[derive(cynic::QueryFragment)]
 pub struct Release {
     pub description: Option<String>,
+    pub published_at: Option<DateTime>,
     pub url: Url,
 }
 
+#[derive(cynic::Scalar)]
+pub struct DateTime(pub String);
+
 #[derive(cynic::QueryFragment)]
 pub struct Organization {
     pub url: Url,
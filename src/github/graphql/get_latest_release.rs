@@ -0,0 +1,34 @@
+use crate::github::graphql::github_schema::github_schema as schema;
+
+/*
+query GetLatestRelease($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    latestRelease {
+      tagName
+    }
+  }
+}
+*/
+
+#[derive(cynic::QueryVariables)]
+pub struct GetLatestReleaseVariables<'a> {
+    pub owner: &'a str,
+    pub name: &'a str,
+}
+
+#[derive(cynic::QueryFragment)]
+#[cynic(graphql_type = "Query", variables = "GetLatestReleaseVariables")]
+pub struct GetLatestRelease {
+    #[arguments(owner: $owner, name: $name)]
+    pub repository: Option<Repository>,
+}
+
+#[derive(cynic::QueryFragment)]
+pub struct Repository {
+    pub latest_release: Option<Release>,
+}
+
+#[derive(cynic::QueryFragment)]
+pub struct Release {
+    pub tag_name: String,
+}
@@ -8,6 +8,7 @@ pub mod get_current_user_login;
 pub mod get_deep_directory_content;
 pub mod get_directory_content;
 pub mod get_directory_content_with_text;
+pub mod get_latest_release;
 pub mod get_pull_request_from_branch;
 pub mod get_repository_info;
 pub mod github_schema;
@@ -1,3 +1,4 @@
 pub mod github_client;
 pub mod graphql;
+pub mod repository;
 pub mod utils;
@@ -0,0 +1,90 @@
+use crate::github::github_client::{MICROSOFT, WINGET_PKGS};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A GitHub repository in the form of `owner/name` that manifests are read from and pull requests
+/// are created to
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitHubRepository {
+    pub owner: String,
+    pub name: String,
+}
+
+impl Default for GitHubRepository {
+    fn default() -> Self {
+        Self {
+            owner: MICROSOFT.to_owned(),
+            name: WINGET_PKGS.to_owned(),
+        }
+    }
+}
+
+impl FromStr for GitHubRepository {
+    type Err = GitHubRepositoryError;
+
+    fn from_str(repository: &str) -> Result<Self, Self::Err> {
+        let (owner, name) = repository
+            .split_once('/')
+            .ok_or(GitHubRepositoryError::MissingSeparator)?;
+        if owner.is_empty() || name.is_empty() || name.contains('/') {
+            return Err(GitHubRepositoryError::InvalidFormat);
+        }
+        Ok(Self {
+            owner: owner.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+}
+
+impl Display for GitHubRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum GitHubRepositoryError {
+    #[error("Repository must be in the form of owner/name")]
+    MissingSeparator,
+    #[error("Repository must have exactly one owner and one name separated by a /")]
+    InvalidFormat,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::github::repository::{GitHubRepository, GitHubRepositoryError};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_repository() {
+        let repository = GitHubRepository::from_str("owner/winget-mirror").unwrap();
+        assert_eq!("owner", repository.owner);
+        assert_eq!("winget-mirror", repository.name);
+        assert_eq!("owner/winget-mirror", repository.to_string());
+    }
+
+    #[test]
+    fn test_invalid_repositories() {
+        assert_eq!(
+            Err(GitHubRepositoryError::MissingSeparator),
+            GitHubRepository::from_str("winget-pkgs")
+        );
+        assert_eq!(
+            Err(GitHubRepositoryError::InvalidFormat),
+            GitHubRepository::from_str("owner/")
+        );
+        assert_eq!(
+            Err(GitHubRepositoryError::InvalidFormat),
+            GitHubRepository::from_str("owner/name/extra")
+        );
+    }
+
+    #[test]
+    fn test_default_repository() {
+        assert_eq!(
+            "Microsoft/winget-pkgs",
+            GitHubRepository::default().to_string()
+        );
+    }
+}
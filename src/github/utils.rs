@@ -1,9 +1,10 @@
+use crate::config::Config;
+use crate::manifest_diff::{pull_request_section, Change};
 use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
 use crate::update_state::UpdateState;
 use clap::{crate_name, crate_version};
 use rand::{thread_rng, Rng};
-use std::env;
 use uuid::Uuid;
 
 pub fn get_package_path(
@@ -30,7 +31,7 @@ pub fn get_package_path(
     result
 }
 
-pub fn get_pull_request_body() -> String {
+pub fn get_pull_request_body(changes: &[Change]) -> String {
     const FRUITS: [&str; 16] = [
         "apple",
         "banana",
@@ -50,10 +51,10 @@ pub fn get_pull_request_body() -> String {
         "watermelon",
     ];
 
-    let custom_tool_info = if let (Ok(tool_name), Ok(tool_url)) = (
-        env::var("KOMAC_CREATED_WITH"),
-        env::var("KOMAC_CREATED_WITH_URL"),
-    ) {
+    let config = Config::get();
+    let custom_tool_info = if let (Some(tool_name), Some(tool_url)) =
+        (config.created_with(), config.created_with_url())
+    {
         format!("[{tool_name}]({tool_url})")
     } else {
         format!(
@@ -72,7 +73,11 @@ pub fn get_pull_request_body() -> String {
         "rocket"
     };
 
-    format!("### Pull request has been created with {custom_tool_info} :{emoji}:")
+    let mut body = format!("### Pull request has been created with {custom_tool_info} :{emoji}:");
+    if let Some(section) = pull_request_section(changes) {
+        body.push_str(&section);
+    }
+    body
 }
 
 pub fn get_branch_name(
@@ -99,9 +104,59 @@ pub fn get_commit_title(
     format!("{update_state}: {identifier} version {version}")
 }
 
+/// The parts of a GitHub release asset URL such as
+/// `https://github.com/owner/repo/releases/download/v1.2.3/Asset-1.2.3.exe`
+#[derive(Debug, Eq, PartialEq)]
+pub struct GitHubReleaseAsset<'url> {
+    pub owner: &'url str,
+    pub repo: &'url str,
+    pub tag: &'url str,
+    pub asset: &'url str,
+}
+
+impl<'url> GitHubReleaseAsset<'url> {
+    pub fn parse(url: &'url url::Url) -> Option<Self> {
+        if url.host_str() != Some("github.com") {
+            return None;
+        }
+        let mut parts = url.path().trim_start_matches('/').splitn(5, '/');
+        let owner = parts.next()?;
+        let repo = parts.next()?;
+        if parts.next()? != "releases" || parts.next()? != "download" {
+            return None;
+        }
+        let (tag, asset) = parts.next()?.rsplit_once('/')?;
+        [owner, repo, tag, asset]
+            .iter()
+            .all(|part| !part.is_empty())
+            .then_some(Self {
+                owner,
+                repo,
+                tag,
+                asset,
+            })
+    }
+
+    /// Builds the URL of the same asset in another release, replacing the version in its name
+    pub fn to_release(&self, tag: &str, old_version: &str, new_version: &str) -> String {
+        format!(
+            "https://github.com/{}/{}/releases/download/{tag}/{}",
+            self.owner,
+            self.repo,
+            self.asset.replace(old_version, new_version)
+        )
+    }
+}
+
+/// Removes any prefix before the version in a tag, such as the `v` in `v1.2.3`
+pub fn get_version_from_tag(tag: &str) -> &str {
+    tag.find(|character: char| character.is_ascii_digit())
+        .map_or(tag, |index| &tag[index..])
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::github::utils::get_package_path;
+    use crate::github::utils::{get_package_path, get_version_from_tag, GitHubReleaseAsset};
     use crate::types::package_identifier::PackageIdentifier;
     use crate::types::package_version::PackageVersion;
 
@@ -123,4 +178,47 @@ mod tests {
             get_package_path(&identifier, Some(&version))
         );
     }
+
+    #[test]
+    fn test_parse_github_release_asset() {
+        let url = url::Url::parse(
+            "https://github.com/owner/repo/releases/download/v1.2.3/Asset-1.2.3-x64.exe",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(GitHubReleaseAsset {
+                owner: "owner",
+                repo: "repo",
+                tag: "v1.2.3",
+                asset: "Asset-1.2.3-x64.exe",
+            }),
+            GitHubReleaseAsset::parse(&url)
+        );
+    }
+
+    #[test]
+    fn test_parse_non_release_url() {
+        let url = url::Url::parse("https://github.com/owner/repo/archive/v1.2.3.zip").unwrap();
+        assert_eq!(None, GitHubReleaseAsset::parse(&url));
+    }
+
+    #[test]
+    fn test_github_release_asset_to_release() {
+        let url = url::Url::parse(
+            "https://github.com/owner/repo/releases/download/v1.2.3/Asset-1.2.3-x64.exe",
+        )
+        .unwrap();
+        let asset = GitHubReleaseAsset::parse(&url).unwrap();
+        assert_eq!(
+            "https://github.com/owner/repo/releases/download/v1.3.0/Asset-1.3.0-x64.exe",
+            asset.to_release("v1.3.0", get_version_from_tag(asset.tag), "1.3.0")
+        );
+    }
+
+    #[test]
+    fn test_version_from_tag() {
+        assert_eq!("1.2.3", get_version_from_tag("v1.2.3"));
+        assert_eq!("1.7", get_version_from_tag("jq-1.7"));
+        assert_eq!("1.2.3", get_version_from_tag("1.2.3"));
+    }
 }
@@ -0,0 +1,385 @@
+use crate::inno::version::InnoVersion;
+use color_eyre::eyre::{bail, OptionExt, Result};
+use serde::{Deserialize, Serialize};
+
+/// The values of the setup header that are used for manifests. Everything else is skipped.
+#[derive(Default)]
+pub struct Header {
+    pub app_name: String,
+    pub app_versioned_name: String,
+    pub app_id: String,
+    pub app_publisher: String,
+    pub app_version: String,
+    pub default_dir_name: String,
+    pub uninstall_name: String,
+    pub create_uninstall_registry_key: String,
+    pub architectures_allowed: Option<String>,
+    pub architectures_install_in_64_bit_mode: Option<String>,
+    pub privileges_required: Option<PrivilegesRequired>,
+    pub privileges_required_overrides_allowed: bool,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivilegesRequired {
+    None,
+    PowerUser,
+    Admin,
+    Lowest,
+}
+
+impl PrivilegesRequired {
+    const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Self::None),
+            1 => Some(Self::PowerUser),
+            2 => Some(Self::Admin),
+            3 => Some(Self::Lowest),
+            _ => None,
+        }
+    }
+}
+
+/// Before 6.3.0, architectures were stored as a set of flags instead of an expression
+const ARCHITECTURE_FLAGS: [&str; 5] = ["", "x86", "x64", "ia64", "arm64"];
+
+/// The newest version whose layout after the strings is known
+const LAST_KNOWN_VERSION: InnoVersion = InnoVersion::new(6, 4, u8::MAX);
+
+struct Reader<'data> {
+    data: &'data [u8],
+    position: usize,
+    unicode: bool,
+}
+
+impl<'data> Reader<'data> {
+    fn bytes(&mut self, length: usize) -> Result<&'data [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + length)
+            .ok_or_eyre("The Inno Setup header is truncated")?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<()> {
+        self.bytes(length).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn binary(&mut self) -> Result<&'data [u8]> {
+        let length = self.u32()? as usize;
+        self.bytes(length)
+    }
+
+    /// Reads a string that is UTF-16 in Unicode installers and in the installer's codepage
+    /// otherwise, which is read as Latin-1
+    fn string(&mut self) -> Result<String> {
+        let bytes = self.binary()?;
+        if self.unicode {
+            if bytes.len() % 2 != 0 {
+                bail!("An Inno Setup string has an odd length");
+            }
+            Ok(String::from_utf16_lossy(
+                &bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect::<Vec<_>>(),
+            ))
+        } else {
+            Ok(bytes.iter().copied().map(char::from).collect())
+        }
+    }
+}
+
+impl Header {
+    /// Reads the decompressed setup header, following the layout used by innoextract
+    pub fn read(data: &[u8], version: InnoVersion, unicode: bool) -> Result<Self> {
+        let mut reader = Reader {
+            data,
+            position: 0,
+            unicode,
+        };
+        let mut header = Self {
+            app_name: reader.string()?,
+            app_versioned_name: reader.string()?,
+            app_id: reader.string()?,
+            ..Self::default()
+        };
+        reader.string()?; // AppCopyright
+        header.app_publisher = reader.string()?;
+        reader.string()?; // AppPublisherURL
+        if version >= InnoVersion::new(5, 1, 13) {
+            reader.string()?; // AppSupportPhone
+        }
+        reader.string()?; // AppSupportURL
+        reader.string()?; // AppUpdatesURL
+        header.app_version = reader.string()?;
+        header.default_dir_name = reader.string()?;
+        reader.string()?; // DefaultGroupName
+        reader.string()?; // BaseFilename
+        if version < InnoVersion::new(5, 2, 5) {
+            // License, info before and info after texts
+            for _ in 0..3 {
+                reader.binary()?;
+            }
+        }
+        reader.string()?; // UninstallFilesDir
+        header.uninstall_name = reader.string()?;
+        reader.string()?; // UninstallDisplayIcon
+        reader.string()?; // AppMutex
+        reader.string()?; // DefaultUserInfoName
+        reader.string()?; // DefaultUserInfoOrg
+        reader.string()?; // DefaultUserInfoSerial
+        if version < InnoVersion::new(5, 2, 5) {
+            reader.binary()?; // CompiledCodeText
+        }
+        if version >= InnoVersion::new(4, 2, 4) {
+            // AppReadmeFile, AppContact, AppComments and AppModifyPath
+            for _ in 0..4 {
+                reader.string()?;
+            }
+        }
+        if version >= InnoVersion::new(5, 3, 8) {
+            header.create_uninstall_registry_key = reader.string()?;
+        }
+        if version >= InnoVersion::new(5, 3, 10) {
+            reader.string()?; // Uninstallable
+        }
+        if version >= InnoVersion::new(5, 5, 0) {
+            reader.string()?; // CloseApplicationsFilter
+        }
+        if version >= InnoVersion::new(5, 5, 6) {
+            reader.string()?; // SetupMutex
+        }
+        if version >= InnoVersion::new(5, 6, 1) {
+            reader.string()?; // ChangesEnvironment
+            reader.string()?; // ChangesAssociations
+        }
+        if version >= InnoVersion::new(6, 3, 0) {
+            header.architectures_allowed = Some(reader.string()?);
+            header.architectures_install_in_64_bit_mode = Some(reader.string()?);
+        }
+        if version >= InnoVersion::new(6, 4, 0) {
+            reader.string()?; // CloseApplicationsFilterExcludes
+        }
+        if version > LAST_KNOWN_VERSION {
+            return Ok(header);
+        }
+        if version >= InnoVersion::new(5, 2, 5) {
+            // License, info before and info after texts
+            for _ in 0..3 {
+                reader.binary()?;
+            }
+        }
+        if version >= InnoVersion::new(5, 2, 1) && version < InnoVersion::new(5, 3, 10) {
+            reader.binary()?; // Uninstaller signature
+        }
+        if version >= InnoVersion::new(5, 2, 5) {
+            reader.binary()?; // CompiledCodeText
+        }
+
+        // The numeric values are only read as far as the privileges and architectures. If they
+        // aren't valid, the layout of this version isn't the one expected and they're discarded.
+        let Ok(values) = read_values(&mut reader, version, unicode) else {
+            return Ok(header);
+        };
+        if let Some(values) = values {
+            header.privileges_required = Some(values.privileges_required);
+            header.privileges_required_overrides_allowed = values.overrides_allowed;
+            if version < InnoVersion::new(6, 3, 0) {
+                header.architectures_allowed =
+                    Some(architecture_flags(values.architectures_allowed));
+                header.architectures_install_in_64_bit_mode = Some(architecture_flags(
+                    values.architectures_install_in_64_bit_mode,
+                ));
+            }
+        }
+        Ok(header)
+    }
+}
+
+struct Values {
+    privileges_required: PrivilegesRequired,
+    overrides_allowed: bool,
+    architectures_allowed: u8,
+    architectures_install_in_64_bit_mode: u8,
+}
+
+fn read_values(reader: &mut Reader, version: InnoVersion, unicode: bool) -> Result<Option<Values>> {
+    if !unicode {
+        reader.skip(32)?; // Lead bytes
+    }
+    // Entry counts for languages, messages, permissions, types, components, tasks, directories,
+    // files, file locations, icons, ini entries, registry entries, install deletes, uninstall
+    // deletes, runs and uninstall runs
+    let entry_counts = if version >= InnoVersion::new(4, 2, 1) {
+        16
+    } else {
+        15
+    };
+    reader.skip(4 * entry_counts)?;
+    reader.skip(20)?; // MinVersion and OnlyBelowVersion
+    reader.skip(8)?; // BackColor and BackColor2
+    if version < InnoVersion::new(5, 5, 7) {
+        reader.skip(4)?; // WizardImageBackColor
+    }
+    if version < InnoVersion::new(5, 0, 4) {
+        reader.skip(4)?; // WizardSmallImageBackColor
+    }
+    if version >= InnoVersion::new(6, 0, 0) {
+        reader.skip(1 + 4 + 4)?; // WizardStyle, WizardSizePercentX and WizardSizePercentY
+    }
+    if version >= InnoVersion::new(5, 5, 7) {
+        reader.skip(1)?; // WizardImageAlphaFormat
+    }
+    if version >= InnoVersion::new(6, 4, 0) {
+        // Password test, KDF salt, KDF iterations and base nonce
+        reader.skip(4 + 16 + 4 + 24)?;
+    } else {
+        let password_hash = match version {
+            version if version < InnoVersion::new(4, 2, 0) => 4,
+            version if version < InnoVersion::new(5, 3, 9) => 16,
+            _ => 20,
+        };
+        reader.skip(password_hash)?;
+        if version >= InnoVersion::new(4, 2, 2) {
+            reader.skip(8)?; // Password salt
+        }
+    }
+    reader.skip(8)?; // ExtraDiskSpaceRequired
+    reader.skip(4)?; // SlicesPerDisk
+    if version < InnoVersion::new(5, 0, 0) {
+        reader.skip(1)?; // InstallVerbosity
+    }
+    reader.skip(1)?; // UninstallLogMode
+    if version < InnoVersion::new(5, 0, 0) {
+        reader.skip(1)?; // UninstallStyle
+    }
+    reader.skip(1)?; // DirExistsWarning
+    let Some(privileges_required) = PrivilegesRequired::from_byte(reader.u8()?) else {
+        return Ok(None);
+    };
+    let overrides_allowed = if version >= InnoVersion::new(5, 7, 0) {
+        let overrides = reader.u8()?;
+        if overrides > 0b11 {
+            return Ok(None);
+        }
+        overrides != 0
+    } else {
+        false
+    };
+    if version >= InnoVersion::new(4, 0, 10) {
+        reader.skip(2)?; // ShowLanguageDialog and LanguageDetectionMethod
+    }
+    if version >= InnoVersion::new(5, 3, 9) {
+        reader.skip(1)?; // CompressMethod
+    }
+    let (architectures_allowed, architectures_install_in_64_bit_mode) =
+        if version >= InnoVersion::new(5, 1, 0) && version < InnoVersion::new(6, 3, 0) {
+            (reader.u8()?, reader.u8()?)
+        } else {
+            (0, 0)
+        };
+    Ok(Some(Values {
+        privileges_required,
+        overrides_allowed,
+        architectures_allowed,
+        architectures_install_in_64_bit_mode,
+    }))
+}
+
+/// Converts a set of architecture flags into the identifiers used in architecture expressions
+fn architecture_flags(flags: u8) -> String {
+    ARCHITECTURE_FLAGS
+        .iter()
+        .enumerate()
+        .filter(|(bit, name)| !name.is_empty() && flags & (1 << bit) != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inno::header::{architecture_flags, Header, PrivilegesRequired};
+    use crate::inno::version::InnoVersion;
+
+    fn push_string(data: &mut Vec<u8>, value: &str) {
+        let utf_16 = value.encode_utf16().collect::<Vec<_>>();
+        data.extend(u32::try_from(utf_16.len() * 2).unwrap().to_le_bytes());
+        data.extend(utf_16.into_iter().flat_map(u16::to_le_bytes));
+    }
+
+    #[test]
+    fn test_read_6_2_header() {
+        let mut data = Vec::new();
+        for value in [
+            "App",              // AppName
+            "App 1.2.3",        // AppVerName
+            "{{A1B2C3D4-0000}", // AppId
+            "",                 // AppCopyright
+            "Publisher",        // AppPublisher
+            "",                 // AppPublisherURL
+            "",                 // AppSupportPhone
+            "",                 // AppSupportURL
+            "",                 // AppUpdatesURL
+            "1.2.3",            // AppVersion
+            "{autopf}\\App",    // DefaultDirName
+            "",                 // DefaultGroupName
+            "setup",            // BaseFilename
+            "{app}",            // UninstallFilesDir
+            "",                 // UninstallDisplayName
+            "",                 // UninstallDisplayIcon
+            "",                 // AppMutex
+            "",                 // DefaultUserInfoName
+            "",                 // DefaultUserInfoOrg
+            "",                 // DefaultUserInfoSerial
+            "",                 // AppReadmeFile
+            "",                 // AppContact
+            "",                 // AppComments
+            "",                 // AppModifyPath
+            "yes",              // CreateUninstallRegKey
+            "yes",              // Uninstallable
+            "",                 // CloseApplicationsFilter
+            "",                 // SetupMutex
+            "no",               // ChangesEnvironment
+            "no",               // ChangesAssociations
+        ] {
+            push_string(&mut data, value);
+        }
+        // License, info before, info after and compiled code
+        data.extend([0; 4 * 4]);
+        data.extend([0; 4 * 16]); // Entry counts
+        data.extend([0; 20 + 8]); // Versions and colours
+        data.extend([0; 1 + 4 + 4 + 1]); // Wizard style, size and image alpha format
+        data.extend([0; 20 + 8]); // Password hash and salt
+        data.extend([0; 8 + 4 + 1 + 1]); // Disk space, slices, log mode and dir exists warning
+        data.extend([2, 0]); // Admin privileges without overrides
+        data.extend([0; 2 + 1]); // Language dialog, detection and compression
+        data.extend([0b100, 0b100]); // x64 only
+
+        let header = Header::read(&data, InnoVersion::new(6, 2, 0), true).unwrap();
+        assert_eq!("App", header.app_name);
+        assert_eq!("{{A1B2C3D4-0000}", header.app_id);
+        assert_eq!("1.2.3", header.app_version);
+        assert_eq!("{autopf}\\App", header.default_dir_name);
+        assert_eq!(Some(PrivilegesRequired::Admin), header.privileges_required);
+        assert!(!header.privileges_required_overrides_allowed);
+        assert_eq!(Some("x64"), header.architectures_allowed.as_deref());
+    }
+
+    #[test]
+    fn test_architecture_flags() {
+        assert_eq!("x86 or x64", architecture_flags(0b110));
+        assert_eq!("", architecture_flags(0));
+    }
+}
@@ -0,0 +1,397 @@
+mod header;
+mod version;
+
+use crate::inno::header::{Header, PrivilegesRequired};
+use crate::inno::version::InnoVersion;
+use crate::lzma;
+use crate::manifests::installer_manifest::{
+    AppsAndFeaturesEntry, ElevationRequirement, InstallationMetadata, Scope,
+};
+use crate::types::architecture::Architecture;
+use crate::types::product_code::ProductCode;
+use color_eyre::eyre::{bail, eyre, OptionExt, Result};
+use object::pe::RT_RCDATA;
+use object::read::pe::{ImageNtHeaders, PeFile, ResourceDirectoryEntryData};
+use object::{LittleEndian, ReadRef};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// The resource ID of the table that points to the setup data appended to the executable
+const SETUP_LDR_OFFSET_TABLE_ID: u16 = 11111;
+
+/// IDs of the offset table from Inno Setup 5.1.5 onwards
+const SETUP_LDR_OFFSET_TABLE_IDS: [&[u8; 12]; 2] = [
+    b"rDlPtS\xcd\xe6\xd7\x7b\x0b\x2a",
+    b"nS5W7dT\x83\xaa\x1b\x0f\x6a",
+];
+
+const SETUP_DATA_ID_LENGTH: usize = 64;
+
+/// Data is split into chunks that are each preceded by their CRC32
+const CHUNK_SIZE: usize = 4096;
+
+/// The first version that compresses its header with LZMA
+const FIRST_LZMA_VERSION: InnoVersion = InnoVersion::new(4, 1, 6);
+
+const UNINSTALL_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Uninstall";
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
+pub struct Inno {
+    pub version: String,
+    pub app_id: Option<String>,
+    pub app_name: Option<String>,
+    pub app_version: Option<String>,
+    pub app_publisher: Option<String>,
+    pub display_name: Option<String>,
+    pub default_dir_name: Option<String>,
+    pub uninstall_registry_key: Option<String>,
+    pub privileges_required: Option<PrivilegesRequired>,
+    pub privileges_required_overrides_allowed: bool,
+    pub architectures_allowed: Option<String>,
+    pub architectures_install_in_64_bit_mode: Option<String>,
+}
+
+impl Inno {
+    pub fn new<'data, Pe, R>(pe: &PeFile<'data, Pe, R>, data: &[u8]) -> Result<Self>
+    where
+        Pe: ImageNtHeaders,
+        R: ReadRef<'data>,
+    {
+        let header_offset = setup_data_offset(pe)?;
+        let (version, unicode, header) = read_setup_data(data, header_offset)?;
+        let header = Header::read(&header, version, unicode)?;
+
+        let app_id = expand_escaped(&header.app_id).or_else(|| expand_escaped(&header.app_name));
+        let display_name = expand_escaped(&header.uninstall_name)
+            .or_else(|| expand_escaped(&header.app_versioned_name))
+            .or_else(|| {
+                let app_name = expand_escaped(&header.app_name)?;
+                Some(match expand_escaped(&header.app_version) {
+                    Some(app_version) => format!("{app_name} version {app_version}"),
+                    None => app_name,
+                })
+            });
+        Ok(Self {
+            version: version.to_string(),
+            uninstall_registry_key: app_id
+                .as_ref()
+                .filter(|_| {
+                    !header
+                        .create_uninstall_registry_key
+                        .eq_ignore_ascii_case("no")
+                })
+                .map(|app_id| format!(r"{UNINSTALL_KEY}\{app_id}_is1")),
+            app_id,
+            app_name: expand_escaped(&header.app_name),
+            app_version: expand_escaped(&header.app_version),
+            app_publisher: expand_escaped(&header.app_publisher),
+            display_name,
+            default_dir_name: Some(header.default_dir_name).filter(|dir| !dir.is_empty()),
+            privileges_required: header.privileges_required,
+            privileges_required_overrides_allowed: header.privileges_required_overrides_allowed,
+            architectures_allowed: header.architectures_allowed,
+            architectures_install_in_64_bit_mode: header.architectures_install_in_64_bit_mode,
+        })
+    }
+
+    /// Inno Setup writes its uninstall entry to `{AppId}_is1`
+    pub fn product_code(&self) -> Option<ProductCode> {
+        self.uninstall_registry_key
+            .as_ref()
+            .and_then(|_| ProductCode::new(format!("{}_is1", self.app_id.as_deref()?)).ok())
+    }
+
+    /// Inno Setup installers are 32-bit, so they install as x86 unless they use 64-bit mode
+    pub fn architecture(&self) -> Architecture {
+        let allowed = architecture_identifiers(self.architectures_allowed.as_deref());
+        let install_in_64_bit_mode =
+            architecture_identifiers(self.architectures_install_in_64_bit_mode.as_deref());
+        if !allowed.is_empty()
+            && allowed
+                .iter()
+                .all(|architecture| *architecture == Architecture::Arm64)
+        {
+            Architecture::Arm64
+        } else if install_in_64_bit_mode.contains(&Architecture::X64) {
+            Architecture::X64
+        } else if install_in_64_bit_mode.contains(&Architecture::Arm64) {
+            Architecture::Arm64
+        } else {
+            Architecture::X86
+        }
+    }
+
+    /// Only known if the user can't override the privileges that the installer requires
+    pub fn scope(&self) -> Option<Scope> {
+        if self.privileges_required_overrides_allowed {
+            return None;
+        }
+        match self.privileges_required? {
+            PrivilegesRequired::Admin | PrivilegesRequired::PowerUser => Some(Scope::Machine),
+            PrivilegesRequired::Lowest => Some(Scope::User),
+            PrivilegesRequired::None => None,
+        }
+    }
+
+    /// Inno Setup relaunches itself as administrator when it requires administrative privileges
+    pub fn elevation_requirement(&self) -> Option<ElevationRequirement> {
+        matches!(self.scope(), Some(Scope::Machine)).then_some(ElevationRequirement::ElevatesSelf)
+    }
+
+    pub fn installation_metadata(&self) -> Option<InstallationMetadata> {
+        let default_dir_name = self.default_dir_name.as_deref()?;
+        let (constant, rest) = default_dir_name.strip_prefix('{')?.split_once('}')?;
+        let user = self.privileges_required == Some(PrivilegesRequired::Lowest);
+        let program_files = if self.architecture() == Architecture::X86 {
+            "%ProgramFiles(x86)%"
+        } else {
+            "%ProgramFiles%"
+        };
+        let directory = match constant {
+            "autopf" if user => r"%LocalAppData%\Programs",
+            "autopf" | "pf" | "commonpf" => program_files,
+            "pf32" | "commonpf32" => "%ProgramFiles(x86)%",
+            "pf64" | "commonpf64" => "%ProgramFiles%",
+            "userpf" => r"%LocalAppData%\Programs",
+            "localappdata" => "%LocalAppData%",
+            "userappdata" => "%AppData%",
+            "autoappdata" if user => "%AppData%",
+            "autoappdata" | "commonappdata" => "%ProgramData%",
+            "sd" => "%SystemDrive%",
+            "win" => "%WinDir%",
+            _ => return None,
+        };
+        if rest.contains('{') {
+            return None;
+        }
+        Some(InstallationMetadata {
+            default_install_location: Some(format!("{directory}{rest}")),
+            files: None,
+        })
+    }
+
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry {
+            display_name: self.display_name.clone(),
+            publisher: self.app_publisher.clone(),
+            display_version: self.app_version.clone(),
+            ..AppsAndFeaturesEntry::default()
+        }
+    }
+}
+
+/// Finds where the setup data starts from the offset table in the executable's resources
+fn setup_data_offset<'data, Pe, R>(pe: &PeFile<'data, Pe, R>) -> Result<usize>
+where
+    Pe: ImageNtHeaders,
+    R: ReadRef<'data>,
+{
+    let table = setup_ldr_offset_table(pe)?;
+    let id = table
+        .get(..12)
+        .ok_or_eyre("The offset table is truncated")?;
+    if !SETUP_LDR_OFFSET_TABLE_IDS
+        .iter()
+        .any(|known| known.as_slice() == id)
+    {
+        bail!("The Inno Setup offset table has an unknown ID");
+    }
+    let field = |index: usize| -> Result<u32> {
+        let start = 12 + index * 4;
+        let bytes = table
+            .get(start..start + 4)
+            .ok_or_eyre("The offset table is truncated")?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    // Version, TotalSize, OffsetEXE, UncompressedSizeEXE, CRCEXE, Offset0
+    if field(0)? != 1 {
+        bail!("The Inno Setup offset table version is not supported");
+    }
+    Ok(field(5)? as usize)
+}
+
+pub fn setup_ldr_offset_table<'data, Pe, R>(pe: &PeFile<'data, Pe, R>) -> Result<&'data [u8]>
+where
+    Pe: ImageNtHeaders,
+    R: ReadRef<'data>,
+{
+    let resource_directory = pe
+        .data_directories()
+        .resource_directory(pe.data(), &pe.section_table())?
+        .ok_or_eyre("No resource directory was found")?;
+    let entry = resource_directory
+        .root()?
+        .entries
+        .iter()
+        .find(|entry| entry.name_or_id().id() == Some(RT_RCDATA))
+        .ok_or_eyre("No RT_RCDATA was found")?
+        .data(resource_directory)?
+        .table()
+        .and_then(|table| {
+            table
+                .entries
+                .iter()
+                .find(|entry| entry.name_or_id().id() == Some(SETUP_LDR_OFFSET_TABLE_ID))
+        })
+        .and_then(|entry| entry.data(resource_directory).ok())
+        .and_then(ResourceDirectoryEntryData::table)
+        .and_then(|table| table.entries.first())
+        .and_then(|entry| entry.data(resource_directory).ok())
+        .and_then(ResourceDirectoryEntryData::data)
+        .ok_or_eyre("No Inno Setup offset table was found")?;
+    pe.section_table()
+        .pe_data_at(pe.data(), entry.offset_to_data.get(LittleEndian))
+        .and_then(|data| data.get(..entry.size.get(LittleEndian) as usize))
+        .ok_or_eyre("The Inno Setup offset table is outside of the file")
+}
+
+/// Reads the setup data's version and decompresses the setup header that follows it
+fn read_setup_data(data: &[u8], offset: usize) -> Result<(InnoVersion, bool, Vec<u8>)> {
+    let truncated = || eyre!("The Inno Setup data is truncated");
+    let id = data
+        .get(offset..offset + SETUP_DATA_ID_LENGTH)
+        .ok_or_else(truncated)?;
+    let (version, unicode) =
+        InnoVersion::from_setup_data_id(id).ok_or_eyre("The Inno Setup data ID is unknown")?;
+    if version < FIRST_LZMA_VERSION {
+        bail!("Inno Setup {version} installers are not supported");
+    }
+
+    // CRC32 of the next 5 bytes, the stored size and whether the block is compressed
+    let block = data
+        .get(offset + SETUP_DATA_ID_LENGTH..)
+        .ok_or_else(truncated)?;
+    let stored_size = block
+        .get(4..8)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+        .ok_or_else(truncated)?;
+    let compressed = *block.get(8).ok_or_else(truncated)? != 0;
+    let stored = block.get(9..9 + stored_size).ok_or_else(truncated)?;
+
+    let mut joined = Vec::with_capacity(stored_size);
+    let mut chunks = stored;
+    while !chunks.is_empty() {
+        let chunk = chunks.get(4..).ok_or_else(truncated)?;
+        let length = chunk.len().min(CHUNK_SIZE);
+        joined.extend_from_slice(&chunk[..length]);
+        chunks = &chunk[length..];
+    }
+
+    let header = if compressed {
+        lzma::decompress(&joined)?
+    } else {
+        joined
+    };
+    Ok((version, unicode, header))
+}
+
+/// Unescapes `{{` to `{`, returning None if the value is empty or contains a constant that is
+/// only expanded when the installer runs
+fn expand_escaped(value: &str) -> Option<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut characters = value.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character == '{' {
+            characters.next_if_eq(&'{')?;
+        }
+        expanded.push(character);
+    }
+    Some(expanded).filter(|expanded| !expanded.is_empty())
+}
+
+/// Finds the architectures in an expression such as `x64compatible and not arm64`, ignoring those
+/// that are negated
+fn architecture_identifiers(expression: Option<&str>) -> Vec<Architecture> {
+    let mut architectures = Vec::new();
+    let mut negated = false;
+    for identifier in expression
+        .unwrap_or_default()
+        .split(|character: char| character.is_whitespace() || character == '(' || character == ')')
+        .filter(|identifier| !identifier.is_empty())
+    {
+        let identifier = identifier.to_ascii_lowercase();
+        if identifier == "not" {
+            negated = true;
+            continue;
+        }
+        let architecture = match identifier.as_str() {
+            "x64" | "x64os" | "x64compatible" | "win64" => Some(Architecture::X64),
+            "arm64" => Some(Architecture::Arm64),
+            "x86" | "x86os" | "x86compatible" => Some(Architecture::X86),
+            _ => None,
+        };
+        if let Some(architecture) = architecture.filter(|_| !negated) {
+            architectures.push(architecture);
+        }
+        negated = false;
+    }
+    architectures
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inno::header::PrivilegesRequired;
+    use crate::inno::{architecture_identifiers, expand_escaped, Inno};
+    use crate::manifests::installer_manifest::Scope;
+    use crate::types::architecture::Architecture;
+
+    fn inno(architectures_install_in_64_bit_mode: &str) -> Inno {
+        Inno {
+            version: "6.2.0".to_owned(),
+            app_id: Some("{A1B2C3D4-0000}".to_owned()),
+            app_name: Some("App".to_owned()),
+            app_version: Some("1.2.3".to_owned()),
+            app_publisher: Some("Publisher".to_owned()),
+            display_name: Some("App version 1.2.3".to_owned()),
+            default_dir_name: Some(r"{autopf}\App".to_owned()),
+            uninstall_registry_key: Some(String::new()),
+            privileges_required: Some(PrivilegesRequired::Lowest),
+            privileges_required_overrides_allowed: false,
+            architectures_allowed: None,
+            architectures_install_in_64_bit_mode: Some(
+                architectures_install_in_64_bit_mode.to_owned(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_expand_escaped() {
+        assert_eq!(Some("{A1B2}".to_owned()), expand_escaped("{{A1B2}"));
+        assert_eq!(None, expand_escaped("{cm:AppName}"));
+        assert_eq!(None, expand_escaped(""));
+    }
+
+    #[test]
+    fn test_architecture_identifiers() {
+        assert_eq!(
+            vec![Architecture::X64],
+            architecture_identifiers(Some("x64compatible and not arm64"))
+        );
+        assert_eq!(
+            vec![Architecture::X86, Architecture::X64],
+            architecture_identifiers(Some("x86 or x64"))
+        );
+    }
+
+    #[test]
+    fn test_manifest_values() {
+        let installer = inno("x64compatible");
+        assert_eq!(
+            "{A1B2C3D4-0000}_is1",
+            installer.product_code().unwrap().as_str()
+        );
+        assert_eq!(Architecture::X64, installer.architecture());
+        assert_eq!(Some(Scope::User), installer.scope());
+        assert_eq!(None, installer.elevation_requirement());
+        assert_eq!(
+            Some(r"%LocalAppData%\Programs\App"),
+            installer
+                .installation_metadata()
+                .unwrap()
+                .default_install_location
+                .as_deref()
+        );
+        assert_eq!(Architecture::X86, inno("").architecture());
+    }
+}
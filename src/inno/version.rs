@@ -0,0 +1,67 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct InnoVersion {
+    major: u8,
+    minor: u8,
+    patch: u8,
+}
+
+const SETUP_DATA_PREFIX: &str = "Inno Setup Setup Data (";
+
+impl InnoVersion {
+    pub const fn new(major: u8, minor: u8, patch: u8) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses the version and whether the installer is Unicode from the setup data ID, such as
+    /// `Inno Setup Setup Data (5.5.7) (u)`
+    pub fn from_setup_data_id(id: &[u8]) -> Option<(Self, bool)> {
+        let id = std::str::from_utf8(id).ok()?.trim_end_matches('\0');
+        let (version, rest) = id.strip_prefix(SETUP_DATA_PREFIX)?.split_once(')')?;
+        let mut parts = version.split('.').map(str::parse::<u8>);
+        let version = Self::new(
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+            parts.next()?.ok()?,
+        );
+        // Inno Setup 6 dropped support for non-Unicode installers
+        let unicode = version >= Self::new(6, 0, 0) || rest.contains("(u)") || rest.contains("(U)");
+        Some((version, unicode))
+    }
+}
+
+impl Display for InnoVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inno::version::InnoVersion;
+
+    #[test]
+    fn test_setup_data_id() {
+        assert_eq!(
+            Some((InnoVersion::new(5, 5, 7), true)),
+            InnoVersion::from_setup_data_id(b"Inno Setup Setup Data (5.5.7) (u)\0\0\0")
+        );
+        assert_eq!(
+            Some((InnoVersion::new(5, 5, 7), false)),
+            InnoVersion::from_setup_data_id(b"Inno Setup Setup Data (5.5.7)\0")
+        );
+        assert_eq!(
+            Some((InnoVersion::new(6, 2, 0), true)),
+            InnoVersion::from_setup_data_id(b"Inno Setup Setup Data (6.2.0)\0")
+        );
+        assert_eq!(
+            None,
+            InnoVersion::from_setup_data_id(b"Inno Setup Messages (6.2.0)")
+        );
+    }
+}
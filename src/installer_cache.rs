@@ -0,0 +1,312 @@
+use crate::config::Config;
+use crate::file_analyser::FileAnalyser;
+use color_eyre::eyre::Result;
+use reqwest::header::{HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
+
+/// 1 GiB
+const DEFAULT_MAX_SIZE_MIB: u64 = 1024;
+
+const ENTRY_EXTENSION: &str = "json";
+
+/// The format of cached analyses. Bump this whenever `FileAnalyser` or the way installers are
+/// analysed changes so that analyses made by an older format aren't reused.
+const ANALYSIS_VERSION: u32 = 1;
+
+/// Keeps downloaded installers and their analysis so that unchanged installers aren't downloaded
+/// or analysed again. Each URL is stored as an installer file and a JSON entry named after the
+/// SHA-256 of the URL.
+pub struct InstallerCache {
+    directory: PathBuf,
+    max_size: u64,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CacheEntry {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub sha_256: String,
+    pub file_name: String,
+    pub size: u64,
+    last_used: u64,
+    komac_version: String,
+    analysis: Option<serde_json::Value>,
+    #[serde(default)]
+    analysis_version: u32,
+}
+
+impl CacheEntry {
+    pub fn new(
+        url: &str,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        sha_256: String,
+        file_name: String,
+        size: u64,
+    ) -> Self {
+        Self {
+            url: url.to_owned(),
+            etag,
+            last_modified,
+            sha_256,
+            file_name,
+            size,
+            last_used: now(),
+            komac_version: env!("CARGO_PKG_VERSION").to_owned(),
+            analysis: None,
+            analysis_version: 0,
+        }
+    }
+
+    /// Headers that make the server respond with 304 Not Modified if the installer is unchanged
+    pub fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = self
+            .etag
+            .as_deref()
+            .and_then(|etag| HeaderValue::from_str(etag).ok())
+        {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = self
+            .last_modified
+            .as_deref()
+            .and_then(|last_modified| HeaderValue::from_str(last_modified).ok())
+        {
+            headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+        headers
+    }
+
+    /// Whether the server can be asked if the installer has changed
+    pub fn is_revalidatable(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+impl InstallerCache {
+    /// Opens the cache from the config, returning None if it has been disabled with a size of 0
+    pub fn open() -> Option<Self> {
+        let config = &Config::get().cache;
+        let max_size = config.max_size.unwrap_or(DEFAULT_MAX_SIZE_MIB) * 1024 * 1024;
+        if max_size == 0 {
+            return None;
+        }
+        let directory = config
+            .directory
+            .clone()
+            .or_else(|| dirs::cache_dir().map(|directory| directory.join("komac")))?
+            .join("installers");
+        fs::create_dir_all(&directory).ok()?;
+        Some(Self {
+            directory,
+            max_size,
+        })
+    }
+
+    fn key(url: &str) -> String {
+        base16ct::lower::encode_string(&Sha256::digest(url.as_bytes()))
+    }
+
+    fn installer_path(&self, key: &str) -> PathBuf {
+        self.directory.join(key)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.directory.join(key).with_extension(ENTRY_EXTENSION)
+    }
+
+    /// Returns the entry for a URL if both the entry and its installer exist
+    pub fn entry(&self, url: &str) -> Option<CacheEntry> {
+        let key = Self::key(url);
+        if !self.installer_path(&key).is_file() {
+            return None;
+        }
+        let entry =
+            serde_json::from_slice::<CacheEntry>(&fs::read(self.entry_path(&key)).ok()?).ok()?;
+        (entry.url == url).then_some(entry)
+    }
+
+    fn write_entry(&self, entry: &CacheEntry) -> Result<()> {
+        fs::write(
+            self.entry_path(&Self::key(&entry.url)),
+            serde_json::to_vec(entry)?,
+        )?;
+        Ok(())
+    }
+
+    /// A temporary file in the cache directory so that it can be moved into the cache once complete
+    pub fn temp_file(&self) -> Result<NamedTempFile> {
+        Ok(NamedTempFile::new_in(&self.directory)?)
+    }
+
+    /// Moves a completed download into the cache and evicts the least recently used installers
+    /// that no longer fit
+    pub fn insert(&self, temp_file: NamedTempFile, entry: &CacheEntry) -> Result<File> {
+        let key = Self::key(&entry.url);
+        let path = self.installer_path(&key);
+        temp_file.persist(&path)?;
+        self.write_entry(entry)?;
+        self.evict(&key);
+        Ok(File::open(path)?)
+    }
+
+    /// Marks an entry as used and opens its installer
+    pub fn reuse(&self, mut entry: CacheEntry) -> Result<File> {
+        entry.last_used = now();
+        self.write_entry(&entry)?;
+        Ok(File::open(self.installer_path(&Self::key(&entry.url)))?)
+    }
+
+    /// Returns the analysis of an installer if it was made by this version of Komac with the
+    /// current analysis format
+    pub fn analysis(&self, url: &str, sha_256: &str) -> Option<FileAnalyser<'static>> {
+        let entry = self.entry(url)?;
+        if entry.sha_256 != sha_256
+            || entry.analysis_version != ANALYSIS_VERSION
+            || entry.komac_version != env!("CARGO_PKG_VERSION")
+        {
+            return None;
+        }
+        serde_json::from_value(entry.analysis?).ok()
+    }
+
+    pub fn set_analysis(&self, url: &str, analysis: &FileAnalyser) -> Result<()> {
+        if let Some(mut entry) = self.entry(url) {
+            entry.analysis = Some(serde_json::to_value(analysis)?);
+            entry.analysis_version = ANALYSIS_VERSION;
+            self.write_entry(&entry)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the least recently used installers until the cache fits within its maximum size.
+    /// The installer that was just added is never removed.
+    fn evict(&self, keep: &str) {
+        let Ok(read_dir) = fs::read_dir(&self.directory) else {
+            return;
+        };
+        let mut entries = read_dir
+            .filter_map(Result::ok)
+            .map(|dir_entry| dir_entry.path())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == ENTRY_EXTENSION)
+            })
+            .filter_map(|path| {
+                let entry = serde_json::from_slice::<CacheEntry>(&fs::read(&path).ok()?).ok()?;
+                Some((path, entry))
+            })
+            .collect::<Vec<_>>();
+        let mut total_size = entries.iter().map(|(_, entry)| entry.size).sum::<u64>();
+        entries.sort_unstable_by_key(|(_, entry)| entry.last_used);
+        for (path, entry) in entries {
+            if total_size <= self.max_size {
+                break;
+            }
+            let key = Self::key(&entry.url);
+            if key == keep {
+                continue;
+            }
+            // Another download may have evicted the same entry concurrently
+            let _ = fs::remove_file(self.installer_path(&key));
+            let _ = fs::remove_file(path);
+            total_size -= entry.size;
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::installer_cache::{CacheEntry, InstallerCache, ANALYSIS_VERSION};
+    use serde_json::json;
+    use std::io::Write;
+
+    fn insert(cache: &InstallerCache, url: &str, size: usize, last_used: u64) {
+        let mut temp_file = cache.temp_file().unwrap();
+        temp_file.write_all(&vec![0; size]).unwrap();
+        let mut entry = CacheEntry::new(
+            url,
+            Some("\"etag\"".to_owned()),
+            None,
+            String::new(),
+            String::new(),
+            size as u64,
+        );
+        entry.last_used = last_used;
+        cache.insert(temp_file, &entry).unwrap();
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = InstallerCache {
+            directory: directory.path().to_owned(),
+            max_size: 100,
+        };
+        insert(&cache, "https://example.com/first.exe", 50, 1);
+        insert(&cache, "https://example.com/second.exe", 50, 3);
+        insert(&cache, "https://example.com/third.exe", 50, 2);
+
+        assert!(cache.entry("https://example.com/first.exe").is_none());
+        assert!(cache.entry("https://example.com/second.exe").is_some());
+        assert!(cache.entry("https://example.com/third.exe").is_some());
+    }
+
+    #[test]
+    fn test_conditional_headers() {
+        let entry = CacheEntry::new(
+            "https://example.com/installer.exe",
+            Some("\"etag\"".to_owned()),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT".to_owned()),
+            String::new(),
+            String::new(),
+            0,
+        );
+        let headers = entry.conditional_headers();
+        assert_eq!("\"etag\"", headers["If-None-Match"]);
+        assert_eq!(
+            "Wed, 21 Oct 2015 07:28:00 GMT",
+            headers["If-Modified-Since"]
+        );
+    }
+
+    #[test]
+    fn test_analysis_version_is_checked() {
+        const URL: &str = "https://example.com/installer.exe";
+        let directory = tempfile::tempdir().unwrap();
+        let cache = InstallerCache {
+            directory: directory.path().to_owned(),
+            max_size: 100,
+        };
+        insert(&cache, URL, 0, 0);
+        let mut entry = cache.entry(URL).unwrap();
+        entry.analysis = Some(json!({
+            "architecture": "x64",
+            "installer_type": "exe",
+            "installer_sha_256": "",
+            "file_name": "installer.exe",
+        }));
+
+        entry.analysis_version = ANALYSIS_VERSION;
+        cache.write_entry(&entry).unwrap();
+        assert!(cache.analysis(URL, "").is_some());
+
+        entry.analysis_version = ANALYSIS_VERSION - 1;
+        cache.write_entry(&entry).unwrap();
+        assert!(cache.analysis(URL, "").is_none());
+    }
+}
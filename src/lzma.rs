@@ -0,0 +1,84 @@
+//! Decompression of the LZMA1 streams in the compressed headers of Inno Setup and Nullsoft
+//! installers, which start with the 5 byte properties header but not the uncompressed size
+
+use color_eyre::eyre::{Result, WrapErr};
+use lzma_rs::decompress::{Options, Stream, UnpackedSize};
+use std::io::Write;
+
+/// Decompresses a stream that starts with the 5 byte LZMA properties header. Decoding stops at the
+/// end marker or, as Inno Setup doesn't store the uncompressed size, at the end of the input.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut stream = Stream::new_with_options(
+        &Options {
+            unpacked_size: UnpackedSize::UseProvided(None),
+            allow_incomplete: true,
+            ..Options::default()
+        },
+        Vec::new(),
+    );
+    stream
+        .write_all(data)
+        .wrap_err("Failed to decompress the LZMA stream")?;
+    stream
+        .finish()
+        .wrap_err("Failed to decompress the LZMA stream")
+}
+
+/// Decompresses at least `limit` bytes of a stream when it has them, which avoids decoding all of
+/// a solid archive just to read what is at its start. As LZMA barely expands data that doesn't
+/// compress, the start of the input is decoded first and more is only added if that wasn't enough.
+pub fn decompress_with_limit(data: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut input_length = limit.saturating_mul(2).max(1 << 16);
+    loop {
+        let output = decompress(&data[..input_length.min(data.len())])?;
+        if output.len() >= limit || input_length >= data.len() {
+            return Ok(output);
+        }
+        input_length = input_length.saturating_mul(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lzma::{decompress, decompress_with_limit};
+
+    // The streams were compressed by liblzma (`xz --format=lzma`) with the 8 byte uncompressed size
+    // removed from their header, as Inno Setup and NSIS store them
+
+    #[test]
+    fn test_decompress_literals_and_matches() {
+        const COMPRESSED: &[u8] = b"\x5d\x00\x00\x00\x04\x00\x24\x9b\xac\xde\x20\x29\x98\xdb\xb7\x20\xdc\x86\x3c\xe3\xdd\x49\xe9\x9c\x65\x01\x66\x41\x69\x7b\xa9\x1b\xde\x85\x39\xdd\xdf\xf3\x94\x67\x64\x7e\xf2\x34\x32\x38\x49\x5a\xef\xd7\x36\x03\xdc\x6f\x47\x84\x5e\xec\x2c\xcf\xec\xd9\x49\xc9\x50\x8d\x01\x20\xe4\xce\x5b\x39\xb5\x79\x73\xaa\xbf\x75\x8d\xeb\x34\x76\x75\x76\x94\x31\x33\xad\xe1\x29\x2f\x7e\xd5\x8e\x5d\xd6\xe2\x53\xdf\xff\xfb\x10\x8c\x00";
+        let mut expected = b"Inno Setup Setup Data ".repeat(3);
+        expected.extend(0..40);
+        expected.extend(b"AppName AppVersion AppPublisher AppName AppVersion");
+        assert_eq!(expected, decompress(COMPRESSED).unwrap());
+    }
+
+    #[test]
+    fn test_decompress_long_distances() {
+        const COMPRESSED: &[u8] = b"\x5d\x00\x00\x80\x00\x00\x00\x00\x52\xb8\x0f\xc2\x5b\x88\xf0\x1c\x5e\x85\x42\x23\xc8\x6e\xf9\x6a\x7e\x8e\xa7\x3d\xe2\xbe\xae\x83\x1d\x05\x69\x01\xce\x65\x05\xb3\x90\x8b\x57\xbd\x59\x44\xe5\x33\x0c\xf9\x89\x66\x5e\x21\x0c\x6e\xe8\xf2\xa6\x7d\x6a\x04\x30\xa4\x0d\xba\x59\x93\x11\x99\x2d\x36\xf5\x0c\x9f\xf5\x6a\xb5\x77\x0c\xfc\x0a\xcf\x60\x03\xc9\x2f\x22\x47\x4c\x38\x7b\xb8\x00\x94\xb3\x50\x2a\xc5\x91\x56\x61\x0c\x01\x0c\xab\x29\x35\xfd\x59\xc7\x82\x26\x63\xb3\xa3\xb1\xf7\x78\xa1\xc1\x7e\x42\x4f\x38\xce\x18\xed\x23\xc3\x5e\xad\x8f\x22\xb8\xb0\x2a\x0b\x89\x63\x3a\x46\x25\x92\x31\xbd\x6e\x13\xb5\xf8\x27\x54\x28\xae\xbf\x26\x94\x54\x68\xbc\x70\x6a\x55\xd0\xc2\x71\xd9\x87\x95\x86\x75\x0b\x5e\xe1\x05\x31\xb9\x50\x55\x73\x35\xca\xef\xa2\xbf\xdb\xd4\x46\x23\xdb\xdf\xd4\x76\xb5\x2c\x72\xcf\xc9\xe3\x30\x34\xee\xf1\x8e\xa2\xce\x3b\x69\x1a\xa8\x20\xe5\x95\x98\xe1\xc8\xc5\xd6\xa4\x1b\x65\x51\xb2\x8a\x3a\x25\xe7\xda\x4b\x7f\x49\xb1\x7a\xe2\xf9\x2d\xcc\xd1\x2f\xf3\xed\xe2\x2d\x8e\xef\xc7\xe7\x5b\x7b\xc9\xc4\x41\xa4\xd0\xcc\x2b\x87\x5f\xbd\x96\x1d\x02\xc2\x51\x56\x98\x4c\x99\xe4\xb7\xcc\x13\x8f\xa8\xfd\x54\x8e\xf7\x60\x1f\x42\x26\x0c\x8a\x0b\x45\xe9\xa7\x07\x75\x2e\x34\xf7\xc1\x60\x4d\xdf\xfe\xa3\x4e\x2b";
+        let mut expected = (0..600_u32)
+            .map(|index| (index * index % 251) as u8)
+            .collect::<Vec<_>>();
+        expected.extend_from_within(50..350);
+        expected.extend(b"Inno Setup".repeat(20));
+        assert_eq!(expected, decompress(COMPRESSED).unwrap());
+
+        let limited = decompress_with_limit(COMPRESSED, 700).unwrap();
+        assert!(limited.len() >= 700);
+        assert!(expected.starts_with(&limited));
+    }
+
+    #[test]
+    fn test_decompress_without_end_marker() {
+        const COMPRESSED: &[u8] = b"\x5d\x00\x00\x01\x00\x00\x24\x9b\xac\xde\x20\x29\x98\xdb\xb7\x20\xdc\x86\x3c\xe3\xdd\x49\xe9\x9c\x65\x01\x66\x41\x69\x7b\xa9\x1b\xde\x85\x39\xdd\xdf\xf3\x94\x67\x64\x7e\xf2\x34\x32\x38\x49\x5a\xef\xd7\x36\x03\xdc\x6f\x47\x84\x5e\xec\x2c\xcf\xec\xd9\x49\xc9\x50\x8d\x01\x20\xe4\xce\x5b\x39\xb5\x79\x73\xaa\xbf\x75\x8d\xeb\x34\x76\x75\x76\x94\x31\x33\xad\xe1\x29\x2f\x7e\xd5\x8e\x5d\xd6\xe2\x53\xdf\xff\xfb\x10\x8c\x00";
+        let mut expected = b"Inno Setup Setup Data ".repeat(3);
+        expected.extend(0..40);
+        expected.extend(b"AppName AppVersion AppPublisher AppName AppVersion");
+
+        // A stream that ends before its end marker is decoded as far as it goes
+        let partial = decompress(&COMPRESSED[..COMPRESSED.len() / 2]).unwrap();
+        assert!(!partial.is_empty());
+        assert!(expected.starts_with(&partial));
+    }
+}
@@ -1,28 +1,48 @@
 extern crate core;
 
+mod authenticode;
+mod burn;
 mod commands;
+mod config;
 mod credential;
 mod download_file;
 mod exe;
 mod file_analyser;
 mod github;
+mod inno;
+mod installer_cache;
+mod lzma;
 mod manifest;
+mod manifest_diff;
 mod manifests;
 mod match_installers;
 mod msi;
 mod msix_family;
+mod nsis;
 mod prompts;
+mod release_notes;
+mod storage;
+#[cfg(test)]
+mod test_utils;
 mod types;
 mod update_state;
 mod url_utils;
+mod validation;
 mod zip;
 
+use crate::commands::analyse::Analyse;
 use crate::commands::cleanup::Cleanup;
+use crate::commands::config::{ConfigArgs, ConfigCommands};
 use crate::commands::list_versions::ListVersions;
+use crate::commands::locale::{LocaleArgs, LocaleCommands};
 use crate::commands::new_version::NewVersion;
 use crate::commands::remove_version::RemoveVersion;
+use crate::commands::submit::Submit;
 use crate::commands::token::token::{TokenArgs, TokenCommands};
 use crate::commands::update_version::UpdateVersion;
+use crate::commands::validate::Validate;
+use crate::config::Config;
+use crate::github::repository::GitHubRepository;
 use clap::{Parser, Subcommand};
 use color_eyre::eyre::Result;
 use mimalloc::MiMalloc;
@@ -35,22 +55,45 @@ async fn main() -> Result<()> {
     color_eyre::config::HookBuilder::default()
         .display_env_section(false)
         .install()?;
-    match Cli::parse().command {
-        Commands::New(new_version) => new_version.run().await,
-        Commands::Update(update_version) => update_version.run().await,
-        Commands::Cleanup(cleanup) => cleanup.run().await,
-        Commands::Remove(remove_version) => remove_version.run().await,
+    let cli = Cli::parse();
+    if let Commands::Config(config_args) = cli.command {
+        return match config_args.command {
+            ConfigCommands::Get(get_config) => get_config.run(),
+            ConfigCommands::Set(set_config) => set_config.run(),
+            ConfigCommands::Unset(unset_config) => unset_config.run(),
+            ConfigCommands::List(list_config) => list_config.run(),
+        };
+    }
+    let config = Config::load()?;
+    let repository = cli.repo.or_else(|| config.repo.clone()).unwrap_or_default();
+    match cli.command {
+        Commands::New(new_version) => new_version.run(repository).await,
+        Commands::Update(update_version) => update_version.run(repository).await,
+        Commands::Cleanup(cleanup) => cleanup.run(repository).await,
+        Commands::Remove(remove_version) => remove_version.run(repository).await,
+        Commands::Locale(locale_args) => match locale_args.command {
+            LocaleCommands::Add(add_locale) => add_locale.run(repository).await,
+            LocaleCommands::Remove(remove_locale) => remove_locale.run(repository).await,
+        },
         Commands::Token(token_args) => match token_args.command {
             TokenCommands::Remove(remove_token) => remove_token.run(),
             TokenCommands::Update(update_token) => update_token.run(),
         },
-        Commands::ListVersions(list_versions) => list_versions.run().await,
+        Commands::ListVersions(list_versions) => list_versions.run(repository).await,
+        Commands::Validate(validate) => validate.run(),
+        Commands::Analyse(analyse) => analyse.run(),
+        Commands::Submit(submit) => submit.run(repository).await,
+        Commands::Config(_) => unreachable!("Config commands are run before the config is loaded"),
     }
 }
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// GitHub repository to read manifests from and create pull requests to, as owner/name
+    #[arg(long, global = true, env = "KOMAC_REPO")]
+    repo: Option<GitHubRepository>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -60,7 +103,13 @@ enum Commands {
     New(Box<NewVersion>), // Boxed to store on the heap instead as New is a large struct
     Update(UpdateVersion),
     Remove(RemoveVersion),
+    Locale(LocaleArgs),
     Cleanup(Cleanup),
     Token(TokenArgs),
     ListVersions(ListVersions),
+    Validate(Validate),
+    #[command(visible_alias = "analyze")]
+    Analyse(Analyse),
+    Submit(Submit),
+    Config(ConfigArgs),
 }
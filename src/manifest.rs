@@ -1,14 +1,17 @@
+use crate::config::Config;
 use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
 use crate::manifests::installer_manifest::InstallerManifest;
 use crate::manifests::locale_manifest::LocaleManifest;
 use crate::manifests::version_manifest::VersionManifest;
-use clap::{crate_name, crate_version};
+use clap::{crate_name, crate_version, ValueEnum};
 use color_eyre::eyre::{Error, Result};
 use const_format::formatcp;
 use crossterm::style::Stylize;
+use serde::{Deserialize, Serialize};
 use std::io::StdoutLock;
 use std::io::Write;
-use std::{env, io};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
 pub const MANIFEST_VERSION: &str = "1.5.0";
 
@@ -64,9 +67,72 @@ fn print_manifest(lock: &mut StdoutLock, manifest: &str) {
     }
 }
 
+/// Reads every YAML file in a directory as pairs of file names and their content, sorted by name
+pub fn read_manifest_files(directory: &Path) -> Result<Vec<(String, String)>> {
+    let mut files = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("yaml"))
+        })
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            fs::read_to_string(&path).map(|content| (name, content))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(files)
+}
+
+/// How manifests are arranged when they're written to an output directory
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputLayout {
+    /// The same directories as winget-pkgs, such as `manifests/p/Publisher/Package/1.0.0`
+    #[default]
+    Tree,
+    /// Every manifest directly in the output directory
+    Flat,
+}
+
+impl OutputLayout {
+    /// Gets where a manifest goes in the output directory from its path in winget-pkgs
+    pub fn path(self, output: &Path, manifest_path: &str) -> PathBuf {
+        match self {
+            Self::Tree => output.join(manifest_path),
+            Self::Flat => output.join(
+                manifest_path
+                    .rsplit_once('/')
+                    .map_or(manifest_path, |(_, file_name)| file_name),
+            ),
+        }
+    }
+}
+
+/// Writes manifests to an output directory, creating any directories that they're in
+pub async fn write_manifests(
+    changes: &[(String, String)],
+    output: &Path,
+    layout: OutputLayout,
+) -> Result<()> {
+    for (path, content) in changes {
+        let path = layout.path(output, path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, content).await?;
+    }
+    Ok(())
+}
+
 pub fn build_manifest_string(manifest: &Manifest) -> Result<String> {
     let mut result = Vec::from("# Created with ");
-    if let Ok(created_with_tool) = env::var("KOMAC_CREATED_WITH") {
+    if let Some(created_with_tool) = Config::get().created_with() {
         write!(result, "{created_with_tool} using ")?;
     }
     writeln!(result, "{} v{}", crate_name!(), crate_version!())?;
@@ -106,8 +172,9 @@ fn convert_to_crlf(buf: &mut Vec<u8>) {
 
 #[cfg(test)]
 mod tests {
-    use crate::manifest::{build_manifest_string, Manifest};
+    use crate::manifest::{build_manifest_string, write_manifests, Manifest, OutputLayout};
     use crate::manifests::installer_manifest::InstallerManifest;
+    use std::fs;
 
     fn contains_newline_not_preceded_by_carriage_return(value: &str) -> bool {
         value
@@ -125,4 +192,49 @@ mod tests {
             &manifest_string
         ));
     }
+
+    fn changes() -> Vec<(String, String)> {
+        ["installer", "locale.en-US", ""]
+            .into_iter()
+            .map(|manifest_type| {
+                let file_name =
+                    format!("Package.Identifier.{manifest_type}.yaml").replace("..", ".");
+                (
+                    format!("manifests/p/Package/Identifier/1.0.0/{file_name}"),
+                    manifest_type.to_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_write_manifests_tree() {
+        let output = tempfile::tempdir().unwrap();
+        write_manifests(&changes(), output.path(), OutputLayout::Tree)
+            .await
+            .unwrap();
+        let directory = output.path().join("manifests/p/Package/Identifier/1.0.0");
+        assert_eq!(
+            "installer",
+            fs::read_to_string(directory.join("Package.Identifier.installer.yaml")).unwrap()
+        );
+        assert_eq!(
+            "",
+            fs::read_to_string(directory.join("Package.Identifier.yaml")).unwrap()
+        );
+        assert_eq!(3, fs::read_dir(directory).unwrap().count());
+    }
+
+    #[tokio::test]
+    async fn test_write_manifests_flat() {
+        let output = tempfile::tempdir().unwrap();
+        write_manifests(&changes(), output.path(), OutputLayout::Flat)
+            .await
+            .unwrap();
+        assert_eq!(
+            "locale.en-US",
+            fs::read_to_string(output.path().join("Package.Identifier.locale.en-US.yaml")).unwrap()
+        );
+        assert_eq!(3, fs::read_dir(output.path()).unwrap().count());
+    }
 }
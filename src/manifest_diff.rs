@@ -0,0 +1,402 @@
+use crate::github::github_client::Manifests;
+use clap::ValueEnum;
+use color_eyre::eyre::Result;
+use crossterm::style::Stylize;
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+use std::fmt::Write as _;
+use std::io;
+use std::io::Write;
+
+/// Keys that change with every version so aren't worth showing
+const IGNORED_KEYS: [&str; 4] = [
+    "PackageIdentifier",
+    "PackageVersion",
+    "ManifestType",
+    "ManifestVersion",
+];
+
+/// Keys that together identify an installer across versions
+const INSTALLER_KEYS: [&str; 5] = [
+    "Architecture",
+    "InstallerType",
+    "NestedInstallerType",
+    "Scope",
+    "InstallerLocale",
+];
+
+/// GitHub rejects pull request bodies over 65536 characters
+const MAX_PULL_REQUEST_DIFF_LENGTH: usize = 60000;
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum DiffFormat {
+    #[default]
+    Unified,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum Change {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+/// Converts previous manifests into values so that they can be compared after they've been moved
+pub fn manifest_values(manifests: &Manifests) -> Result<Vec<Value>> {
+    let mut values = vec![
+        serde_yaml::to_value(&manifests.installer_manifest)?,
+        serde_yaml::to_value(&manifests.default_locale_manifest)?,
+        serde_yaml::to_value(&manifests.version_manifest)?,
+    ];
+    for locale_manifest in &manifests.locale_manifests {
+        values.push(serde_yaml::to_value(locale_manifest)?);
+    }
+    Ok(values)
+}
+
+/// Compares previous manifests field by field against the contents of new manifest files
+pub fn diff_manifests(previous: &[Value], changes: &[(String, String)]) -> Result<Vec<Change>> {
+    let new = changes
+        .iter()
+        .map(|(_, content)| serde_yaml::from_str::<Value>(content))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let previous_sections = sections(previous);
+    let new_sections = sections(&new);
+    let mut diff = Vec::new();
+    for (name, previous_section) in &previous_sections {
+        match new_sections.iter().find(|(new_name, _)| new_name == name) {
+            Some((_, new_section)) => diff_mappings(name, previous_section, new_section, &mut diff),
+            None => diff.push(Change::Removed {
+                path: name.clone(),
+                value: Value::Mapping(previous_section.clone()),
+            }),
+        }
+    }
+    for (name, new_section) in new_sections {
+        if !previous_sections
+            .iter()
+            .any(|(previous_name, _)| *previous_name == name)
+        {
+            diff.push(Change::Added {
+                path: name,
+                value: Value::Mapping(new_section),
+            });
+        }
+    }
+    Ok(diff)
+}
+
+/// Splits manifests into named sections of one installer, one locale or the version manifest.
+/// Values at the root of the installer manifest are copied into each installer so that moving a
+/// value between the root and the installers isn't seen as a change.
+fn sections(manifests: &[Value]) -> Vec<(String, Mapping)> {
+    let mut sections: Vec<(String, Mapping)> = Vec::new();
+    for manifest in manifests {
+        let Some(mapping) = manifest.as_mapping() else {
+            continue;
+        };
+        let mut mapping = mapping.clone();
+        for key in IGNORED_KEYS {
+            mapping.remove(key);
+        }
+        match manifest.get("ManifestType").and_then(Value::as_str) {
+            Some("installer") => {
+                let installers = mapping.remove("Installers");
+                for installer in installers
+                    .as_ref()
+                    .and_then(Value::as_sequence)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_mapping)
+                {
+                    let mut merged = mapping.clone();
+                    merged.extend(installer.clone());
+                    let mut name = format!("Installer[{}]", installer_key(&merged));
+                    let duplicates = sections
+                        .iter()
+                        .filter(|(section_name, _)| {
+                            section_name == &name || section_name.starts_with(&format!("{name} #"))
+                        })
+                        .count();
+                    if duplicates > 0 {
+                        let _ = write!(name, " #{}", duplicates + 1);
+                    }
+                    sections.push((name, merged));
+                }
+            }
+            Some("defaultLocale" | "locale") => {
+                let locale = mapping
+                    .remove("PackageLocale")
+                    .as_ref()
+                    .map(display_value)
+                    .unwrap_or_default();
+                sections.push((format!("Locale[{locale}]"), mapping));
+            }
+            Some("version") => sections.push(("Version".to_owned(), mapping)),
+            _ => {}
+        }
+    }
+    sections
+}
+
+fn installer_key(installer: &Mapping) -> String {
+    INSTALLER_KEYS
+        .iter()
+        .filter_map(|key| installer.get(key).map(display_value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn diff_mappings(path: &str, previous: &Mapping, new: &Mapping, diff: &mut Vec<Change>) {
+    for (key, previous_value) in previous {
+        let key_path = format!("{path}.{}", key.as_str().unwrap_or_default());
+        match (previous_value, new.get(key)) {
+            (Value::Mapping(previous_mapping), Some(Value::Mapping(new_mapping))) => {
+                diff_mappings(&key_path, previous_mapping, new_mapping, diff);
+            }
+            (_, Some(new_value)) if new_value != previous_value => diff.push(Change::Changed {
+                path: key_path,
+                old: previous_value.clone(),
+                new: new_value.clone(),
+            }),
+            (_, Some(_)) => {}
+            (_, None) => diff.push(Change::Removed {
+                path: key_path,
+                value: previous_value.clone(),
+            }),
+        }
+    }
+    for (key, new_value) in new {
+        if !previous.contains_key(key) {
+            diff.push(Change::Added {
+                path: format!("{path}.{}", key.as_str().unwrap_or_default()),
+                value: new_value.clone(),
+            });
+        }
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(bool) => bool.to_string(),
+        Value::Null => String::from("null"),
+        _ => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+fn write_lines(diff: &mut String, sign: char, path: &str, value: &Value) {
+    let value = display_value(value);
+    let mut lines = value.lines();
+    let _ = writeln!(diff, "{sign} {path}: {}", lines.next().unwrap_or_default());
+    for line in lines {
+        let _ = writeln!(diff, "{sign}   {line}");
+    }
+}
+
+/// Renders the changes as lines starting with `-` for previous values and `+` for new values
+pub fn unified(changes: &[Change]) -> String {
+    let mut diff = String::new();
+    for change in changes {
+        match change {
+            Change::Added { path, value } => write_lines(&mut diff, '+', path, value),
+            Change::Removed { path, value } => write_lines(&mut diff, '-', path, value),
+            Change::Changed { path, old, new } => {
+                write_lines(&mut diff, '-', path, old);
+                write_lines(&mut diff, '+', path, new);
+            }
+        }
+    }
+    diff
+}
+
+pub fn print_diff(changes: &[Change], format: DiffFormat) -> Result<()> {
+    let mut lock = io::stdout().lock();
+    match format {
+        DiffFormat::Unified => {
+            if changes.is_empty() {
+                writeln!(lock, "No fields have changed from the previous version")?;
+            }
+            for line in unified(changes).lines() {
+                if line.starts_with('-') {
+                    writeln!(lock, "{}", line.red())?;
+                } else {
+                    writeln!(lock, "{}", line.green())?;
+                }
+            }
+        }
+        DiffFormat::Json => {
+            serde_json::to_writer_pretty(&mut lock, changes)?;
+            writeln!(lock)?;
+        }
+    }
+    Ok(())
+}
+
+/// A collapsible section for a pull request body showing the changes as a diff
+pub fn pull_request_section(changes: &[Change]) -> Option<String> {
+    if changes.is_empty() {
+        return None;
+    }
+    let mut diff = unified(changes);
+    if diff.len() > MAX_PULL_REQUEST_DIFF_LENGTH {
+        let end = diff[..diff.floor_char_boundary(MAX_PULL_REQUEST_DIFF_LENGTH)]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        diff.truncate(end);
+        diff.push_str("...\n");
+    }
+    Some(format!(
+        "\n\n<details>\n<summary>Changes from the previous version</summary>\n\n```diff\n{diff}```\n\n</details>"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::manifest_diff::{
+        diff_manifests, pull_request_section, unified, Change, MAX_PULL_REQUEST_DIFF_LENGTH,
+    };
+    use serde_yaml::Value;
+
+    const PREVIOUS_INSTALLER: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.0.0
+InstallerType: exe
+InstallerSwitches:
+  Silent: /S
+Installers:
+- Architecture: x64
+  InstallerUrl: https://example.com/1.0.0/x64.exe
+  InstallerSha256: AAAA
+- Architecture: x86
+  InstallerUrl: https://example.com/1.0.0/x86.exe
+  InstallerSha256: BBBB
+ManifestType: installer
+ManifestVersion: 1.5.0
+";
+
+    const NEW_INSTALLER: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.1.0
+Installers:
+- Architecture: x64
+  InstallerType: exe
+  InstallerSwitches:
+    Silent: /S
+    SilentWithProgress: /S
+  InstallerUrl: https://example.com/1.1.0/x64.exe
+  InstallerSha256: CCCC
+- Architecture: arm64
+  InstallerType: exe
+  InstallerUrl: https://example.com/1.1.0/arm64.exe
+  InstallerSha256: DDDD
+ManifestType: installer
+ManifestVersion: 1.5.0
+";
+
+    const PREVIOUS_LOCALE: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.0.0
+PackageLocale: en-US
+Publisher: Publisher
+ShortDescription: Old description
+ManifestType: defaultLocale
+ManifestVersion: 1.5.0
+";
+
+    const NEW_LOCALE: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.1.0
+PackageLocale: en-US
+Publisher: Publisher
+ShortDescription: New description
+ManifestType: defaultLocale
+ManifestVersion: 1.5.0
+";
+
+    fn diff() -> Vec<Change> {
+        let previous = [PREVIOUS_INSTALLER, PREVIOUS_LOCALE]
+            .iter()
+            .map(|manifest| serde_yaml::from_str::<Value>(manifest).unwrap())
+            .collect::<Vec<_>>();
+        let changes = [NEW_INSTALLER, NEW_LOCALE]
+            .iter()
+            .map(|manifest| (String::new(), (*manifest).to_owned()))
+            .collect::<Vec<_>>();
+        diff_manifests(&previous, &changes).unwrap()
+    }
+
+    #[test]
+    fn test_field_changes() {
+        let paths = diff()
+            .into_iter()
+            .map(|change| match change {
+                Change::Added { path, .. } => format!("+{path}"),
+                Change::Removed { path, .. } => format!("-{path}"),
+                Change::Changed { path, .. } => format!("~{path}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "+Installer[x64, exe].InstallerSwitches.SilentWithProgress",
+                "~Installer[x64, exe].InstallerUrl",
+                "~Installer[x64, exe].InstallerSha256",
+                "-Installer[x86, exe]",
+                "~Locale[en-US].ShortDescription",
+                "+Installer[arm64, exe]",
+            ],
+            paths
+        );
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let unified = unified(&diff());
+        assert!(unified.contains(
+            "- Installer[x64, exe].InstallerUrl: https://example.com/1.0.0/x64.exe\n\
+             + Installer[x64, exe].InstallerUrl: https://example.com/1.1.0/x64.exe\n"
+        ));
+        assert!(unified.contains(
+            "- Locale[en-US].ShortDescription: Old description\n\
+             + Locale[en-US].ShortDescription: New description\n"
+        ));
+    }
+
+    #[test]
+    fn test_pull_request_section_multibyte_limit() {
+        let prefix = "+ ShortDescription: Short\n+ Description: ".len();
+        let description = format!(
+            "{}é and more",
+            "a".repeat(MAX_PULL_REQUEST_DIFF_LENGTH - prefix - 1)
+        );
+        let changes = [
+            Change::Added {
+                path: "ShortDescription".to_owned(),
+                value: Value::String("Short".to_owned()),
+            },
+            Change::Added {
+                path: "Description".to_owned(),
+                value: Value::String(description),
+            },
+        ];
+        assert!(!unified(&changes).is_char_boundary(MAX_PULL_REQUEST_DIFF_LENGTH));
+
+        let section = pull_request_section(&changes).unwrap();
+        assert!(section.contains("+ ShortDescription: Short\n...\n"));
+        assert!(!section.contains("Description: a"));
+    }
+}
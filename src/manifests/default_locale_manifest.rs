@@ -66,7 +66,7 @@ pub struct Agreement {
     pub agreement_url: Option<Url>,
 }
 
-#[derive(Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq)]
 #[serde(rename_all = "PascalCase")]
 pub struct Documentation {
     pub document_label: Option<String>,
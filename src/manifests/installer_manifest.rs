@@ -11,10 +11,12 @@ use crate::types::manifest_version::ManifestVersion;
 use crate::types::minimum_os_version::MinimumOSVersion;
 use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
+use crate::types::product_code::ProductCode;
 use crate::types::protocol::Protocol;
 use crate::types::silent_switch::SilentSwitch;
 use crate::types::silent_with_progress_switch::SilentWithProgressSwitch;
 use crate::types::urls::url::Url;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use std::collections::BTreeSet;
@@ -48,8 +50,7 @@ pub struct InstallerManifest {
     pub file_extensions: Option<BTreeSet<FileExtension>>,
     pub dependencies: Option<Dependencies>,
     pub package_family_name: Option<String>,
-    #[serde(serialize_with = "upper_uuid_braced")]
-    pub product_code: Option<Uuid>,
+    pub product_code: Option<ProductCode>,
     pub capabilities: Option<BTreeSet<String>>,
     pub restricted_capabilities: Option<BTreeSet<String>>,
     pub markets: Option<Markets>,
@@ -112,7 +113,18 @@ pub enum Scope {
 }
 
 #[derive(
-    Serialize, Deserialize, Clone, Debug, Display, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    Display,
+    EnumIter,
+    Eq,
+    PartialEq,
+    Hash,
+    Ord,
+    PartialOrd,
+    ValueEnum,
 )]
 #[serde(rename_all = "camelCase")]
 pub enum InstallModes {
@@ -197,6 +209,7 @@ pub enum ReturnResponse {
     Hash,
     Ord,
     PartialOrd,
+    ValueEnum,
 )]
 #[serde(rename_all = "camelCase")]
 pub enum UpgradeBehavior {
@@ -262,13 +275,36 @@ pub struct AppsAndFeaturesEntry {
     pub display_name: Option<String>,
     pub publisher: Option<String>,
     pub display_version: Option<String>,
-    #[serde(serialize_with = "upper_uuid_braced")]
-    pub product_code: Option<Uuid>,
+    pub product_code: Option<ProductCode>,
     #[serde(serialize_with = "upper_uuid_braced")]
     pub upgrade_code: Option<Uuid>,
     pub installer_type: Option<InstallerType>,
 }
 
+impl AppsAndFeaturesEntry {
+    /// Removes values that are the same as the package's, returning None if none are left
+    pub fn deduplicate(
+        self,
+        package_name: Option<&str>,
+        publisher: Option<&str>,
+        package_version: &str,
+    ) -> Option<Self> {
+        let entry = Self {
+            display_name: self
+                .display_name
+                .filter(|display_name| Some(display_name.as_str()) != package_name),
+            publisher: self
+                .publisher
+                .filter(|entry_publisher| Some(entry_publisher.as_str()) != publisher),
+            display_version: self
+                .display_version
+                .filter(|display_version| display_version != package_version),
+            ..self
+        };
+        (entry != Self::default()).then_some(entry)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 #[serde(rename_all = "camelCase")]
 pub enum ElevationRequirement {
@@ -330,8 +366,7 @@ pub struct Installer {
     pub file_extensions: Option<BTreeSet<FileExtension>>,
     pub dependencies: Option<Dependencies>,
     pub package_family_name: Option<String>,
-    #[serde(serialize_with = "upper_uuid_braced")]
-    pub product_code: Option<Uuid>,
+    pub product_code: Option<ProductCode>,
     pub capabilities: Option<BTreeSet<String>>,
     pub restricted_capabilities: Option<BTreeSet<String>>,
     pub markets: Option<Markets>,
@@ -5,7 +5,7 @@ use crate::types::package_identifier::PackageIdentifier;
 use crate::types::package_version::PackageVersion;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "PascalCase")]
 pub struct VersionManifest {
     pub package_identifier: PackageIdentifier,
@@ -1,21 +1,49 @@
+use crate::manifests::installer_manifest::{InstallationMetadata, Scope};
 use crate::types::architecture::Architecture;
+use crate::types::file_extension::FileExtension;
 use crate::types::language_tag::LanguageTag;
-use color_eyre::eyre::{bail, Result};
+use crate::types::protocol::Protocol;
+use color_eyre::eyre::Result;
 use msi::{Language, Package, Select};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::{BTreeSet, HashMap};
 use std::io::{Read, Seek};
 use std::str::FromStr;
+use thiserror::Error;
 use uuid::Uuid;
 
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
 pub struct Msi {
-    pub architecture: Architecture,
-    pub product_code: Uuid,
-    pub upgrade_code: Uuid,
-    pub product_name: String,
-    pub product_version: String,
-    pub manufacturer: String,
-    pub product_language: LanguageTag,
+    pub architecture: Option<Architecture>,
+    pub product_code: Option<Uuid>,
+    pub upgrade_code: Option<Uuid>,
+    pub product_name: Option<String>,
+    pub product_version: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product_language: Option<LanguageTag>,
     pub is_wix: bool,
+    pub scope: Option<Scope>,
+    pub arp_system_component: bool,
+    pub file_extensions: Option<BTreeSet<FileExtension>>,
+    pub protocols: Option<BTreeSet<Protocol>>,
+    pub install_location: Option<String>,
+    /// Properties that were missing or couldn't be read, which leave their fields empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<MsiError>,
+}
+
+#[derive(Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
+pub enum MsiError {
+    #[error("The MSI is missing the {0} property")]
+    MissingProperty(String),
+    #[error("The {property} property of the MSI has an invalid value: {value}")]
+    InvalidProperty { property: String, value: String },
+    #[error("The MSI does not specify an architecture in its template")]
+    MissingArchitecture,
+    #[error("The MSI has an unsupported architecture: {0}")]
+    UnsupportedArchitecture(String),
 }
 
 const PROPERTY: &str = "Property";
@@ -26,18 +54,51 @@ const PRODUCT_VERSION: &str = "ProductVersion";
 const MANUFACTURER: &str = "Manufacturer";
 const UPGRADE_CODE: &str = "UpgradeCode";
 const WIX: &str = "wix";
+const ALL_USERS: &str = "ALLUSERS";
+const MSI_INSTALL_PER_USER: &str = "MSIINSTALLPERUSER";
+const ARP_SYSTEM_COMPONENT: &str = "ARPSYSTEMCOMPONENT";
+const WIXUI_INSTALLDIR: &str = "WIXUI_INSTALLDIR";
+
+const EXTENSION: &str = "Extension";
+const REGISTRY: &str = "Registry";
+const DIRECTORY: &str = "Directory";
+const CUSTOM_ACTION: &str = "CustomAction";
+
+/// The language ID of a package that is language-neutral
+const LANGUAGE_NEUTRAL: u16 = 0;
+
+/// Directory IDs that are commonly used for the installation directory
+const INSTALL_DIRECTORIES: [&str; 5] = [
+    "INSTALLDIR",
+    "INSTALLFOLDER",
+    "INSTALLLOCATION",
+    "APPLICATIONFOLDER",
+    "APPDIR",
+];
+
+/// The registry value that marks a key under `Software\Classes` as a URL protocol handler
+const URL_PROTOCOL: &str = "URL Protocol";
+const CLASSES_KEY: &str = r"Software\Classes\";
+
+/// The `Root` column of the `Registry` table
+const HKEY_CLASSES_ROOT: i32 = 0;
+
+/// Custom action types that set a directory or property to formatted text, ignoring their
+/// execution options
+const CUSTOM_ACTION_TYPE_MASK: i32 = 0x3F;
+const CUSTOM_ACTION_SET_DIRECTORY: i32 = 35;
+const CUSTOM_ACTION_SET_PROPERTY: i32 = 51;
 
 impl Msi {
     pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
         let mut msi = Package::open(reader)?;
 
-        let architecture = match msi.summary_info().arch() {
-            Some("x64" | "Intel64" | "AMD64") => Architecture::X64,
-            Some("Intel") => Architecture::X86,
-            _ => bail!("No architecture was found in the MSI"),
-        };
+        let mut errors = Vec::new();
+        let architecture = Self::architecture(msi.summary_info().arch())
+            .map_err(|error| errors.push(error))
+            .ok();
 
-        let mut property_map = msi
+        let property_map = msi
             .select_rows(Select::table(PROPERTY))?
             .filter_map(|row| {
                 if row.len() == 2 {
@@ -50,21 +111,364 @@ impl Msi {
             })
             .collect::<HashMap<_, _>>();
 
+        let property = |name: &str| property_map.get(name).map(String::as_str);
+        let scope = match (property(ALL_USERS), property(MSI_INSTALL_PER_USER)) {
+            (Some("1"), _) => Some(Scope::Machine),
+            // A dual-purpose package that installs per-user unless told otherwise
+            (Some("2"), Some("1")) => Some(Scope::User),
+            _ => None,
+        };
+        let arp_system_component = property(ARP_SYSTEM_COMPONENT) == Some("1");
+        let file_extensions = Self::file_extensions(&mut msi);
+        let protocols = Self::protocols(&mut msi);
+        let install_location =
+            Self::install_location(&mut msi, &property_map, architecture.unwrap_or_default());
+
+        let mut property = |name: &str| {
+            parse_property(&property_map, name, |value| Some(value.to_owned()))
+                .map_err(|error| errors.push(error))
+                .ok()
+        };
+        let product_name = property(PRODUCT_NAME);
+        let product_version = property(PRODUCT_VERSION);
+        let manufacturer = property(MANUFACTURER);
+        let mut property = |name: &str| {
+            parse_property(&property_map, name, |value| Uuid::parse_str(value).ok())
+                .map_err(|error| errors.push(error))
+                .ok()
+        };
+        let product_code = property(PRODUCT_CODE);
+        let upgrade_code = property(UPGRADE_CODE);
+        let product_language =
+            parse_property(
+                &property_map,
+                PRODUCT_LANGUAGE,
+                |value| match u16::from_str(value).ok()? {
+                    LANGUAGE_NEUTRAL => Some(None),
+                    code => LanguageTag::from_str(Language::from_code(code).tag())
+                        .ok()
+                        .map(Some),
+                },
+            )
+            .map_err(|error| errors.push(error))
+            .ok()
+            .flatten();
+
         Ok(Self {
             architecture,
-            product_code: Uuid::parse_str(property_map.get(PRODUCT_CODE).unwrap())?,
-            upgrade_code: Uuid::parse_str(property_map.get(UPGRADE_CODE).unwrap())?,
-            product_name: property_map.remove(PRODUCT_NAME).unwrap(),
-            product_version: property_map.remove(PRODUCT_VERSION).unwrap(),
-            manufacturer: property_map.remove(MANUFACTURER).unwrap(),
-            product_language: LanguageTag::from_str(
-                Language::from_code(u16::from_str(property_map.get(PRODUCT_LANGUAGE).unwrap())?)
-                    .tag(),
-            )?,
+            product_code,
+            upgrade_code,
+            product_name,
+            product_version,
+            manufacturer,
+            product_language,
             is_wix: property_map.into_keys().any(|mut property| {
                 property.make_ascii_lowercase();
                 property.contains(WIX)
             }),
+            scope,
+            arp_system_component,
+            file_extensions,
+            protocols,
+            install_location,
+            errors,
+        })
+    }
+
+    /// Gets the architecture from the platform in the summary information's template, such as
+    /// `x64;1033`
+    fn architecture(arch: Option<&str>) -> Result<Architecture, MsiError> {
+        match arch {
+            Some("x64" | "Intel64" | "AMD64") => Ok(Architecture::X64),
+            Some("Intel") => Ok(Architecture::X86),
+            Some("Arm64") => Ok(Architecture::Arm64),
+            Some("Arm") => Ok(Architecture::Arm),
+            Some(arch) => Err(MsiError::UnsupportedArchitecture(arch.to_owned())),
+            None => Err(MsiError::MissingArchitecture),
+        }
+    }
+
+    /// Reads the selected columns of a table as strings, returning nothing if the table doesn't
+    /// exist
+    fn select_strings<R: Read + Seek>(
+        msi: &mut Package<R>,
+        table: &str,
+        columns: &[&str],
+    ) -> Vec<Vec<Option<String>>> {
+        if !msi.has_table(table) {
+            return Vec::new();
+        }
+        msi.select_rows(Select::table(table).columns(columns))
+            .map(|rows| {
+                rows.map(|row| {
+                    (0..row.len())
+                        .map(|index| row[index].as_str().map(str::to_owned))
+                        .collect()
+                })
+                .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Gets the extensions of the `Extension` table, which registers them along with their
+    /// `ProgId`
+    fn file_extensions<R: Read + Seek>(msi: &mut Package<R>) -> Option<BTreeSet<FileExtension>> {
+        let extensions = Self::select_strings(msi, EXTENSION, &[EXTENSION])
+            .into_iter()
+            .filter_map(|mut row| row.swap_remove(0))
+            .filter_map(|extension| FileExtension::new(extension.to_ascii_lowercase()).ok())
+            .collect::<BTreeSet<_>>();
+        (!extensions.is_empty()).then_some(extensions)
+    }
+
+    /// Finds keys under `Software\Classes` that have a `URL Protocol` value
+    fn protocols<R: Read + Seek>(msi: &mut Package<R>) -> Option<BTreeSet<Protocol>> {
+        if !msi.has_table(REGISTRY) {
+            return None;
+        }
+        let protocols = msi
+            .select_rows(Select::table(REGISTRY).columns(&["Root", "Key", "Name"]))
+            .ok()?
+            .filter(|row| row[2].as_str() == Some(URL_PROTOCOL))
+            .filter_map(|row| {
+                let key = row[1].as_str()?;
+                let scheme = if row[0].as_int() == Some(HKEY_CLASSES_ROOT) {
+                    key
+                } else {
+                    key.get(..CLASSES_KEY.len())
+                        .filter(|prefix| prefix.eq_ignore_ascii_case(CLASSES_KEY))
+                        .and_then(|_| key.get(CLASSES_KEY.len()..))?
+                };
+                (!scheme.contains(['\\', '['])).then(|| scheme.to_ascii_lowercase())
+            })
+            .filter_map(|scheme| Protocol::new(scheme).ok())
+            .collect::<BTreeSet<_>>();
+        (!protocols.is_empty()).then_some(protocols)
+    }
+
+    /// Resolves the installation directory from a custom action that sets it or otherwise from
+    /// its parents in the `Directory` table
+    fn install_location<R: Read + Seek>(
+        msi: &mut Package<R>,
+        property_map: &HashMap<String, String>,
+        architecture: Architecture,
+    ) -> Option<String> {
+        let directories = Self::select_strings(
+            msi,
+            DIRECTORY,
+            &[DIRECTORY, "Directory_Parent", "DefaultDir"],
+        )
+        .into_iter()
+        .filter_map(|mut row| {
+            let default_dir = row.pop()??;
+            let parent = row.pop()?;
+            Some((row.pop()??, (parent, default_dir)))
         })
+        .collect::<HashMap<_, _>>();
+        let install_directory = property_map
+            .get(WIXUI_INSTALLDIR)
+            .map(String::as_str)
+            .into_iter()
+            .chain(INSTALL_DIRECTORIES)
+            .find(|directory| directories.contains_key(*directory))?;
+        let resolver = DirectoryResolver {
+            directories: &directories,
+            properties: property_map,
+            architecture,
+        };
+
+        let custom_action = msi
+            .has_table(CUSTOM_ACTION)
+            .then(|| {
+                msi.select_rows(Select::table(CUSTOM_ACTION).columns(&["Type", "Source", "Target"]))
+                    .ok()
+            })
+            .flatten()
+            .and_then(|mut rows| {
+                rows.find(|row| {
+                    matches!(
+                        row[0].as_int().unwrap_or_default() & CUSTOM_ACTION_TYPE_MASK,
+                        CUSTOM_ACTION_SET_DIRECTORY | CUSTOM_ACTION_SET_PROPERTY
+                    ) && row[1].as_str() == Some(install_directory)
+                })
+            })
+            .and_then(|row| row[2].as_str().map(str::to_owned));
+        custom_action
+            .and_then(|target| resolver.format(&target))
+            .or_else(|| resolver.resolve(install_directory, 0))
+            .map(|location| location.trim_end_matches('\\').to_owned())
+    }
+
+    pub fn installation_metadata(&self) -> Option<InstallationMetadata> {
+        self.install_location
+            .as_ref()
+            .map(|install_location| InstallationMetadata {
+                default_install_location: Some(install_location.clone()),
+                files: None,
+            })
+    }
+}
+
+fn parse_property<T>(
+    properties: &HashMap<String, String>,
+    name: &str,
+    parse: impl FnOnce(&str) -> Option<T>,
+) -> Result<T, MsiError> {
+    let value = properties
+        .get(name)
+        .ok_or_else(|| MsiError::MissingProperty(name.to_owned()))?;
+    parse(value).ok_or_else(|| MsiError::InvalidProperty {
+        property: name.to_owned(),
+        value: value.clone(),
+    })
+}
+
+struct DirectoryResolver<'a> {
+    /// Maps each directory to its parent and its `DefaultDir` value
+    directories: &'a HashMap<String, (Option<String>, String)>,
+    properties: &'a HashMap<String, String>,
+    architecture: Architecture,
+}
+
+impl DirectoryResolver<'_> {
+    /// The directories that Windows Installer sets, which are written as environment variables
+    fn system_folder(&self, directory: &str) -> Option<&'static str> {
+        let is_64_bit = matches!(self.architecture, Architecture::X64 | Architecture::Arm64);
+        Some(match directory {
+            "ProgramFilesFolder" => "%ProgramFiles(x86)%",
+            "ProgramFiles64Folder" => "%ProgramFiles%",
+            "ProgramFiles6432Folder" if is_64_bit => "%ProgramFiles%",
+            "ProgramFiles6432Folder" => "%ProgramFiles(x86)%",
+            "CommonFilesFolder" => "%CommonProgramFiles(x86)%",
+            "CommonFiles64Folder" => "%CommonProgramFiles%",
+            "LocalAppDataFolder" => "%LocalAppData%",
+            "AppDataFolder" => "%AppData%",
+            "CommonAppDataFolder" => "%ProgramData%",
+            "WindowsFolder" => "%WinDir%",
+            "WindowsVolume" => "%SystemDrive%",
+            _ => return None,
+        })
+    }
+
+    /// Builds the path of a directory from its parents, returning None if it isn't under a known
+    /// system folder
+    fn resolve(&self, directory: &str, depth: u8) -> Option<String> {
+        if let Some(folder) = self.system_folder(directory) {
+            return Some(folder.to_owned());
+        }
+        if depth > 32 {
+            return None;
+        }
+        let (parent, default_dir) = self.directories.get(directory)?;
+        let parent = self.resolve(parent.as_deref()?, depth + 1)?;
+        // The target name is before the colon and the long name is after the pipe
+        let target = default_dir.split(':').next().unwrap_or_default();
+        let name = target.rsplit('|').next().unwrap_or_default();
+        Some(if name == "." || name.is_empty() {
+            parent
+        } else {
+            format!(r"{parent}\{name}")
+        })
+    }
+
+    /// Expands the directories and properties in formatted text such as
+    /// `[ProgramFilesFolder][Manufacturer]\[ProductName]`
+    fn format(&self, text: &str) -> Option<String> {
+        let mut formatted = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find('[') {
+            formatted.push_str(&rest[..start]);
+            let end = start + rest[start..].find(']')?;
+            let name = &rest[start + 1..end];
+            if let Some(directory) = self.resolve(name, 0) {
+                formatted.push_str(&directory);
+                formatted.push('\\');
+            } else {
+                formatted.push_str(self.properties.get(name)?);
+            }
+            rest = &rest[end + 1..];
+        }
+        formatted.push_str(rest);
+        Some(formatted.replace(r"\\", r"\"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::msi::{parse_property, DirectoryResolver, Msi, MsiError};
+    use crate::types::architecture::Architecture;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_architecture() {
+        assert_eq!(Ok(Architecture::X64), Msi::architecture(Some("x64")));
+        assert_eq!(Ok(Architecture::X86), Msi::architecture(Some("Intel")));
+        assert_eq!(Ok(Architecture::Arm64), Msi::architecture(Some("Arm64")));
+        assert_eq!(
+            Err(MsiError::UnsupportedArchitecture("Alpha".to_owned())),
+            Msi::architecture(Some("Alpha"))
+        );
+        assert_eq!(Err(MsiError::MissingArchitecture), Msi::architecture(None));
+    }
+
+    #[test]
+    fn test_parse_property() {
+        let properties = HashMap::from([("ProductLanguage".to_owned(), "English".to_owned())]);
+        let parse = |value: &str| value.parse::<u16>().ok();
+        assert_eq!(
+            Err(MsiError::InvalidProperty {
+                property: "ProductLanguage".to_owned(),
+                value: "English".to_owned(),
+            }),
+            parse_property(&properties, "ProductLanguage", parse)
+        );
+        let error = parse_property(&properties, "ProductCode", parse).unwrap_err();
+        assert_eq!(
+            "The MSI is missing the ProductCode property",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_resolve_directories() {
+        let directories = HashMap::from([
+            ("TARGETDIR".to_owned(), (None, "SourceDir".to_owned())),
+            (
+                "ProgramFiles64Folder".to_owned(),
+                (Some("TARGETDIR".to_owned()), ".".to_owned()),
+            ),
+            (
+                "CompanyFolder".to_owned(),
+                (
+                    Some("ProgramFiles64Folder".to_owned()),
+                    "Example".to_owned(),
+                ),
+            ),
+            (
+                "INSTALLFOLDER".to_owned(),
+                (
+                    Some("CompanyFolder".to_owned()),
+                    "APP~1|My App:App".to_owned(),
+                ),
+            ),
+        ]);
+        let properties = HashMap::from([("ProductName".to_owned(), "My App".to_owned())]);
+        let resolver = DirectoryResolver {
+            directories: &directories,
+            properties: &properties,
+            architecture: Architecture::X64,
+        };
+        assert_eq!(
+            Some(r"%ProgramFiles%\Example\My App"),
+            resolver.resolve("INSTALLFOLDER", 0).as_deref()
+        );
+        assert_eq!(None, resolver.resolve("TARGETDIR", 0));
+        assert_eq!(
+            Some(r"%LocalAppData%\Programs\My App"),
+            resolver
+                .format(r"[LocalAppDataFolder]Programs\[ProductName]")
+                .as_deref()
+        );
+        assert_eq!(None, resolver.format("[UnknownProperty]"));
     }
 }
@@ -1,29 +1,46 @@
 use crate::manifests::installer_manifest::Platform;
 use crate::msix_family::utils::{hash_signature, read_manifest};
 use crate::types::architecture::Architecture;
+use crate::types::command::Command;
+use crate::types::file_extension::FileExtension;
 use crate::types::minimum_os_version::MinimumOSVersion;
+use crate::types::protocol::Protocol;
 use color_eyre::eyre::Result;
 use package_family_name::get_package_family_name;
 use quick_xml::de::from_str;
-use serde::Deserialize;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::collections::BTreeSet;
 use std::io::{Read, Seek};
 use std::str::FromStr;
 use zip::ZipArchive;
 
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
 pub struct Msix {
     pub display_name: String,
     pub publisher_display_name: String,
     pub version: String,
     pub signature_sha_256: String,
     pub package_family_name: String,
-    pub target_device_family: Platform,
-    pub min_version: MinimumOSVersion,
+    pub target_device_families: Vec<TargetDeviceFamily>,
     pub processor_architecture: Architecture,
+    pub capabilities: Option<BTreeSet<String>>,
+    pub restricted_capabilities: Option<BTreeSet<String>>,
+    pub protocols: Option<BTreeSet<Protocol>>,
+    pub file_extensions: Option<BTreeSet<FileExtension>>,
+    pub commands: Option<BTreeSet<Command>>,
 }
 
 const APPX_MANIFEST_XML: &str = "AppxManifest.xml";
 pub const APPX_SIGNATURE_P7X: &str = "AppxSignature.p7x";
 
+const RESTRICTED_CAPABILITIES_NAMESPACE: &[u8] =
+    b"http://schemas.microsoft.com/appx/manifest/foundation/windows10/restrictedcapabilities";
+
 impl Msix {
     pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
         let mut zip = ZipArchive::new(reader)?;
@@ -33,6 +50,7 @@ impl Msix {
         let signature_sha_256 = hash_signature(&mut zip)?;
 
         let manifest = from_str::<Package>(&appx_manifest)?;
+        let features = Features::read(&appx_manifest)?;
 
         Ok(Self {
             display_name: manifest.properties.display_name,
@@ -43,19 +61,103 @@ impl Msix {
                 &manifest.identity.name,
                 &manifest.identity.publisher,
             ),
-            target_device_family: Platform::from_str(
-                &manifest.dependencies.target_device_family.name,
-            )?,
-            min_version: MinimumOSVersion::new(
-                manifest.dependencies.target_device_family.min_version,
-            )?,
+            target_device_families: manifest.dependencies.target_device_family,
             processor_architecture: Architecture::from_str(
                 &manifest.identity.processor_architecture,
             )?,
+            capabilities: non_empty(features.capabilities),
+            restricted_capabilities: non_empty(features.restricted_capabilities),
+            protocols: non_empty(features.protocols),
+            file_extensions: non_empty(features.file_extensions),
+            commands: non_empty(features.commands),
         })
     }
 }
 
+fn non_empty<T>(set: BTreeSet<T>) -> Option<BTreeSet<T>> {
+    (!set.is_empty()).then_some(set)
+}
+
+/// The capabilities and extensions of a package's applications
+#[derive(Default)]
+struct Features {
+    capabilities: BTreeSet<String>,
+    restricted_capabilities: BTreeSet<String>,
+    protocols: BTreeSet<Protocol>,
+    file_extensions: BTreeSet<FileExtension>,
+    commands: BTreeSet<Command>,
+}
+
+impl Features {
+    /// Reads the manifest with its namespaces resolved, as restricted capabilities only differ
+    /// from other capabilities by their namespace
+    fn read(appx_manifest: &str) -> Result<Self> {
+        let mut reader = NsReader::from_str(appx_manifest);
+        let mut features = Self::default();
+        let mut in_file_type = false;
+        loop {
+            match reader.read_resolved_event()? {
+                (namespace, Event::Start(element) | Event::Empty(element)) => {
+                    in_file_type = element.local_name().as_ref() == b"FileType";
+                    features.add(&namespace, &element)?;
+                }
+                (_, Event::Text(text)) if in_file_type => {
+                    let extension = text.unescape()?;
+                    let extension = extension.trim().trim_start_matches('.').to_lowercase();
+                    if let Ok(extension) = FileExtension::new(extension) {
+                        features.file_extensions.insert(extension);
+                    }
+                }
+                (_, Event::End(_)) => in_file_type = false,
+                (_, Event::Eof) => break,
+                _ => {}
+            }
+        }
+        Ok(features)
+    }
+
+    fn add(&mut self, namespace: &ResolveResult, element: &BytesStart) -> Result<()> {
+        let attribute = |name: &str| -> Result<Option<String>> {
+            Ok(match element.try_get_attribute(name)? {
+                Some(attribute) => Some(attribute.unescape_value()?.into_owned()),
+                None => None,
+            })
+        };
+        match element.local_name().as_ref() {
+            b"Capability" | b"DeviceCapability" => {
+                let Some(name) = attribute("Name")? else {
+                    return Ok(());
+                };
+                if *namespace == ResolveResult::Bound(Namespace(RESTRICTED_CAPABILITIES_NAMESPACE))
+                {
+                    self.restricted_capabilities.insert(name);
+                } else {
+                    self.capabilities.insert(name);
+                }
+            }
+            b"Protocol" => {
+                if let Some(protocol) = attribute("Name")?.and_then(|name| Protocol::new(name).ok())
+                {
+                    self.protocols.insert(protocol);
+                }
+            }
+            b"ExecutionAlias" => {
+                if let Some(command) = attribute("Alias")?.and_then(|alias| {
+                    let command = alias
+                        .strip_suffix(".exe")
+                        .or_else(|| alias.strip_suffix(".EXE"))
+                        .unwrap_or(&alias);
+                    Command::new(command).ok()
+                }) {
+                    self.commands.insert(command);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
 struct Package {
@@ -87,14 +189,127 @@ struct Properties {
 #[derive(Default, Deserialize)]
 #[serde(default, rename_all = "PascalCase")]
 pub(super) struct Dependencies {
-    pub target_device_family: TargetDeviceFamily,
+    pub target_device_family: Vec<TargetDeviceFamily>,
 }
 
-#[derive(Default, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
-pub(super) struct TargetDeviceFamily {
-    #[serde(rename = "@Name")]
+pub struct TargetDeviceFamily {
+    #[serde(rename(deserialize = "@Name"))]
     pub name: String,
-    #[serde(rename = "@MinVersion")]
+    #[serde(rename(deserialize = "@MinVersion"))]
     pub min_version: String,
 }
+
+impl TargetDeviceFamily {
+    /// Gets the platforms that winget supports, ignoring others such as `Windows.Holographic`
+    pub fn platforms<'a>(families: impl IntoIterator<Item = &'a Self>) -> BTreeSet<Platform> {
+        families
+            .into_iter()
+            .filter_map(|family| Platform::from_str(&family.name).ok())
+            .collect()
+    }
+
+    /// Gets the lowest minimum version of the families that winget supports, or of all the
+    /// families if it supports none of them
+    pub fn lowest_min_version<'a>(
+        families: impl IntoIterator<Item = &'a Self> + Clone,
+    ) -> Option<MinimumOSVersion> {
+        let is_supported = |family: &&Self| Platform::from_str(&family.name).is_ok();
+        let any_supported = families
+            .clone()
+            .into_iter()
+            .any(|family| is_supported(&family));
+        families
+            .into_iter()
+            .filter(|family| !any_supported || is_supported(family))
+            .filter_map(|family| MinimumOSVersion::new(&family.min_version).ok())
+            .min_by_key(|version| {
+                // Compare each part as a number so that 10.0.9200.0 is lower than 10.0.17763.0
+                version
+                    .split('.')
+                    .map(|part| part.parse::<u16>().unwrap_or_default())
+                    .collect::<Vec<_>>()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::msix_family::msix::Features;
+
+    #[test]
+    fn test_read_features() {
+        let appx_manifest = r#"<?xml version="1.0" encoding="utf-8"?>
+<Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10"
+    xmlns:uap="http://schemas.microsoft.com/appx/manifest/uap/windows10"
+    xmlns:uap5="http://schemas.microsoft.com/appx/manifest/uap/windows10/5"
+    xmlns:rescap="http://schemas.microsoft.com/appx/manifest/foundation/windows10/restrictedcapabilities">
+  <Applications>
+    <Application Id="App" Executable="App.exe" EntryPoint="Windows.FullTrustApplication">
+      <Extensions>
+        <uap:Extension Category="windows.protocol">
+          <uap:Protocol Name="example" />
+        </uap:Extension>
+        <uap:Extension Category="windows.fileTypeAssociation">
+          <uap:FileTypeAssociation Name="documents">
+            <uap:SupportedFileTypes>
+              <uap:FileType>.TXT</uap:FileType>
+              <uap:FileType ContentType="text/markdown">.md</uap:FileType>
+            </uap:SupportedFileTypes>
+          </uap:FileTypeAssociation>
+        </uap:Extension>
+        <uap5:Extension Category="windows.appExecutionAlias">
+          <uap5:AppExecutionAlias>
+            <uap5:ExecutionAlias Alias="example.exe" />
+          </uap5:AppExecutionAlias>
+        </uap5:Extension>
+      </Extensions>
+    </Application>
+  </Applications>
+  <Capabilities>
+    <Capability Name="internetClient" />
+    <rescap:Capability Name="runFullTrust" />
+    <DeviceCapability Name="microphone" />
+  </Capabilities>
+</Package>"#;
+        let features = Features::read(appx_manifest).unwrap();
+        assert_eq!(
+            ["internetClient", "microphone"],
+            features
+                .capabilities
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+        assert!(features.restricted_capabilities.contains("runFullTrust"));
+        assert_eq!(
+            ["example"],
+            features
+                .protocols
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+        assert_eq!(
+            ["md", "txt"],
+            features
+                .file_extensions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+        assert_eq!(
+            ["example"],
+            features
+                .commands
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+    }
+}
@@ -1,30 +1,43 @@
 use crate::manifests::installer_manifest::Platform;
 use crate::msix_family::msix;
+use crate::msix_family::msix::TargetDeviceFamily;
 use crate::msix_family::utils::{hash_signature, read_manifest};
 use crate::types::architecture::Architecture;
+use crate::types::minimum_os_version::MinimumOSVersion;
 use color_eyre::eyre::Result;
 use package_family_name::get_package_family_name;
 use quick_xml::de::from_str;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{Read, Seek};
 use std::str::FromStr;
 use zip::ZipArchive;
 
+#[derive(Deserialize, Serialize)]
 pub struct MsixBundle {
     pub signature_sha_256: String,
     pub package_family_name: String,
     pub packages: Vec<IndividualPackage>,
 }
 
+#[derive(Deserialize, Serialize)]
 pub struct IndividualPackage {
     pub version: String,
-    pub target_device_family: Platform,
-    pub min_version: String,
     pub processor_architecture: Architecture,
+    pub target_device_families: Vec<TargetDeviceFamily>,
+}
+
+/// The platforms and minimum OS version of the packages for one architecture
+pub struct BundleTargets {
+    pub platform: Option<BTreeSet<Platform>>,
+    pub minimum_os_version: Option<MinimumOSVersion>,
 }
 
 const APPX_BUNDLE_MANIFEST_PATH: &str = "AppxMetadata/AppxBundleManifest.xml";
 
+/// Resource packages only contain resources such as languages and scales
+const RESOURCE_PACKAGE: &str = "resource";
+
 impl MsixBundle {
     pub fn new<R: Read + Seek>(reader: R) -> Result<Self> {
         let mut zip = ZipArchive::new(reader)?;
@@ -45,18 +58,43 @@ impl MsixBundle {
                 .packages
                 .package
                 .into_iter()
-                .map(|package| IndividualPackage {
-                    version: package.version,
-                    target_device_family: Platform::from_str(
-                        &package.dependencies.target_device_family.name,
-                    )
-                    .unwrap(),
-                    min_version: package.dependencies.target_device_family.min_version,
-                    processor_architecture: Architecture::from_str(&package.architecture).unwrap(),
+                .filter(|package| package.r#type != RESOURCE_PACKAGE)
+                .map(|package| {
+                    Ok(IndividualPackage {
+                        version: package.version,
+                        processor_architecture: Architecture::from_str(&package.architecture)?,
+                        target_device_families: package.dependencies.target_device_family,
+                    })
                 })
-                .collect(),
+                .collect::<Result<_>>()?,
         })
     }
+
+    /// Groups the packages by their architecture, as each architecture is a separate installer
+    pub fn targets(&self) -> BTreeMap<Architecture, BundleTargets> {
+        let mut families = BTreeMap::<_, Vec<_>>::new();
+        for package in &self.packages {
+            families
+                .entry(package.processor_architecture)
+                .or_default()
+                .extend(&package.target_device_families);
+        }
+        families
+            .into_iter()
+            .map(|(architecture, families)| {
+                let platform = TargetDeviceFamily::platforms(families.iter().copied());
+                (
+                    architecture,
+                    BundleTargets {
+                        platform: (!platform.is_empty()).then_some(platform),
+                        minimum_os_version: TargetDeviceFamily::lowest_min_version(
+                            families.iter().copied(),
+                        ),
+                    },
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Default, Deserialize)]
@@ -93,3 +131,69 @@ struct Package {
     #[serde(rename = "Dependencies")]
     dependencies: msix::Dependencies,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::manifests::installer_manifest::Platform;
+    use crate::msix_family::msix::TargetDeviceFamily;
+    use crate::msix_family::msixbundle::{IndividualPackage, MsixBundle};
+    use crate::types::architecture::Architecture;
+    use std::collections::BTreeSet;
+
+    fn package(architecture: Architecture, families: &[(&str, &str)]) -> IndividualPackage {
+        IndividualPackage {
+            version: "1.0.0.0".to_owned(),
+            processor_architecture: architecture,
+            target_device_families: families
+                .iter()
+                .map(|(name, min_version)| TargetDeviceFamily {
+                    name: (*name).to_owned(),
+                    min_version: (*min_version).to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_targets_per_architecture() {
+        let bundle = MsixBundle {
+            signature_sha_256: String::new(),
+            package_family_name: String::new(),
+            packages: vec![
+                package(
+                    Architecture::X64,
+                    &[
+                        ("Windows.Desktop", "10.0.17763.0"),
+                        ("Windows.Universal", "10.0.9200.0"),
+                        ("Windows.Holographic", "10.0.1.0"),
+                    ],
+                ),
+                package(
+                    Architecture::Arm64,
+                    &[("Windows.Holographic", "10.0.19041.0")],
+                ),
+            ],
+        };
+        let targets = bundle.targets();
+
+        let x64 = &targets[&Architecture::X64];
+        assert_eq!(
+            Some(BTreeSet::from([
+                Platform::WindowsDesktop,
+                Platform::WindowsUniversal
+            ])),
+            x64.platform
+        );
+        assert_eq!(
+            Some("10.0.9200.0"),
+            x64.minimum_os_version.as_deref().map(String::as_str)
+        );
+
+        let arm64 = &targets[&Architecture::Arm64];
+        assert_eq!(None, arm64.platform);
+        assert_eq!(
+            Some("10.0.19041.0"),
+            arm64.minimum_os_version.as_deref().map(String::as_str)
+        );
+    }
+}
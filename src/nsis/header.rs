@@ -0,0 +1,118 @@
+use crate::nsis::strings::NsisStrings;
+use crate::nsis::u32_at;
+use color_eyre::eyre::{OptionExt, Result};
+
+/// The number of blocks that the header starts with after its flags
+const BLOCKS_NUM: usize = 8;
+
+/// Indexes of the blocks in the header that are read
+const NB_ENTRIES: usize = 2;
+const NB_STRINGS: usize = 3;
+const NB_LANGTABLES: usize = 4;
+const NB_CTLCOLORS: usize = 5;
+
+/// An opcode followed by its 6 parameters
+const ENTRY_SIZE: usize = 4 * 7;
+
+/// The language ID, dialog offset and right-to-left flag that precede a table's strings
+const LANGUAGE_TABLE_HEADER_SIZE: usize = 2 + 4 + 4;
+
+const ENGLISH_LANGUAGE_ID: u16 = 1033;
+
+/// Opcodes are the same from NSIS 2 onwards until after `WriteReg`
+pub const EW_SETFLAG: u32 = 13;
+pub const EW_WRITEREG: u32 = 51;
+
+#[derive(Clone, Copy)]
+struct Block {
+    offset: usize,
+    num: usize,
+}
+
+pub struct Entry {
+    pub which: u32,
+    pub offsets: [u32; 6],
+}
+
+pub struct Header<'data> {
+    data: &'data [u8],
+    blocks: [Block; BLOCKS_NUM],
+}
+
+impl<'data> Header<'data> {
+    pub fn parse(data: &'data [u8]) -> Result<Self> {
+        let mut blocks = [Block { offset: 0, num: 0 }; BLOCKS_NUM];
+        for (index, block) in blocks.iter_mut().enumerate() {
+            let position = 4 + index * 8;
+            *block = Block {
+                offset: u32_at(data, position).ok_or_eyre("The NSIS header is truncated")? as usize,
+                num: u32_at(data, position + 4).ok_or_eyre("The NSIS header is truncated")?
+                    as usize,
+            };
+        }
+        Ok(Self { data, blocks })
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = Entry> + 'data {
+        let Block { offset, num } = self.blocks[NB_ENTRIES];
+        self.data
+            .get(offset..)
+            .unwrap_or_default()
+            .chunks_exact(ENTRY_SIZE)
+            .take(num)
+            .map(|entry| {
+                let mut values = entry
+                    .chunks_exact(4)
+                    .map(|value| u32::from_le_bytes([value[0], value[1], value[2], value[3]]));
+                Entry {
+                    which: values.next().unwrap_or_default(),
+                    offsets: std::array::from_fn(|_| values.next().unwrap_or_default()),
+                }
+            })
+    }
+
+    pub fn strings(&self) -> Result<NsisStrings<'data>> {
+        let start = self.blocks[NB_STRINGS].offset;
+        let end = self.blocks[NB_LANGTABLES].offset;
+        let strings = self
+            .data
+            .get(start..end)
+            .ok_or_eyre("The NSIS string table is outside of the header")?;
+        Ok(NsisStrings::new(strings, self.language_strings()))
+    }
+
+    /// Reads the string offsets of the English language table, or the first table if the
+    /// installer doesn't include English
+    fn language_strings(&self) -> Vec<i32> {
+        let Block { offset, num } = self.blocks[NB_LANGTABLES];
+        if num == 0 {
+            return Vec::new();
+        }
+        let table_size = self.blocks[NB_CTLCOLORS].offset.saturating_sub(offset) / num;
+        if table_size <= LANGUAGE_TABLE_HEADER_SIZE {
+            return Vec::new();
+        }
+        let tables = self
+            .data
+            .get(offset..offset + table_size * num)
+            .unwrap_or_default()
+            .chunks_exact(table_size);
+        let mut first = None;
+        for table in tables {
+            let language_id = u16::from_le_bytes([table[0], table[1]]);
+            if language_id == ENGLISH_LANGUAGE_ID {
+                first = Some(table);
+                break;
+            }
+            first = first.or(Some(table));
+        }
+        first
+            .map(|table| {
+                table[LANGUAGE_TABLE_HEADER_SIZE..]
+                    .chunks_exact(4)
+                    .map(|value| i32::from_le_bytes([value[0], value[1], value[2], value[3]]))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,315 @@
+mod header;
+mod strings;
+
+use crate::lzma;
+use crate::manifests::installer_manifest::{AppsAndFeaturesEntry, Scope};
+use crate::nsis::header::{Header, EW_SETFLAG, EW_WRITEREG};
+use crate::types::product_code::ProductCode;
+use color_eyre::eyre::{bail, OptionExt, Result};
+use flate2::read::DeflateDecoder;
+use object::{LittleEndian, ReadRef, U32};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use std::io::Read;
+
+const FIRST_HEADER_SIGNATURE: u32 = 0xDEAD_BEEF;
+const FIRST_HEADER_MAGIC: &[u8; 12] = b"NullsoftInst";
+
+/// Flags, signature, magic, header length and the length of all the data that follows
+const FIRST_HEADER_SIZE: usize = 4 + 4 + 12 + 4 + 4;
+
+/// The first header is stored at a 512 byte boundary after the executable stub
+const FIRST_HEADER_ALIGNMENT: usize = 512;
+
+/// Set on the size of a block that is compressed on its own
+const COMPRESSED_FLAG: u32 = 0x8000_0000;
+
+const UNINSTALL_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Uninstall\";
+
+const HKEY_CURRENT_USER: u32 = 0x8000_0001;
+const HKEY_LOCAL_MACHINE: u32 = 0x8000_0002;
+
+/// NSIS 3 stores the registry view of root keys such as `HKLM64` in these bits
+const REGISTRY_VIEW_MASK: u32 = 0x6000_0000;
+
+/// The index of `all_user_var` in the installer's flags, which is set by `SetShellVarContext`
+const ALL_USER_VAR_FLAG: u32 = 1;
+
+#[skip_serializing_none]
+#[derive(Deserialize, Serialize)]
+pub struct Nsis {
+    pub uninstall_registry_key: Option<String>,
+    pub display_name: Option<String>,
+    pub display_version: Option<String>,
+    pub publisher: Option<String>,
+    pub scope: Option<Scope>,
+}
+
+impl Nsis {
+    pub fn new(data: &[u8]) -> Result<Self> {
+        let (header_length, data) = find_first_header(data)?;
+        let header = read_header(data, header_length)?;
+        let header = Header::parse(&header)?;
+        let strings = header.strings()?;
+
+        let mut nsis = Self {
+            uninstall_registry_key: None,
+            display_name: None,
+            display_version: None,
+            publisher: None,
+            scope: None,
+        };
+        let mut root_key = None;
+        let mut all_users = Vec::new();
+        for entry in header.entries() {
+            match entry.which {
+                EW_SETFLAG if entry.offsets[0] == ALL_USER_VAR_FLAG => {
+                    if let Some(value) = strings.get(entry.offsets[1] as i32) {
+                        all_users.push(value != "0");
+                    }
+                }
+                // Only string values are read, which have a type of REG_SZ or REG_EXPAND_SZ
+                EW_WRITEREG if matches!(entry.offsets[4], 1 | 2) => {
+                    let Some(key) = strings
+                        .get(entry.offsets[1] as i32)
+                        .filter(|key| uninstall_key_name(key).is_some())
+                    else {
+                        continue;
+                    };
+                    match &nsis.uninstall_registry_key {
+                        Some(uninstall_key) if !uninstall_key.eq_ignore_ascii_case(&key) => {
+                            continue
+                        }
+                        Some(_) => {}
+                        None => {
+                            root_key = Some(entry.offsets[0] & !REGISTRY_VIEW_MASK);
+                            nsis.uninstall_registry_key = Some(key);
+                        }
+                    }
+                    let (Some(name), Some(value)) = (
+                        strings.get(entry.offsets[2] as i32),
+                        strings
+                            .get(entry.offsets[3] as i32)
+                            .filter(|value| !value.is_empty()),
+                    ) else {
+                        continue;
+                    };
+                    let field = match name.to_ascii_lowercase().as_str() {
+                        "displayname" => &mut nsis.display_name,
+                        "displayversion" => &mut nsis.display_version,
+                        "publisher" => &mut nsis.publisher,
+                        _ => continue,
+                    };
+                    field.get_or_insert(value);
+                }
+                _ => {}
+            }
+        }
+        nsis.scope = match root_key {
+            Some(HKEY_LOCAL_MACHINE) => Some(Scope::Machine),
+            Some(HKEY_CURRENT_USER) => Some(Scope::User),
+            // SHCTX writes to HKLM if SetShellVarContext is all and HKCU otherwise
+            Some(_) => match (all_users.contains(&true), all_users.contains(&false)) {
+                (true, false) => Some(Scope::Machine),
+                (false, _) => Some(Scope::User),
+                (true, true) => None,
+            },
+            None => None,
+        };
+        Ok(nsis)
+    }
+
+    /// The name of the uninstall key is what winget uses as the product code
+    pub fn product_code(&self) -> Option<ProductCode> {
+        self.uninstall_registry_key
+            .as_deref()
+            .and_then(uninstall_key_name)
+            .and_then(|name| ProductCode::new(name).ok())
+    }
+
+    pub fn apps_and_features_entry(&self) -> AppsAndFeaturesEntry {
+        AppsAndFeaturesEntry {
+            display_name: self.display_name.clone(),
+            publisher: self.publisher.clone(),
+            display_version: self.display_version.clone(),
+            ..AppsAndFeaturesEntry::default()
+        }
+    }
+}
+
+pub fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    data.read_at::<U32<LittleEndian>>(offset as u64)
+        .ok()
+        .map(|value| value.get(LittleEndian))
+}
+
+/// Returns the name of a key directly under the uninstall key
+fn uninstall_key_name(key: &str) -> Option<&str> {
+    key.get(..UNINSTALL_KEY.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(UNINSTALL_KEY))
+        .and_then(|_| key.get(UNINSTALL_KEY.len()..))
+        .filter(|name| !name.is_empty() && !name.contains('\\'))
+}
+
+/// Finds the first header that is appended to the executable stub, returning the length of the
+/// header and the data that follows it
+pub fn find_first_header(data: &[u8]) -> Result<(usize, &[u8])> {
+    (0..data.len())
+        .step_by(FIRST_HEADER_ALIGNMENT)
+        .find(|&offset| {
+            u32_at(data, offset + 4) == Some(FIRST_HEADER_SIGNATURE)
+                && data.get(offset + 8..offset + 20) == Some(FIRST_HEADER_MAGIC)
+        })
+        .and_then(|offset| {
+            Some((
+                u32_at(data, offset + 20)? as usize,
+                data.get(offset + FIRST_HEADER_SIZE..)?,
+            ))
+        })
+        .ok_or_eyre("No NSIS first header was found")
+}
+
+/// Reads the header from either a block that is compressed on its own, an uncompressed block, or
+/// the start of a solid archive, where the header is preceded by its size
+fn read_header(data: &[u8], header_length: usize) -> Result<Vec<u8>> {
+    let truncated = "The NSIS header is truncated";
+    let mut header = if let Some(stream) = lzma_stream(data) {
+        solid_header(lzma::decompress_with_limit(stream, 4 + header_length)?)?
+    } else {
+        let size = u32_at(data, 0).ok_or_eyre(truncated)?;
+        match data.get(4..4 + (size & !COMPRESSED_FLAG) as usize) {
+            Some(block) if size & COMPRESSED_FLAG != 0 => match lzma_stream(block) {
+                Some(stream) => lzma::decompress_with_limit(stream, header_length)?,
+                None => match inflate(block, header_length) {
+                    Ok(header) => header,
+                    Err(_) => solid_header(inflate(data, 4 + header_length)?)?,
+                },
+            },
+            Some(block) if size as usize == header_length => block.to_vec(),
+            _ => solid_header(inflate(data, 4 + header_length)?)?,
+        }
+    };
+    if header.len() < header_length {
+        bail!(truncated);
+    }
+    header.truncate(header_length);
+    Ok(header)
+}
+
+/// Skips the size of the header at the start of a decompressed solid archive
+fn solid_header(mut data: Vec<u8>) -> Result<Vec<u8>> {
+    if data.len() < 4 {
+        bail!("The NSIS header is truncated");
+    }
+    data.drain(..4);
+    Ok(data)
+}
+
+/// Returns the LZMA stream that starts with its properties, skipping the byte that NSIS uses to
+/// mark whether the x86 branch filter is used when it's not
+fn lzma_stream(data: &[u8]) -> Option<&[u8]> {
+    const LZMA_PROPERTIES: [u8; 3] = [0x5D, 0, 0];
+    if data.starts_with(&LZMA_PROPERTIES) {
+        Some(data)
+    } else if data.first() == Some(&0) && data[1..].starts_with(&LZMA_PROPERTIES) {
+        Some(&data[1..])
+    } else {
+        None
+    }
+}
+
+fn inflate(data: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    DeflateDecoder::new(data)
+        .take(limit as u64)
+        .read_to_end(&mut output)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::manifests::installer_manifest::Scope;
+    use crate::nsis::header::{EW_SETFLAG, EW_WRITEREG};
+    use crate::nsis::{Nsis, COMPRESSED_FLAG, FIRST_HEADER_MAGIC, FIRST_HEADER_SIGNATURE};
+    use flate2::write::DeflateEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    const SHCTX: u32 = 0;
+    const HKEY_LOCAL_MACHINE: u32 = 0x8000_0002;
+
+    /// Builds an ANSI header with the given entries and strings
+    fn header(entries: &[[u32; 7]], strings: &[u8]) -> Vec<u8> {
+        let blocks_size = 4 + 8 * 8;
+        let entries_offset = blocks_size;
+        let strings_offset = entries_offset + entries.len() * 28;
+        let language_tables_offset = strings_offset + strings.len();
+        let mut header = vec![0; 4];
+        for (offset, num) in [
+            (0, 0),
+            (0, 0),
+            (entries_offset, entries.len()),
+            (strings_offset, 0),
+            (language_tables_offset, 0),
+            (language_tables_offset, 0),
+            (language_tables_offset, 0),
+            (language_tables_offset, 0),
+        ] {
+            header.extend((offset as u32).to_le_bytes());
+            header.extend((num as u32).to_le_bytes());
+        }
+        for entry in entries {
+            header.extend(entry.iter().flat_map(|value| value.to_le_bytes()));
+        }
+        header.extend(strings);
+        header
+    }
+
+    fn installer(header: &[u8]) -> Vec<u8> {
+        let mut compressed = DeflateEncoder::new(Vec::new(), Compression::default());
+        compressed.write_all(header).unwrap();
+        let compressed = compressed.finish().unwrap();
+
+        let mut data = vec![0; 1024];
+        data.extend(0_u32.to_le_bytes());
+        data.extend(FIRST_HEADER_SIGNATURE.to_le_bytes());
+        data.extend(FIRST_HEADER_MAGIC);
+        data.extend((header.len() as u32).to_le_bytes());
+        data.extend(0_u32.to_le_bytes());
+        data.extend((compressed.len() as u32 | COMPRESSED_FLAG).to_le_bytes());
+        data.extend(compressed);
+        data
+    }
+
+    #[test]
+    fn test_uninstall_entry() {
+        // 0: "", 1: uninstall key, 57: "DisplayName", 69: "App", 73: "DisplayVersion",
+        // 88: "1.2.3", 94: "Publisher", 104: "Example", 112: "1"
+        let strings = b"\0Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\App\0DisplayName\0App\0DisplayVersion\0\x31.2.3\0Publisher\0Example\0\x31\0";
+        let entries = [
+            [EW_SETFLAG, 1, 112, 0, 0, 0, 0],
+            [EW_WRITEREG, SHCTX, 1, 57, 69, 1, 1],
+            [EW_WRITEREG, SHCTX, 1, 73, 88, 1, 1],
+            [EW_WRITEREG, SHCTX, 1, 94, 104, 1, 1],
+        ];
+        let nsis = Nsis::new(&installer(&header(&entries, strings))).unwrap();
+        assert_eq!(
+            Some("App"),
+            nsis.product_code().as_deref().map(String::as_str)
+        );
+        assert_eq!(Some("App"), nsis.display_name.as_deref());
+        assert_eq!(Some("1.2.3"), nsis.display_version.as_deref());
+        assert_eq!(Some("Example"), nsis.publisher.as_deref());
+        assert_eq!(Some(Scope::Machine), nsis.scope);
+    }
+
+    #[test]
+    fn test_other_keys_are_ignored() {
+        let strings = b"\0Software\\App\0DisplayName\0App\0";
+        let entries = [[EW_WRITEREG, HKEY_LOCAL_MACHINE, 1, 14, 26, 1, 1]];
+        let nsis = Nsis::new(&installer(&header(&entries, strings))).unwrap();
+        assert_eq!(None, nsis.uninstall_registry_key);
+        assert_eq!(None, nsis.display_name);
+        assert_eq!(None, nsis.scope);
+    }
+}
@@ -0,0 +1,165 @@
+/// The special characters that NSIS uses to embed variables and language strings in its strings
+struct Codes {
+    skip: u16,
+    var: u16,
+    shell: u16,
+    lang: u16,
+}
+
+/// NSIS 3 uses the same low codes for both ANSI and Unicode installers
+const NSIS_3_CODES: Codes = Codes {
+    skip: 4,
+    var: 3,
+    shell: 2,
+    lang: 1,
+};
+
+const NSIS_2_CODES: Codes = Codes {
+    skip: 252,
+    var: 253,
+    shell: 254,
+    lang: 255,
+};
+
+/// The Unicode fork of NSIS 2 by Jim Park uses private use characters
+const PARK_CODES: Codes = Codes {
+    skip: 0xE000,
+    var: 0xE001,
+    shell: 0xE002,
+    lang: 0xE003,
+};
+
+/// The string table of an NSIS header along with the strings of the installer's language
+pub struct NsisStrings<'data> {
+    data: &'data [u8],
+    unicode: bool,
+    codes: &'static Codes,
+    language_strings: Vec<i32>,
+}
+
+impl<'data> NsisStrings<'data> {
+    pub fn new(data: &'data [u8], language_strings: Vec<i32>) -> Self {
+        // The first string is always empty, so a Unicode table starts with two zero bytes
+        let unicode = data.get(..2) == Some(&[0, 0]);
+        let codes = if unicode {
+            if data
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .any(|character| (PARK_CODES.skip..=PARK_CODES.lang).contains(&character))
+            {
+                &PARK_CODES
+            } else {
+                &NSIS_3_CODES
+            }
+        } else if data
+            .iter()
+            .any(|&byte| (NSIS_3_CODES.lang..=NSIS_3_CODES.skip).contains(&u16::from(byte)))
+        {
+            &NSIS_3_CODES
+        } else {
+            &NSIS_2_CODES
+        };
+        Self {
+            data,
+            unicode,
+            codes,
+            language_strings,
+        }
+    }
+
+    /// Gets the string at an offset, where negative offsets refer to language strings. Returns
+    /// None if the string contains a variable or shell folder, as those are only known when the
+    /// installer runs.
+    pub fn get(&self, offset: i32) -> Option<String> {
+        self.get_with_depth(offset, 0)
+    }
+
+    fn get_with_depth(&self, offset: i32, depth: u8) -> Option<String> {
+        if offset < 0 {
+            return self.language_string(offset.unsigned_abs() as usize - 1, depth);
+        }
+        let offset = usize::try_from(offset).ok()?;
+        let mut characters = self.characters(offset)?;
+        let mut string = String::new();
+        while let Some(character) = characters.next() {
+            match character {
+                0 => return Some(string),
+                code if code == self.codes.skip => {
+                    string.push(char::from_u32(u32::from(characters.next()?))?);
+                }
+                code if code == self.codes.lang => {
+                    let index = self.decode_short(&mut characters)?;
+                    string.push_str(&self.language_string(index, depth)?);
+                }
+                code if code == self.codes.var || code == self.codes.shell => return None,
+                character if self.unicode => {
+                    string.push(char::from_u32(u32::from(character))?);
+                }
+                // ANSI strings are read as Latin-1
+                character => string.push(char::from(u8::try_from(character).ok()?)),
+            }
+        }
+        None
+    }
+
+    fn language_string(&self, index: usize, depth: u8) -> Option<String> {
+        // Language strings can refer to other language strings, such as $(^NameDA) to $(^Name)
+        if depth > 2 {
+            return None;
+        }
+        let offset = *self.language_strings.get(index)?;
+        self.get_with_depth(offset, depth + 1)
+    }
+
+    fn characters(&self, offset: usize) -> Option<Box<dyn Iterator<Item = u16> + 'data>> {
+        if self.unicode {
+            let data = self.data.get(offset * 2..)?;
+            Some(Box::new(
+                data.chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]])),
+            ))
+        } else {
+            let data = self.data.get(offset..)?;
+            Some(Box::new(data.iter().map(|&byte| u16::from(byte))))
+        }
+    }
+
+    /// Reads the index that follows a code, which is stored with the high bits set so that it
+    /// never contains a null character
+    fn decode_short(&self, characters: &mut impl Iterator<Item = u16>) -> Option<usize> {
+        if self.unicode {
+            Some(usize::from(characters.next()? & 0x7FFF))
+        } else {
+            let low = characters.next()? & 0x7F;
+            let high = characters.next()? & 0x7F;
+            Some(usize::from(high << 7 | low))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nsis::strings::NsisStrings;
+
+    #[test]
+    fn test_ansi_strings() {
+        // "", "App $INSTDIR", "$(^Name) 1.0", "App"
+        let data = b"\0App \x03\x81\x80\0\x01\x82\x80 1.0\0App\0";
+        let strings = NsisStrings::new(data, vec![0, 0, 17]);
+        assert_eq!(Some(String::new()), strings.get(0));
+        assert_eq!(None, strings.get(1));
+        assert_eq!(Some("App 1.0".to_owned()), strings.get(9));
+        assert_eq!(Some("App".to_owned()), strings.get(-3));
+    }
+
+    #[test]
+    fn test_unicode_strings() {
+        let data = "\0Publisher\0\u{e000}\u{e003}\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let strings = NsisStrings::new(&data, Vec::new());
+        assert_eq!(Some("Publisher".to_owned()), strings.get(1));
+        assert_eq!(Some("\u{e003}".to_owned()), strings.get(11));
+    }
+}
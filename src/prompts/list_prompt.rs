@@ -1,3 +1,4 @@
+use crate::prompts::prompts_disabled;
 use inquire::validator::Validation;
 use inquire::Text;
 use std::collections::{BTreeSet, HashSet};
@@ -10,11 +11,16 @@ pub trait ListPrompt {
     const MAX_ITEMS: u16;
 }
 
-pub fn list_prompt<T>() -> color_eyre::Result<Option<BTreeSet<T>>>
+pub fn list_prompt<T>(parameter: Vec<T>) -> color_eyre::Result<Option<BTreeSet<T>>>
 where
     T: FromStr + ListPrompt + Ord,
     <T as FromStr>::Err: Display + Debug + Sync + Send + 'static,
 {
+    if !parameter.is_empty() {
+        return Ok(Some(parameter.into_iter().collect()));
+    } else if prompts_disabled() {
+        return Ok(None);
+    }
     let items = Text::new(T::MESSAGE)
         .with_help_message(T::HELP_MESSAGE)
         .with_validator(|input: &str| {
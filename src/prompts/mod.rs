@@ -1,3 +1,25 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 pub mod list_prompt;
 pub mod multi_prompt;
 pub mod prompt;
+
+static PROMPTS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Stops prompts from asking for input so that Komac can run without a terminal, such as in CI.
+/// Optional values are then left empty and required values are an error.
+pub fn disable_prompts() {
+    PROMPTS_DISABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn prompts_disabled() -> bool {
+    PROMPTS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// The error for a required value that would have been prompted for
+pub fn missing_value(message: &str) -> color_eyre::Report {
+    color_eyre::eyre::eyre!(
+        "{} is required but was not given as an argument or in the answers file",
+        message.trim_end_matches(':')
+    )
+}
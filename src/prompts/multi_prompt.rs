@@ -1,4 +1,5 @@
 use crate::manifests::installer_manifest::{InstallModes, UpgradeBehavior};
+use crate::prompts::{missing_value, prompts_disabled};
 use color_eyre::eyre::{Error, Result};
 use inquire::{MultiSelect, Select};
 use std::collections::BTreeSet;
@@ -17,19 +18,29 @@ impl MultiPrompt for InstallModes {
     const MESSAGE: &'static str = "Install modes:";
 }
 
-pub fn radio_prompt<T>() -> Result<T>
+pub fn radio_prompt<T>(parameter: Option<T>) -> Result<T>
 where
     T: MultiPrompt + IntoEnumIterator + Display + Ord,
 {
+    if let Some(value) = parameter {
+        return Ok(value);
+    } else if prompts_disabled() {
+        return Err(missing_value(T::MESSAGE));
+    }
     Select::new(T::MESSAGE, T::iter().collect())
         .prompt()
         .map_err(Error::msg)
 }
 
-pub fn check_prompt<T>() -> Result<Option<BTreeSet<T>>>
+pub fn check_prompt<T>(parameter: Vec<T>) -> Result<Option<BTreeSet<T>>>
 where
     T: MultiPrompt + IntoEnumIterator + Display + Ord,
 {
+    if !parameter.is_empty() {
+        return Ok(Some(parameter.into_iter().collect()));
+    } else if prompts_disabled() {
+        return Ok(None);
+    }
     MultiSelect::new(T::MESSAGE, T::iter().collect())
         .prompt()
         .map(|items| {
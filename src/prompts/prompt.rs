@@ -1,3 +1,4 @@
+use crate::prompts::{missing_value, prompts_disabled};
 use color_eyre::eyre::Error;
 use inquire::validator::Validation;
 use inquire::Text;
@@ -23,6 +24,8 @@ where
 {
     if let Some(value) = parameter {
         Ok(Some(value))
+    } else if prompts_disabled() {
+        Ok(None)
     } else {
         let mut prompt = Text::new(T::MESSAGE).with_validator(|input: &str| {
             if input.is_empty() {
@@ -58,6 +61,8 @@ where
 {
     if let Some(value) = parameter {
         Ok(value)
+    } else if prompts_disabled() {
+        Err(missing_value(T::MESSAGE))
     } else {
         let mut prompt = Text::new(T::MESSAGE).with_validator(|input: &str| {
             if let Err(error) = T::from_str(input) {
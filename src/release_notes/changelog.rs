@@ -0,0 +1,132 @@
+use crate::release_notes::{get_text, ReleaseNotesProvider};
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use color_eyre::eyre::Result;
+use reqwest::Client;
+use url::Url;
+
+/// A markdown changelog at a URL, such as a raw `CHANGELOG.md`
+pub struct Changelog(pub Url);
+
+impl ReleaseNotesProvider for Changelog {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>> {
+        Ok(get_text(client, &self.0).await?.and_then(|changelog| {
+            version_section(&changelog, &version.to_string()).and_then(ReleaseNotes::from_markdown)
+        }))
+    }
+}
+
+/// Finds the section under the first markdown heading that mentions the version, up to the next
+/// heading of the same or a higher level
+pub fn version_section<'changelog>(
+    changelog: &'changelog str,
+    version: &str,
+) -> Option<&'changelog str> {
+    let mut start = None;
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if let Some((level, heading)) = heading(line) {
+            match start {
+                Some((start_level, start_offset)) if level <= start_level => {
+                    return non_empty(&changelog[start_offset..offset]);
+                }
+                None if mentions_version(heading, version) => {
+                    start = Some((level, offset + line.len()));
+                }
+                _ => (),
+            }
+        }
+        offset += line.len();
+    }
+    start.and_then(|(_, start_offset)| non_empty(&changelog[start_offset..]))
+}
+
+/// The level and text of an ATX heading such as `## [1.2.3] - 2024-01-01`
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim();
+    let level = line.chars().take_while(|&char| char == '#').count();
+    let text = &line[level..];
+    ((1..=6).contains(&level) && (text.is_empty() || text.starts_with(' ')))
+        .then(|| (level, text.trim()))
+}
+
+/// Whether the text contains the version on its own, so that 1.2.3 isn't found in 1.2.30 or 1.2.3-beta
+fn mentions_version(text: &str, version: &str) -> bool {
+    text.match_indices(version).any(|(index, _)| {
+        let before = text[..index].chars().next_back();
+        let after = text[index + version.len()..].chars().next();
+        before.is_none_or(|char| {
+            matches!(char, 'v' | 'V') || !(char.is_alphanumeric() || char == '.')
+        }) && after.is_none_or(|char| !(char.is_alphanumeric() || matches!(char, '.' | '-' | '+')))
+    })
+}
+
+fn non_empty(section: &str) -> Option<&str> {
+    let section = section.trim();
+    (!section.is_empty()).then_some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::release_notes::changelog::{version_section, Changelog};
+    use crate::release_notes::ReleaseNotesProvider;
+    use crate::test_utils::serve;
+    use crate::types::package_version::PackageVersion;
+    use crate::types::release_notes::ReleaseNotes;
+    use reqwest::Client;
+
+    const CHANGELOG: &str = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.2.30] - 2024-03-01
+- Not this one
+
+## [1.2.3] - 2024-02-01
+### Fixed
+- A bug
+
+## [1.2.3-beta] - 2024-01-01
+- A beta
+";
+
+    #[test]
+    fn test_version_section() {
+        assert_eq!(
+            version_section(CHANGELOG, "1.2.3"),
+            Some("### Fixed\n- A bug")
+        );
+    }
+
+    #[test]
+    fn test_v_prefixed_version_section() {
+        assert_eq!(
+            version_section("# v2.0.0\nNew\n# v1.0.0\nOld", "2.0.0"),
+            Some("New")
+        );
+    }
+
+    #[test]
+    fn test_missing_version_section() {
+        assert_eq!(version_section(CHANGELOG, "1.2"), None);
+        assert_eq!(version_section(CHANGELOG, "2.0.0"), None);
+    }
+
+    #[tokio::test]
+    async fn test_changelog_release_notes() {
+        let url = serve(&[("/CHANGELOG.md", CHANGELOG)]);
+        let changelog = Changelog(url.join("CHANGELOG.md").unwrap());
+        assert_eq!(
+            changelog
+                .release_notes(&Client::new(), &PackageVersion::new("1.2.3").unwrap())
+                .await
+                .unwrap(),
+            ReleaseNotes::new("Fixed\n- A bug").ok()
+        );
+    }
+}
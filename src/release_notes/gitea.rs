@@ -0,0 +1,90 @@
+use crate::release_notes::{encode_segment, get_text, release_tags, ReleaseNotesProvider};
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use color_eyre::eyre::{OptionExt, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+/// The releases of a Gitea or Forgejo repository, such as `https://codeberg.org/owner/repo`
+pub struct Gitea {
+    origin: String,
+    owner: String,
+    repo: String,
+}
+
+#[derive(Deserialize)]
+struct Release {
+    body: Option<String>,
+}
+
+impl Gitea {
+    pub fn new(url: Url) -> Result<Self> {
+        let mut segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty());
+        let (owner, repo) = segments
+            .next()
+            .zip(segments.next())
+            .ok_or_eyre(format!("{url} is not the URL of a Gitea repository"))?;
+        Ok(Self {
+            origin: url.origin().ascii_serialization(),
+            owner: owner.to_owned(),
+            repo: repo.trim_end_matches(".git").to_owned(),
+        })
+    }
+}
+
+impl ReleaseNotesProvider for Gitea {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>> {
+        for tag in release_tags(version) {
+            let url = Url::parse(&format!(
+                "{}/api/v1/repos/{}/{}/releases/tags/{}",
+                self.origin,
+                self.owner,
+                self.repo,
+                encode_segment(&tag)
+            ))?;
+            if let Some(text) = get_text(client, &url).await? {
+                let release = serde_json::from_str::<Release>(&text)?;
+                return Ok(release
+                    .body
+                    .as_deref()
+                    .and_then(ReleaseNotes::from_markdown));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::release_notes::gitea::Gitea;
+    use crate::release_notes::ReleaseNotesProvider;
+    use crate::test_utils::serve;
+    use crate::types::package_version::PackageVersion;
+    use crate::types::release_notes::ReleaseNotes;
+    use reqwest::Client;
+
+    #[tokio::test]
+    async fn test_gitea_release_notes_without_v_prefix() {
+        let url = serve(&[(
+            "/api/v1/repos/owner/repo/releases/tags/1.2.3",
+            r#"{"tag_name":"1.2.3","body":"- Added a feature"}"#,
+        )]);
+        let gitea = Gitea::new(url.join("owner/repo").unwrap()).unwrap();
+        assert_eq!(
+            gitea
+                .release_notes(&Client::new(), &PackageVersion::new("1.2.3").unwrap())
+                .await
+                .unwrap(),
+            ReleaseNotes::new("- Added a feature").ok()
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::release_notes::{encode_segment, get_text, release_tags, ReleaseNotesProvider};
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use color_eyre::eyre::{bail, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+/// The releases of a GitLab project, such as `https://gitlab.com/group/project`
+pub struct GitLab {
+    origin: String,
+    project: String,
+}
+
+#[derive(Deserialize)]
+struct Release {
+    description: Option<String>,
+}
+
+impl GitLab {
+    pub fn new(url: Url) -> Result<Self> {
+        let project = url
+            .path()
+            .split("/-/")
+            .next()
+            .unwrap_or_default()
+            .trim_matches('/')
+            .trim_end_matches(".git");
+        if !project.contains('/') {
+            bail!("{url} is not the URL of a GitLab project");
+        }
+        Ok(Self {
+            origin: url.origin().ascii_serialization(),
+            project: project.to_owned(),
+        })
+    }
+}
+
+impl ReleaseNotesProvider for GitLab {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>> {
+        for tag in release_tags(version) {
+            let url = Url::parse(&format!(
+                "{}/api/v4/projects/{}/releases/{}",
+                self.origin,
+                encode_segment(&self.project),
+                encode_segment(&tag)
+            ))?;
+            if let Some(text) = get_text(client, &url).await? {
+                let release = serde_json::from_str::<Release>(&text)?;
+                return Ok(release
+                    .description
+                    .as_deref()
+                    .and_then(ReleaseNotes::from_markdown));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::release_notes::gitlab::GitLab;
+    use crate::release_notes::ReleaseNotesProvider;
+    use crate::test_utils::serve;
+    use crate::types::package_version::PackageVersion;
+    use crate::types::release_notes::ReleaseNotes;
+    use reqwest::Client;
+
+    #[tokio::test]
+    async fn test_gitlab_release_notes() {
+        let url = serve(&[(
+            "/api/v4/projects/group%2Fproject/releases/v1.2.3",
+            r###"{"tag_name":"v1.2.3","description":"## Changes\n\n- Fixed a **bug**"}"###,
+        )]);
+        let gitlab = GitLab::new(url.join("group/project/-/releases").unwrap()).unwrap();
+        assert_eq!(
+            gitlab
+                .release_notes(&Client::new(), &PackageVersion::new("1.2.3").unwrap())
+                .await
+                .unwrap(),
+            ReleaseNotes::new("Changes\n- Fixed a bug").ok()
+        );
+    }
+}
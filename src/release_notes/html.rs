@@ -0,0 +1,240 @@
+use crate::release_notes::changelog::version_section;
+use crate::release_notes::{get_text, ReleaseNotesProvider};
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use color_eyre::eyre::Result;
+use reqwest::Client;
+use std::borrow::Cow;
+use url::Url;
+
+/// An HTML changelog page with a heading per version
+pub struct HtmlChangelog(pub Url);
+
+impl ReleaseNotesProvider for HtmlChangelog {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>> {
+        let Some(html) = get_text(client, &self.0).await? else {
+            return Ok(None);
+        };
+        let text = to_text(&html);
+        Ok(
+            version_section(&text, &version.to_string()).and_then(|section| {
+                let section = section
+                    .lines()
+                    .map(|line| line.trim_start_matches('#').trim_start())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ReleaseNotes::new(section).ok()
+            }),
+        )
+    }
+}
+
+/// Converts HTML to plain text, keeping headings as markdown headings so that a version's section
+/// can be found
+pub fn to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut skipped_element = None;
+    let mut is_preformatted = false;
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if skipped_element.is_none() {
+            push_text(&mut text, &rest[..start], is_preformatted);
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|char: char| char.is_whitespace() || char == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if let Some(skipped) = &skipped_element {
+            if is_closing && *skipped == name {
+                skipped_element = None;
+            }
+            continue;
+        }
+        match name.as_str() {
+            "script" | "style" | "head" | "noscript" | "template" | "svg" if !is_closing => {
+                skipped_element = Some(name);
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                new_line(&mut text);
+                if !is_closing {
+                    let level = usize::from(name.as_bytes()[1] - b'0');
+                    text.push_str(&"#".repeat(level));
+                    text.push(' ');
+                }
+            }
+            "li" if !is_closing => {
+                new_line(&mut text);
+                text.push_str("- ");
+            }
+            "br" => text.push('\n'),
+            "pre" => {
+                new_line(&mut text);
+                is_preformatted = !is_closing;
+            }
+            "p" | "div" | "ul" | "ol" | "dl" | "dt" | "dd" | "tr" | "table" | "section"
+            | "article" | "header" | "footer" | "blockquote" | "hr" | "li" => {
+                new_line(&mut text);
+            }
+            _ => (),
+        }
+    }
+    if skipped_element.is_none() {
+        push_text(&mut text, rest, is_preformatted);
+    }
+
+    let mut result = String::with_capacity(text.len());
+    for line in text.lines().map(str::trim_end) {
+        if !line.trim().is_empty() || !result.ends_with("\n\n") {
+            result.push_str(if is_list_or_heading(line) {
+                line.trim_start()
+            } else {
+                line
+            });
+            result.push('\n');
+        }
+    }
+    result.trim().to_owned()
+}
+
+fn is_list_or_heading(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("- ") || line.starts_with('#')
+}
+
+fn new_line(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+fn push_text(text: &mut String, html: &str, is_preformatted: bool) {
+    let decoded = decode_entities(html);
+    if is_preformatted {
+        text.push_str(&decoded);
+        return;
+    }
+    for char in decoded.chars() {
+        if char.is_whitespace() {
+            if !text.is_empty() && !text.ends_with(char::is_whitespace) {
+                text.push(' ');
+            }
+        } else {
+            text.push(char);
+        }
+    }
+}
+
+fn decode_entities(html: &str) -> Cow<'_, str> {
+    if !html.contains('&') {
+        return Cow::Borrowed(html);
+    }
+    let mut decoded = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| (&rest[1..=end], end + 2));
+        let character = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => name
+                .strip_prefix("#x")
+                .or_else(|| name.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| name.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        });
+        match (character, entity) {
+            (Some(character), Some((_, length))) => {
+                decoded.push(character);
+                rest = &rest[length..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    Cow::Owned(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::release_notes::html::{to_text, HtmlChangelog};
+    use crate::release_notes::ReleaseNotesProvider;
+    use crate::test_utils::serve;
+    use crate::types::package_version::PackageVersion;
+    use crate::types::release_notes::ReleaseNotes;
+    use reqwest::Client;
+
+    const CHANGELOG: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Changelog</title><style>h2 { color: red; }</style></head>
+<body>
+  <h1>Changelog</h1>
+  <!-- Newest first -->
+  <h2 id="v2">Version 2.0.0</h2>
+  <ul>
+    <li>Added <b>dark mode</b></li>
+    <li>Fixed &lt;input&gt; &amp; &#8220;output&#8221;</li>
+  </ul>
+  <h2 id="v1">Version 1.0.0</h2>
+  <p>Initial release</p>
+  <script>console.log("<h2>2.0.0</h2>")</script>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            to_text(CHANGELOG),
+            "\
+# Changelog
+## Version 2.0.0
+- Added dark mode
+- Fixed <input> & \u{201c}output\u{201d}
+## Version 1.0.0
+Initial release"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_html_release_notes() {
+        let url = serve(&[("/changelog.html", CHANGELOG)]);
+        let changelog = HtmlChangelog(url.join("changelog.html").unwrap());
+        assert_eq!(
+            changelog
+                .release_notes(&Client::new(), &PackageVersion::new("2.0.0").unwrap())
+                .await
+                .unwrap(),
+            ReleaseNotes::new("- Added dark mode\n- Fixed <input> & \u{201c}output\u{201d}").ok()
+        );
+    }
+}
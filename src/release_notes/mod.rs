@@ -0,0 +1,204 @@
+pub mod changelog;
+pub mod gitea;
+pub mod gitlab;
+pub mod html;
+
+use crate::config::Config;
+use crate::release_notes::changelog::Changelog;
+use crate::release_notes::gitea::Gitea;
+use crate::release_notes::gitlab::GitLab;
+use crate::release_notes::html::HtmlChangelog;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::types::release_notes::ReleaseNotes;
+use crate::types::urls::release_notes_url::ReleaseNotesUrl;
+use clap::ValueEnum;
+use color_eyre::eyre::{Result, WrapErr};
+use crossterm::style::Stylize;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use url::Url;
+
+/// Somewhere that the release notes of a version can be read from
+pub trait ReleaseNotesProvider {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>>;
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseNotesKind {
+    /// The releases API of a GitLab project
+    Gitlab,
+    /// The releases API of a Gitea or Forgejo repository, such as on Codeberg
+    Gitea,
+    /// A markdown changelog with a section per version
+    Changelog,
+    /// An HTML page with a section per version
+    Html,
+}
+
+impl ReleaseNotesKind {
+    /// Guesses the kind of a release notes URL from its host and file extension
+    pub fn detect(url: &Url) -> Self {
+        let host = url.host_str().unwrap_or_default();
+        let path = url.path().to_ascii_lowercase();
+        if host == "gitlab.com" || host.starts_with("gitlab.") {
+            Self::Gitlab
+        } else if host == "codeberg.org" || host.starts_with("gitea.") {
+            Self::Gitea
+        } else if path.ends_with(".md") || path.ends_with(".markdown") || path.ends_with(".txt") {
+            Self::Changelog
+        } else {
+            Self::Html
+        }
+    }
+}
+
+/// A release notes URL along with how to read it
+pub struct ReleaseNotesSource {
+    pub kind: ReleaseNotesKind,
+    pub url: Url,
+}
+
+impl ReleaseNotesSource {
+    pub fn new(url: Url, kind: Option<ReleaseNotesKind>) -> Self {
+        Self {
+            kind: kind.unwrap_or_else(|| ReleaseNotesKind::detect(&url)),
+            url,
+        }
+    }
+
+    /// The source from the arguments, or otherwise the one configured for the package
+    pub fn for_package(
+        identifier: &PackageIdentifier,
+        url: Option<Url>,
+        kind: Option<ReleaseNotesKind>,
+    ) -> Option<Self> {
+        let config = Config::get().package(identifier);
+        let url = url.or_else(|| config.and_then(|config| config.release_notes_source.clone()))?;
+        Some(Self::new(
+            url,
+            kind.or_else(|| config.and_then(|config| config.release_notes_kind)),
+        ))
+    }
+
+    /// Gets the release notes of a version, warning instead of failing if the source doesn't have
+    /// any or couldn't be read as release notes are optional
+    pub async fn get(&self, client: &Client, version: &PackageVersion) -> Option<ReleaseNotes> {
+        match self.release_notes(client, version).await {
+            Ok(Some(release_notes)) => return Some(release_notes),
+            Ok(None) => println!(
+                "{} No release notes for {version} were found at {}",
+                "Warning:".yellow(),
+                self.url
+            ),
+            Err(error) => println!(
+                "{} Failed to read release notes from {}: {error:#}",
+                "Warning:".yellow(),
+                self.url
+            ),
+        }
+        None
+    }
+
+    /// The URL to link to when the release notes come from this source
+    pub fn release_notes_url(&self) -> Option<ReleaseNotesUrl> {
+        ReleaseNotesUrl::from_str(self.url.as_str()).ok()
+    }
+}
+
+impl ReleaseNotesProvider for ReleaseNotesSource {
+    async fn release_notes(
+        &self,
+        client: &Client,
+        version: &PackageVersion,
+    ) -> Result<Option<ReleaseNotes>> {
+        let url = self.url.clone();
+        match self.kind {
+            ReleaseNotesKind::Gitlab => GitLab::new(url)?.release_notes(client, version).await,
+            ReleaseNotesKind::Gitea => Gitea::new(url)?.release_notes(client, version).await,
+            ReleaseNotesKind::Changelog => Changelog(url).release_notes(client, version).await,
+            ReleaseNotesKind::Html => HtmlChangelog(url).release_notes(client, version).await,
+        }
+    }
+}
+
+/// Characters that have to be encoded within a single segment of a URL's path
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'?');
+
+fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// The tags that a release of a version is likely to have
+fn release_tags(version: &PackageVersion) -> [String; 2] {
+    [format!("v{version}"), version.to_string()]
+}
+
+/// Gets the text at a URL, or None if there's nothing there
+async fn get_text(client: &Client, url: &Url) -> Result<Option<String>> {
+    let response = client
+        .get(url.as_str())
+        .send()
+        .await
+        .wrap_err_with(|| format!("Failed to get release notes from {url}"))?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    Ok(Some(
+        response
+            .error_for_status()
+            .wrap_err_with(|| format!("Failed to get release notes from {url}"))?
+            .text()
+            .await?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::release_notes::{ReleaseNotesKind, ReleaseNotesSource};
+    use crate::test_utils::serve;
+    use crate::types::package_version::PackageVersion;
+    use reqwest::Client;
+    use url::Url;
+
+    #[test]
+    fn test_detect_kind() {
+        for (url, kind) in [
+            ("https://gitlab.com/group/project", ReleaseNotesKind::Gitlab),
+            ("https://codeberg.org/owner/repo", ReleaseNotesKind::Gitea),
+            (
+                "https://raw.githubusercontent.com/owner/repo/HEAD/CHANGELOG.md",
+                ReleaseNotesKind::Changelog,
+            ),
+            ("https://www.example.com/changelog", ReleaseNotesKind::Html),
+        ] {
+            assert_eq!(ReleaseNotesKind::detect(&Url::parse(url).unwrap()), kind);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_source_has_no_release_notes() {
+        let url = serve(&[("/api/v1/repos/owner/repo/releases/tags/v1.2.3", "Not JSON")]);
+        let source = ReleaseNotesSource::new(
+            url.join("owner/repo").unwrap(),
+            Some(ReleaseNotesKind::Gitea),
+        );
+        assert!(source
+            .get(&Client::new(), &PackageVersion::new("1.2.3").unwrap())
+            .await
+            .is_none());
+    }
+}
@@ -0,0 +1,406 @@
+use crate::github::github_client::{GitHubFile, Manifests};
+use crate::github::utils::{get_branch_name, get_package_path};
+use crate::manifest::read_manifest_files;
+use crate::storage::{Changes, Storage, Submission};
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::process::Command;
+
+/// A local clone of winget-pkgs where changes are committed to a new git branch
+pub struct LocalRepository {
+    path: PathBuf,
+}
+
+impl LocalRepository {
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.join("manifests").is_dir() {
+            bail!(
+                "{} is not a clone of winget-pkgs as it has no manifests directory",
+                path.display()
+            );
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+        })
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String> {
+        git_in(&self.path, args).await
+    }
+
+    /// The commit that new branches start from, which is the default branch of the upstream or
+    /// origin remote if there is one
+    async fn base_commit(&self) -> Result<String> {
+        for remote_head in ["refs/remotes/upstream/HEAD", "refs/remotes/origin/HEAD"] {
+            if let Ok(commit) = self
+                .git(&[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("{remote_head}^{{commit}}"),
+                ])
+                .await
+            {
+                return Ok(commit);
+            }
+        }
+        self.git(&["rev-parse", "HEAD"]).await
+    }
+}
+
+async fn git_in(directory: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .await
+        .wrap_err("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// Applies the changes in a worktree and commits only them
+async fn commit_in(worktree: &Path, changes: &Changes<'_>) -> Result<()> {
+    if let Some(directory) = changes.deleted_directory {
+        git_in(worktree, &["rm", "-r", "--quiet", "--", directory]).await?;
+    }
+    if !changes.deleted_files.is_empty() {
+        let mut args = vec!["rm", "--quiet", "--"];
+        args.extend(changes.deleted_files.iter().map(String::as_str));
+        git_in(worktree, &args).await?;
+    }
+    for (path, content) in changes.additions {
+        let path = worktree.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, content).await?;
+    }
+    if !changes.additions.is_empty() {
+        let mut args = vec!["add", "--"];
+        args.extend(changes.additions.iter().map(|(path, _)| path.as_str()));
+        git_in(worktree, &args).await?;
+    }
+    git_in(
+        worktree,
+        &["commit", "--quiet", "-m", changes.title, "-m", changes.body],
+    )
+    .await?;
+    Ok(())
+}
+
+impl Storage for LocalRepository {
+    async fn get_versions(
+        &self,
+        identifier: &PackageIdentifier,
+    ) -> Result<BTreeSet<PackageVersion>> {
+        let path = get_package_path(identifier, None);
+        let mut entries = fs::read_dir(self.path.join(&path))
+            .await
+            .wrap_err_with(|| format!("Failed to read the directory content of {path}"))?;
+        let mut versions = BTreeSet::new();
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry.file_type().await?.is_dir() {
+                continue;
+            }
+            // Directories that contain other directories are sub-packages rather than versions
+            let mut version_entries = fs::read_dir(entry.path()).await?;
+            let mut is_version = true;
+            while let Some(version_entry) = version_entries.next_entry().await? {
+                if version_entry.file_type().await?.is_dir() {
+                    is_version = false;
+                    break;
+                }
+            }
+            if let (true, Ok(version)) = (
+                is_version,
+                PackageVersion::new(&entry.file_name().to_string_lossy()),
+            ) {
+                versions.insert(version);
+            }
+        }
+
+        if versions.is_empty() {
+            bail!("No files were found for {path}")
+        }
+
+        Ok(versions)
+    }
+
+    async fn get_manifests(
+        &self,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+    ) -> Result<Manifests> {
+        let full_package_path = get_package_path(identifier, Some(version));
+        let content = read_manifest_files(&self.path.join(&full_package_path))?
+            .into_iter()
+            .map(|(name, text)| GitHubFile { name, text })
+            .collect::<Vec<_>>();
+
+        Manifests::from_files(identifier, &full_package_path, content)
+    }
+
+    async fn commit_changes(&self, changes: Changes<'_>) -> Result<Submission> {
+        // The changes are committed in a separate worktree so that the checked out branch, its
+        // staged changes and its working tree are left untouched
+        let branch_name = get_branch_name(changes.identifier, changes.version);
+        let base_commit = self.base_commit().await?;
+        let worktree = tempfile::Builder::new().prefix("komac-").tempdir()?;
+        let worktree_path = worktree.path().to_string_lossy();
+        self.git(&[
+            "worktree",
+            "add",
+            "--quiet",
+            "-b",
+            &branch_name,
+            &worktree_path,
+            &base_commit,
+        ])
+        .await?;
+
+        let result = commit_in(worktree.path(), &changes).await;
+        let removed = self
+            .git(&["worktree", "remove", "--force", &worktree_path])
+            .await;
+        if let Err(error) = result {
+            let _ = self.git(&["branch", "--quiet", "-D", &branch_name]).await;
+            return Err(error);
+        }
+        removed?;
+        Ok(Submission::Branch(branch_name))
+    }
+}
+
+impl Display for LocalRepository {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.path.display().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::local::LocalRepository;
+    use crate::storage::{Changes, Storage, Submission};
+    use crate::types::package_identifier::PackageIdentifier;
+    use crate::types::package_version::PackageVersion;
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    async fn init_repository() -> (tempfile::TempDir, LocalRepository) {
+        let directory = tempfile::tempdir().unwrap();
+        let package = directory.path().join("manifests/p/Package/Identifier");
+        fs::create_dir_all(package.join("1.0.0")).unwrap();
+        fs::create_dir_all(package.join("Sub/1.0.0")).unwrap();
+        fs::write(package.join("1.0.0/Package.Identifier.yaml"), "").unwrap();
+        fs::write(package.join("Sub/1.0.0/Package.Identifier.Sub.yaml"), "").unwrap();
+        let repository = LocalRepository::open(directory.path()).unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &["config", "user.name", "Komac"],
+            &["config", "user.email", "komac@example.com"],
+            &["add", "."],
+            &["commit", "--quiet", "-m", "Initial commit"],
+        ] {
+            repository.git(args).await.unwrap();
+        }
+        (directory, repository)
+    }
+
+    #[tokio::test]
+    async fn test_get_versions_skips_sub_packages() {
+        let (_directory, repository) = init_repository().await;
+        let identifier = PackageIdentifier::parse("Package.Identifier").unwrap();
+        assert_eq!(
+            BTreeSet::from([PackageVersion::new("1.0.0").unwrap()]),
+            repository.get_versions(&identifier).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_changes_to_new_branch() {
+        let (directory, repository) = init_repository().await;
+        let identifier = PackageIdentifier::parse("Package.Identifier").unwrap();
+        let version = PackageVersion::new("1.1.0").unwrap();
+        let path = "manifests/p/Package/Identifier/1.1.0/Package.Identifier.yaml";
+        let submission = repository
+            .commit_changes(Changes {
+                identifier: &identifier,
+                version: &version,
+                title: "New version: Package.Identifier version 1.1.0",
+                body: "Body",
+                additions: &[(path.to_owned(), "content".to_owned())],
+                deleted_directory: None,
+                deleted_files: &[],
+            })
+            .await
+            .unwrap();
+
+        let Submission::Branch(branch) = submission else {
+            panic!("Expected the changes to be committed to a branch");
+        };
+        // The checkout is left on its original branch with the new files only in the new branch
+        assert!(!directory.path().join(path).exists());
+        assert_eq!(
+            "content",
+            repository
+                .git(&["show", &format!("{branch}:{path}")])
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            "New version: Package.Identifier version 1.1.0",
+            repository
+                .git(&["log", "-1", "--format=%s", &branch])
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_deleted_files() {
+        let (directory, repository) = init_repository().await;
+        let identifier = PackageIdentifier::parse("Package.Identifier").unwrap();
+        let version = PackageVersion::new("1.0.0").unwrap();
+        let path = "manifests/p/Package/Identifier/1.0.0/Package.Identifier.yaml";
+        let Submission::Branch(branch) = repository
+            .commit_changes(Changes {
+                identifier: &identifier,
+                version: &version,
+                title: "Remove locale: Package.Identifier version 1.0.0 (de-DE)",
+                body: "Body",
+                additions: &[],
+                deleted_directory: None,
+                deleted_files: &[path.to_owned()],
+            })
+            .await
+            .unwrap()
+        else {
+            panic!("Expected the changes to be committed to a branch");
+        };
+
+        assert!(directory.path().join(path).exists());
+        assert!(repository
+            .git(&["cat-file", "-e", &format!("{branch}:{path}")])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_commit_from_remote_head_without_staged_changes() {
+        let (directory, repository) = init_repository().await;
+        let upstream = repository.git(&["rev-parse", "HEAD"]).await.unwrap();
+        for args in [
+            &["update-ref", "refs/remotes/upstream/master", &upstream][..],
+            &[
+                "symbolic-ref",
+                "refs/remotes/upstream/HEAD",
+                "refs/remotes/upstream/master",
+            ],
+            &["commit", "--quiet", "--allow-empty", "-m", "Local work"],
+        ] {
+            repository.git(args).await.unwrap();
+        }
+        fs::write(directory.path().join("staged.txt"), "").unwrap();
+        repository.git(&["add", "staged.txt"]).await.unwrap();
+        let previous_head = repository.git(&["rev-parse", "HEAD"]).await.unwrap();
+
+        let identifier = PackageIdentifier::parse("Package.Identifier").unwrap();
+        let version = PackageVersion::new("1.1.0").unwrap();
+        let path = "manifests/p/Package/Identifier/1.1.0/Package.Identifier.yaml";
+        let Submission::Branch(branch) = repository
+            .commit_changes(Changes {
+                identifier: &identifier,
+                version: &version,
+                title: "New version: Package.Identifier version 1.1.0",
+                body: "Body",
+                additions: &[(path.to_owned(), "content".to_owned())],
+                deleted_directory: None,
+                deleted_files: &[],
+            })
+            .await
+            .unwrap()
+        else {
+            panic!("Expected the changes to be committed to a branch");
+        };
+
+        assert_eq!(
+            upstream,
+            repository
+                .git(&["rev-parse", &format!("{branch}^")])
+                .await
+                .unwrap()
+        );
+        assert!(repository
+            .git(&["cat-file", "-e", &format!("{branch}:staged.txt")])
+            .await
+            .is_err());
+        assert_eq!(
+            previous_head,
+            repository.git(&["rev-parse", "HEAD"]).await.unwrap()
+        );
+        assert_eq!(
+            "staged.txt",
+            repository
+                .git(&["diff", "--cached", "--name-only"])
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_commit_is_cleaned_up() {
+        let (_directory, repository) = init_repository().await;
+        let previous_head = repository.git(&["rev-parse", "HEAD"]).await.unwrap();
+        let identifier = PackageIdentifier::parse("Package.Identifier").unwrap();
+        let version = PackageVersion::new("1.0.0").unwrap();
+        let result = repository
+            .commit_changes(Changes {
+                identifier: &identifier,
+                version: &version,
+                title: "Remove locale: Package.Identifier version 1.0.0 (de-DE)",
+                body: "Body",
+                additions: &[],
+                deleted_directory: None,
+                deleted_files: &["manifests/p/Package/Identifier/1.0.0/Missing.yaml".to_owned()],
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            previous_head,
+            repository.git(&["rev-parse", "HEAD"]).await.unwrap()
+        );
+        assert_eq!(
+            1,
+            repository
+                .git(&["branch", "--list"])
+                .await
+                .unwrap()
+                .lines()
+                .count()
+        );
+        assert_eq!(
+            1,
+            repository
+                .git(&["worktree", "list", "--porcelain"])
+                .await
+                .unwrap()
+                .lines()
+                .filter(|line| line.starts_with("worktree "))
+                .count()
+        );
+    }
+}
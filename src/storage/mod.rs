@@ -0,0 +1,72 @@
+pub mod local;
+
+use crate::github::github_client::Manifests;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use color_eyre::Result;
+use crossterm::style::Stylize;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// A copy of winget-pkgs that manifests are read from and changes are committed to
+pub trait Storage: Display {
+    async fn get_versions(
+        &self,
+        identifier: &PackageIdentifier,
+    ) -> Result<BTreeSet<PackageVersion>>;
+
+    async fn get_manifests(
+        &self,
+        identifier: &PackageIdentifier,
+        version: &PackageVersion,
+    ) -> Result<Manifests>;
+
+    /// Commits the changes to a new branch
+    async fn commit_changes(&self, changes: Changes<'_>) -> Result<Submission>;
+}
+
+pub struct Changes<'a> {
+    pub identifier: &'a PackageIdentifier,
+    pub version: &'a PackageVersion,
+    pub title: &'a str,
+    pub body: &'a str,
+    /// Paths of files to add or replace along with their content
+    pub additions: &'a [(String, String)],
+    /// Path of a directory whose files should all be deleted
+    pub deleted_directory: Option<&'a str>,
+    /// Paths of individual files to delete
+    pub deleted_files: &'a [String],
+}
+
+pub enum Submission {
+    PullRequest(url::Url),
+    Branch(String),
+}
+
+impl Submission {
+    pub fn print(&self, storage: &impl Storage) {
+        match self {
+            Self::PullRequest(url) => {
+                println!(
+                    "{} created a pull request to {storage}",
+                    "Successfully".green()
+                );
+                println!("{}", url.as_str());
+            }
+            Self::Branch(branch) => println!(
+                "{} committed the changes to {} in {storage}",
+                "Successfully".green(),
+                branch.as_str().blue()
+            ),
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PullRequest(url) => url.fmt(f),
+            Self::Branch(branch) => branch.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Helpers shared by the tests of several modules
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use url::Url;
+
+/// Serves the given bodies at their paths on a local port, responding with 404 to any other path
+pub fn serve(routes: &'static [(&'static str, &'static str)]) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buffer = [0; 4096];
+            let length = stream.read(&mut buffer).unwrap_or_default();
+            let request = String::from_utf8_lossy(&buffer[..length]);
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let response = match routes.iter().find(|(route, _)| *route == path) {
+                Some((_, body)) => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                ),
+                None => String::from(
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                ),
+            };
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    Url::parse(&format!("http://{address}/")).unwrap()
+}
@@ -2,6 +2,7 @@ use crate::exe::vs_version_info::VSVersionInfo;
 use crate::file_analyser::{APPX, APPX_BUNDLE, EXE, MSI, MSIX, MSIX_BUNDLE, ZIP};
 use crate::manifests::installer_manifest::NestedInstallerType;
 use crate::msi::Msi;
+use crate::nsis::find_first_header;
 use color_eyre::eyre::{bail, OptionExt, Result};
 use object::pe::{RT_MANIFEST, RT_RCDATA};
 use object::read::pe::{ImageNtHeaders, PeFile, ResourceDirectoryEntryData};
@@ -57,6 +58,8 @@ impl InstallerType {
                     () if pe.and_then(|pe| Self::is_burn(pe).ok()).unwrap_or(false) => {
                         Ok(Self::Burn)
                     }
+                    // Installers built without the default manifest still have NSIS's header
+                    () if pe.is_some() && find_first_header(data).is_ok() => Ok(Self::Nullsoft),
                     () => Ok(Self::Exe),
                 };
             }
@@ -123,7 +126,8 @@ impl InstallerType {
         Ok(assembly.assembly_identity.name == NULLSOFT_MANIFEST_NAME)
     }
 
-    /// Checks the String File Info of the exe for whether its comment states that it was built with Inno Setup
+    /// Checks the String File Info of the exe for whether its comment states that it was built with Inno Setup,
+    /// falling back to whether it has Inno Setup's offset table resource
     fn is_inno<'data, Pe, R>(pe: &PeFile<'data, Pe, R>, data: &[u8]) -> bool
     where
         Pe: ImageNtHeaders,
@@ -146,6 +150,7 @@ impl InstallerType {
                     .as_deref()
                     == Some(INNO_COMMENT)
             })
+            || crate::inno::setup_ldr_offset_table(pe).is_ok()
     }
 
     fn is_burn<'data, Pe, R>(pe: &PeFile<'data, Pe, R>) -> Result<bool>
@@ -153,6 +158,13 @@ impl InstallerType {
         Pe: ImageNtHeaders,
         R: ReadRef<'data>,
     {
+        if pe
+            .section_table()
+            .iter()
+            .any(|section| &section.name == b".wixburn")
+        {
+            return Ok(true);
+        }
         let resource_directory = pe
             .data_directories()
             .resource_directory(pe.data(), &pe.section_table())?
@@ -5,7 +5,7 @@ use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::str::FromStr;
 use versions::Versioning;
 
-#[derive(Clone, Default, Deref, Display, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Default, Deref, Display, Eq, Ord, PartialEq, PartialOrd)]
 pub struct PackageVersion(Versioning);
 
 impl PackageVersion {
@@ -0,0 +1,33 @@
+use nutype::nutype;
+use uuid::Uuid;
+
+#[nutype(
+    validate(len_char_min = 1, len_char_max = 255),
+    derive(
+        Clone,
+        FromStr,
+        Debug,
+        Deref,
+        Display,
+        Deserialize,
+        Serialize,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash
+    )
+)]
+pub struct ProductCode(String);
+
+impl ProductCode {
+    /// Formats an MSI product code as an uppercase GUID in braces
+    pub fn from_uuid(uuid: &Uuid) -> Self {
+        Self::new(
+            uuid.as_braced()
+                .encode_upper(&mut Uuid::encode_buffer())
+                .to_owned(),
+        )
+        .unwrap()
+    }
+}
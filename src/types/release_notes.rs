@@ -1,3 +1,4 @@
+use crate::prompts::prompt::OptionalPrompt;
 use nutype::nutype;
 use pulldown_cmark::Event::{Code, End, Start, Text};
 use pulldown_cmark::{Event, Options, Parser, Tag};
@@ -12,8 +13,24 @@ use std::num::NonZeroU32;
 )]
 pub struct ReleaseNotes(String);
 
+impl OptionalPrompt for ReleaseNotes {
+    const MESSAGE: &'static str = "Release notes:";
+    const HELP_MESSAGE: Option<&'static str> = None;
+    const PLACEHOLDER: Option<&'static str> = None;
+}
+
 impl ReleaseNotes {
+    /// Formats the markdown body of a GitHub release, shortening links to the repository's issues
     pub fn format(body: &str, owner: &str, repo: &str) -> Option<Self> {
+        Self::format_markdown(body, Some((owner, repo)))
+    }
+
+    /// Formats markdown from somewhere other than a GitHub release
+    pub fn from_markdown(body: &str) -> Option<Self> {
+        Self::format_markdown(body, None)
+    }
+
+    fn format_markdown(body: &str, repository: Option<(&str, &str)>) -> Option<Self> {
         let mut options = Options::empty();
         options.insert(Options::ENABLE_STRIKETHROUGH);
 
@@ -64,7 +81,7 @@ impl ReleaseNotes {
                         {
                             if let "pull" | "issues" = issue_type {
                                 if issue_number.parse::<NonZeroU32>().is_ok() {
-                                    if repo_owner != owner || repo_name != repo {
+                                    if repository != Some((repo_owner, repo_name)) {
                                         result.push_str(repo_owner);
                                         result.push('/');
                                         result.push_str(repo_name);
@@ -1,4 +1,4 @@
-use crate::prompts::prompt::RequiredPrompt;
+use crate::prompts::prompt::{OptionalPrompt, RequiredPrompt};
 use nutype::nutype;
 
 #[nutype(
@@ -13,3 +13,10 @@ impl RequiredPrompt for ShortDescription {
     const HELP_MESSAGE: Option<&'static str> = None;
     const PLACEHOLDER: Option<&'static str> = None;
 }
+
+/// Additional locales don't need a short description as the default locale's is used instead
+impl OptionalPrompt for ShortDescription {
+    const MESSAGE: &'static str = "Short description:";
+    const HELP_MESSAGE: Option<&'static str> = None;
+    const PLACEHOLDER: Option<&'static str> = None;
+}
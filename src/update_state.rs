@@ -15,6 +15,10 @@ pub enum UpdateState {
     UpdateVersion,
     #[strum(serialize = "Remove version")]
     RemoveVersion,
+    #[strum(serialize = "Add locale")]
+    AddLocale,
+    #[strum(serialize = "Remove locale")]
+    RemoveLocale,
 }
 impl UpdateState {
     pub fn get(
@@ -0,0 +1,748 @@
+use crate::manifest::{read_manifest_files, MANIFEST_VERSION};
+use crate::manifests::default_locale_manifest::DefaultLocaleManifest;
+use crate::manifests::installer_manifest::{Installer, InstallerManifest};
+use crate::manifests::locale_manifest::LocaleManifest;
+use crate::manifests::version_manifest::VersionManifest;
+use crate::prompts::list_prompt::ListPrompt;
+use crate::types::command::Command;
+use crate::types::file_extension::FileExtension;
+use crate::types::installer_success_code::InstallerSuccessCode;
+use crate::types::manifest_type::ManifestType;
+use crate::types::package_identifier::PackageIdentifier;
+use crate::types::package_version::PackageVersion;
+use crate::types::protocol::Protocol;
+use crate::types::tag::Tag;
+use color_eyre::eyre::Result;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const YAML: &str = "yaml";
+const MAX_INSTALLERS: usize = 1024;
+const SHA_256_LENGTH: usize = 64;
+
+/// A single schema violation found in a manifest file
+#[derive(Debug, Eq, PartialEq)]
+pub struct Violation {
+    pub file: String,
+    pub path: String,
+    pub reason: String,
+}
+
+impl Violation {
+    fn new(file: &str, path: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            file: file.to_owned(),
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}: {}", self.file, self.reason)
+        } else {
+            write!(f, "{}: {}: {}", self.file, self.path, self.reason)
+        }
+    }
+}
+
+enum ParsedManifest {
+    Installer(InstallerManifest),
+    DefaultLocale(DefaultLocaleManifest),
+    Locale(LocaleManifest),
+    Version(VersionManifest),
+}
+
+/// Validates every YAML manifest in a directory against the rules of the current manifest schema,
+/// returning all the violations that were found rather than stopping at the first one
+pub fn validate_directory(directory: &Path) -> Result<Vec<Violation>> {
+    Ok(validate_manifests(&read_manifest_files(directory)?))
+}
+
+/// Validates a set of manifests given as pairs of file names and their YAML content
+pub fn validate_manifests<N: AsRef<str>, C: AsRef<str>>(files: &[(N, C)]) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    if files.is_empty() {
+        violations.push(Violation::new(".", "", "No manifest files were found"));
+        return violations;
+    }
+
+    let manifests = files
+        .iter()
+        .filter_map(|(name, content)| {
+            let name = name.as_ref();
+            parse_manifest(name, content.as_ref(), &mut violations).map(|manifest| (name, manifest))
+        })
+        .collect::<Vec<_>>();
+
+    for (name, manifest) in &manifests {
+        check_manifest(name, manifest, &mut violations);
+    }
+
+    check_manifest_set(&manifests, &mut violations);
+
+    violations
+}
+
+fn parse_manifest(
+    file: &str,
+    content: &str,
+    violations: &mut Vec<Violation>,
+) -> Option<ParsedManifest> {
+    let value = match serde_yaml::from_str::<Value>(content) {
+        Ok(value) => value,
+        Err(error) => {
+            violations.push(Violation::new(file, "", format!("Invalid YAML: {error}")));
+            return None;
+        }
+    };
+
+    let manifest_type = match value.get("ManifestType") {
+        Some(manifest_type) => {
+            match serde_yaml::from_value::<ManifestType>(manifest_type.clone()) {
+                Ok(manifest_type) => manifest_type,
+                Err(error) => {
+                    violations.push(Violation::new(file, "ManifestType", error.to_string()));
+                    return None;
+                }
+            }
+        }
+        None => {
+            violations.push(Violation::new(
+                file,
+                "",
+                "missing field `ManifestType`, so the kind of manifest could not be determined",
+            ));
+            return None;
+        }
+    };
+
+    match manifest_type {
+        ManifestType::Installer => {
+            deserialize_collecting(file, value, violations).map(ParsedManifest::Installer)
+        }
+        ManifestType::DefaultLocale => {
+            deserialize_collecting(file, value, violations).map(ParsedManifest::DefaultLocale)
+        }
+        ManifestType::Locale => {
+            deserialize_collecting(file, value, violations).map(ParsedManifest::Locale)
+        }
+        ManifestType::Version => {
+            deserialize_collecting(file, value, violations).map(ParsedManifest::Version)
+        }
+    }
+}
+
+/// Deserializes a manifest, recording each invalid or unknown key and removing it before trying
+/// again so that every violation in the file is reported
+fn deserialize_collecting<T: DeserializeOwned>(
+    file: &str,
+    mut value: Value,
+    violations: &mut Vec<Violation>,
+) -> Option<T> {
+    let mut unknown_keys = BTreeSet::new();
+    let mut removed_paths = HashSet::new();
+
+    let result = loop {
+        let mut on_ignored = |path: serde_ignored::Path| {
+            unknown_keys.insert(format_ignored_path(&path));
+        };
+        let result = serde_path_to_error::deserialize::<_, T>(serde_ignored::Deserializer::new(
+            value.clone(),
+            &mut on_ignored,
+        ));
+
+        match result {
+            Ok(manifest) => break Some(manifest),
+            Err(error) => {
+                let path = format_error_path(error.path());
+                let reason = error.inner().to_string();
+                if let Some(field) = missing_field(&reason) {
+                    // A required field that was removed for being invalid has already been
+                    // reported, so a valid placeholder is put in its place to carry on checking
+                    if removed_paths.contains(&join_path(&path, field)) {
+                        if let Some(placeholder) = placeholder(field) {
+                            if insert_at_path(&mut value, error.path(), field, placeholder) {
+                                continue;
+                            }
+                        }
+                    } else {
+                        violations.push(Violation::new(file, path, reason));
+                    }
+                    break None;
+                }
+                violations.push(Violation::new(file, path.clone(), reason));
+                if !remove_at_path(&mut value, error.path()) || !removed_paths.insert(path) {
+                    break None;
+                }
+            }
+        }
+    };
+
+    violations.extend(
+        unknown_keys
+            .into_iter()
+            .map(|path| Violation::new(file, path, "Unknown key")),
+    );
+
+    result
+}
+
+fn check_manifest(file: &str, manifest: &ParsedManifest, violations: &mut Vec<Violation>) {
+    let (identifier, manifest_version) = match manifest {
+        ParsedManifest::Installer(manifest) => {
+            (&manifest.package_identifier, &manifest.manifest_version)
+        }
+        ParsedManifest::DefaultLocale(manifest) => {
+            (&manifest.package_identifier, &manifest.manifest_version)
+        }
+        ParsedManifest::Locale(manifest) => {
+            (&manifest.package_identifier, &manifest.manifest_version)
+        }
+        ParsedManifest::Version(manifest) => {
+            (&manifest.package_identifier, &manifest.manifest_version)
+        }
+    };
+
+    if let Err(error) = PackageIdentifier::parse(identifier) {
+        violations.push(Violation::new(file, "PackageIdentifier", error.to_string()));
+    }
+
+    if manifest_version.to_string() != MANIFEST_VERSION {
+        violations.push(Violation::new(
+            file,
+            "ManifestVersion",
+            format!("Manifest version must be {MANIFEST_VERSION}, found {manifest_version}"),
+        ));
+    }
+
+    match manifest {
+        ParsedManifest::Installer(manifest) => check_installer_manifest(file, manifest, violations),
+        ParsedManifest::DefaultLocale(manifest) => {
+            check_item_count(
+                file,
+                "Tags",
+                manifest.tags.as_ref(),
+                Tag::MAX_ITEMS,
+                violations,
+            );
+        }
+        ParsedManifest::Locale(manifest) => {
+            check_item_count(
+                file,
+                "Tags",
+                manifest.tags.as_ref(),
+                Tag::MAX_ITEMS,
+                violations,
+            );
+        }
+        ParsedManifest::Version(_) => {}
+    }
+}
+
+fn check_installer_manifest(
+    file: &str,
+    manifest: &InstallerManifest,
+    violations: &mut Vec<Violation>,
+) {
+    if manifest.installers.is_empty() {
+        violations.push(Violation::new(
+            file,
+            "Installers",
+            "At least one installer is required",
+        ));
+    } else if manifest.installers.len() > MAX_INSTALLERS {
+        violations.push(Violation::new(
+            file,
+            "Installers",
+            format!("There is a maximum of {MAX_INSTALLERS} installers"),
+        ));
+    }
+
+    check_installer_lists(file, "", &root_installer(manifest), violations);
+
+    for (index, installer) in manifest.installers.iter().enumerate() {
+        let path = format!("Installers[{index}]");
+
+        if manifest.installer_type.is_none() && installer.installer_type.is_none() {
+            violations.push(Violation::new(
+                file,
+                join_path(&path, "InstallerType"),
+                "An installer type must be set either at the root or on the installer",
+            ));
+        }
+
+        if !is_sha_256(&installer.installer_sha_256) {
+            violations.push(Violation::new(
+                file,
+                join_path(&path, "InstallerSha256"),
+                "Must be a 64 character hexadecimal SHA-256 hash",
+            ));
+        }
+
+        if let Some(signature_sha_256) = &installer.signature_sha_256 {
+            if !is_sha_256(signature_sha_256) {
+                violations.push(Violation::new(
+                    file,
+                    join_path(&path, "SignatureSha256"),
+                    "Must be a 64 character hexadecimal SHA-256 hash",
+                ));
+            }
+        }
+
+        check_installer_lists(file, &path, installer, violations);
+    }
+}
+
+/// Builds an installer from the root-level keys so that they share the per-installer checks
+fn root_installer(manifest: &InstallerManifest) -> Installer {
+    Installer {
+        installer_success_codes: manifest.installer_success_codes.clone(),
+        commands: manifest.commands.clone(),
+        protocols: manifest.protocols.clone(),
+        file_extensions: manifest.file_extensions.clone(),
+        ..Installer::default()
+    }
+}
+
+fn check_installer_lists(
+    file: &str,
+    parent: &str,
+    installer: &Installer,
+    violations: &mut Vec<Violation>,
+) {
+    check_item_count(
+        file,
+        &join_path(parent, "InstallerSuccessCodes"),
+        installer.installer_success_codes.as_ref(),
+        InstallerSuccessCode::MAX_ITEMS,
+        violations,
+    );
+    check_item_count(
+        file,
+        &join_path(parent, "Commands"),
+        installer.commands.as_ref(),
+        Command::MAX_ITEMS,
+        violations,
+    );
+    check_item_count(
+        file,
+        &join_path(parent, "Protocols"),
+        installer.protocols.as_ref(),
+        Protocol::MAX_ITEMS,
+        violations,
+    );
+    check_item_count(
+        file,
+        &join_path(parent, "FileExtensions"),
+        installer.file_extensions.as_ref(),
+        FileExtension::MAX_ITEMS,
+        violations,
+    );
+}
+
+fn check_item_count<T>(
+    file: &str,
+    path: &str,
+    items: Option<&BTreeSet<T>>,
+    max_items: u16,
+    violations: &mut Vec<Violation>,
+) {
+    if let Some(items) = items {
+        if items.len() > max_items as usize {
+            violations.push(Violation::new(
+                file,
+                path,
+                format!(
+                    "There is a maximum of {max_items} items. There were {} provided",
+                    items.len()
+                ),
+            ));
+        }
+    }
+}
+
+/// Checks the rules that span multiple files, such as every manifest describing the same package
+fn check_manifest_set(manifests: &[(&str, ParsedManifest)], violations: &mut Vec<Violation>) {
+    let version_manifests = manifests
+        .iter()
+        .filter_map(|(name, manifest)| match manifest {
+            ParsedManifest::Version(version_manifest) => Some((*name, version_manifest)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let installer_count = manifests
+        .iter()
+        .filter(|(_, manifest)| matches!(manifest, ParsedManifest::Installer(_)))
+        .count();
+    if installer_count > 1 {
+        for (name, manifest) in manifests {
+            if let ParsedManifest::Installer(_) = manifest {
+                violations.push(Violation::new(
+                    name,
+                    "",
+                    "Only one installer manifest is allowed",
+                ));
+            }
+        }
+    }
+
+    let [(version_file, version_manifest)] = version_manifests.as_slice() else {
+        let reason = if version_manifests.is_empty() {
+            "No version manifest was found"
+        } else {
+            "Only one version manifest is allowed"
+        };
+        violations.push(Violation::new(".", "", reason));
+        return;
+    };
+
+    let identifier = &version_manifest.package_identifier;
+    let version = &version_manifest.package_version;
+
+    if *version_file != format!("{identifier}.{YAML}") {
+        violations.push(Violation::new(
+            version_file,
+            "",
+            format!("Version manifest file name must be {identifier}.{YAML}"),
+        ));
+    }
+
+    if installer_count == 0 {
+        violations.push(Violation::new(".", "", "No installer manifest was found"));
+    }
+
+    let mut default_locale_found = false;
+    let mut seen_locales = HashSet::new();
+
+    for (name, manifest) in manifests {
+        let (package_identifier, package_version) = match manifest {
+            ParsedManifest::Installer(manifest) => {
+                check_file_name(name, &format!("{identifier}.installer.{YAML}"), violations);
+                (&manifest.package_identifier, &manifest.package_version)
+            }
+            ParsedManifest::DefaultLocale(manifest) => {
+                if manifest.package_locale == version_manifest.default_locale {
+                    default_locale_found = true;
+                } else {
+                    violations.push(Violation::new(
+                        name,
+                        "PackageLocale",
+                        format!(
+                            "Default locale manifest must have the locale {} that is set in {version_file}",
+                            version_manifest.default_locale
+                        ),
+                    ));
+                }
+                if !seen_locales.insert(manifest.package_locale.to_string()) {
+                    violations.push(Violation::new(
+                        name,
+                        "PackageLocale",
+                        format!(
+                            "Locale {} is defined more than once",
+                            manifest.package_locale
+                        ),
+                    ));
+                }
+                check_file_name(
+                    name,
+                    &format!("{identifier}.locale.{}.{YAML}", manifest.package_locale),
+                    violations,
+                );
+                (&manifest.package_identifier, &manifest.package_version)
+            }
+            ParsedManifest::Locale(manifest) => {
+                if !seen_locales.insert(manifest.package_locale.to_string()) {
+                    violations.push(Violation::new(
+                        name,
+                        "PackageLocale",
+                        format!(
+                            "Locale {} is defined more than once",
+                            manifest.package_locale
+                        ),
+                    ));
+                }
+                check_file_name(
+                    name,
+                    &format!("{identifier}.locale.{}.{YAML}", manifest.package_locale),
+                    violations,
+                );
+                (&manifest.package_identifier, &manifest.package_version)
+            }
+            ParsedManifest::Version(_) => continue,
+        };
+        check_package_matches(
+            name,
+            (package_identifier, package_version),
+            (identifier, version),
+            violations,
+        );
+    }
+
+    if !default_locale_found {
+        violations.push(Violation::new(
+            ".",
+            "",
+            format!(
+                "No default locale manifest was found for the default locale {} set in {version_file}",
+                version_manifest.default_locale
+            ),
+        ));
+    }
+}
+
+fn check_file_name(file: &str, expected: &str, violations: &mut Vec<Violation>) {
+    if file != expected {
+        violations.push(Violation::new(
+            file,
+            "",
+            format!("File name must be {expected}"),
+        ));
+    }
+}
+
+fn check_package_matches(
+    file: &str,
+    (identifier, version): (&PackageIdentifier, &PackageVersion),
+    (expected_identifier, expected_version): (&PackageIdentifier, &PackageVersion),
+    violations: &mut Vec<Violation>,
+) {
+    if identifier.as_str() != expected_identifier.as_str() {
+        violations.push(Violation::new(
+            file,
+            "PackageIdentifier",
+            format!("{identifier} does not match the version manifest's {expected_identifier}"),
+        ));
+    }
+    if version != expected_version {
+        violations.push(Violation::new(
+            file,
+            "PackageVersion",
+            format!("{version} does not match the version manifest's {expected_version}"),
+        ));
+    }
+}
+
+fn is_sha_256(value: &str) -> bool {
+    value.len() == SHA_256_LENGTH && value.chars().all(|char| char.is_ascii_hexdigit())
+}
+
+fn missing_field(reason: &str) -> Option<&str> {
+    reason
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(field, _)| field)
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+fn format_error_path(path: &serde_path_to_error::Path) -> String {
+    path.iter().fold(String::new(), |mut result, segment| {
+        match segment {
+            serde_path_to_error::Segment::Seq { index } => {
+                result.push_str(&format!("[{index}]"));
+            }
+            serde_path_to_error::Segment::Map { key }
+            | serde_path_to_error::Segment::Enum { variant: key } => {
+                result = join_path(&result, key);
+            }
+            serde_path_to_error::Segment::Unknown => result = join_path(&result, "?"),
+        }
+        result
+    })
+}
+
+fn format_ignored_path(path: &serde_ignored::Path) -> String {
+    match path {
+        serde_ignored::Path::Root => String::new(),
+        serde_ignored::Path::Seq { parent, index } => {
+            format!("{}[{index}]", format_ignored_path(parent))
+        }
+        serde_ignored::Path::Map { parent, key } => join_path(&format_ignored_path(parent), key),
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => format_ignored_path(parent),
+    }
+}
+
+/// Gets a valid value for a required key from the default manifests
+fn placeholder(key: &str) -> Option<Value> {
+    [
+        serde_yaml::to_value(VersionManifest::default()),
+        serde_yaml::to_value(DefaultLocaleManifest::default()),
+        serde_yaml::to_value(Installer::default()),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .find_map(|value| value.get(key).cloned())
+}
+
+fn navigate<'a>(
+    mut current: &'a mut Value,
+    segments: &[&serde_path_to_error::Segment],
+) -> Option<&'a mut Value> {
+    for segment in segments {
+        current = match segment {
+            serde_path_to_error::Segment::Seq { index } => current
+                .as_sequence_mut()
+                .and_then(|sequence| sequence.get_mut(*index))?,
+            serde_path_to_error::Segment::Map { key } => current.get_mut(key.as_str())?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+fn insert_at_path(
+    value: &mut Value,
+    path: &serde_path_to_error::Path,
+    key: &str,
+    new_value: Value,
+) -> bool {
+    let segments = path.iter().collect::<Vec<_>>();
+    navigate(value, &segments)
+        .and_then(Value::as_mapping_mut)
+        .map(|mapping| mapping.insert(Value::from(key), new_value))
+        .is_some()
+}
+
+/// Removes the value at the given path, returning whether anything was removed
+fn remove_at_path(value: &mut Value, path: &serde_path_to_error::Path) -> bool {
+    let segments = path.iter().collect::<Vec<_>>();
+    let Some((last, parents)) = segments.split_last() else {
+        return false;
+    };
+
+    let Some(current) = navigate(value, parents) else {
+        return false;
+    };
+
+    match last {
+        serde_path_to_error::Segment::Seq { index } => current
+            .as_sequence_mut()
+            .filter(|sequence| *index < sequence.len())
+            .map(|sequence| sequence.remove(*index))
+            .is_some(),
+        serde_path_to_error::Segment::Map { key } => current
+            .as_mapping_mut()
+            .and_then(|mapping| mapping.remove(key.as_str()))
+            .is_some(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validation::validate_manifests;
+
+    const VERSION: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.2.3
+DefaultLocale: en-US
+ManifestType: version
+ManifestVersion: 1.5.0
+";
+
+    const INSTALLER: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.2.3
+InstallerType: exe
+Installers:
+- Architecture: x64
+  InstallerUrl: https://www.example.com/file.exe
+  InstallerSha256: 0000000000000000000000000000000000000000000000000000000000000000
+ManifestType: installer
+ManifestVersion: 1.5.0
+";
+
+    const DEFAULT_LOCALE: &str = r"
+PackageIdentifier: Package.Identifier
+PackageVersion: 1.2.3
+PackageLocale: en-US
+Publisher: Publisher
+PackageName: Package Name
+License: MIT
+ShortDescription: A short description
+ManifestType: defaultLocale
+ManifestVersion: 1.5.0
+";
+
+    #[test]
+    fn test_valid_manifests() {
+        let files = [
+            ("Package.Identifier.yaml", VERSION),
+            ("Package.Identifier.installer.yaml", INSTALLER),
+            ("Package.Identifier.locale.en-US.yaml", DEFAULT_LOCALE),
+        ];
+        assert_eq!(validate_manifests(&files), Vec::new());
+    }
+
+    #[test]
+    fn test_reports_every_invalid_key() {
+        let default_locale = DEFAULT_LOCALE
+            .replace("Publisher: Publisher", "Publisher: P")
+            .replace("License: MIT", "License: M\nUnknownKey: value");
+        let files = [
+            ("Package.Identifier.yaml", VERSION),
+            ("Package.Identifier.installer.yaml", INSTALLER),
+            ("Package.Identifier.locale.en-US.yaml", &default_locale),
+        ];
+        let paths = validate_manifests(&files)
+            .into_iter()
+            .map(|violation| violation.path)
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["Publisher", "License", "UnknownKey"]);
+    }
+
+    #[test]
+    fn test_nested_key_path() {
+        let installer = INSTALLER.replace("https://www.example.com/file.exe", "not a url");
+        let files = [
+            ("Package.Identifier.yaml", VERSION),
+            ("Package.Identifier.installer.yaml", installer.as_str()),
+            ("Package.Identifier.locale.en-US.yaml", DEFAULT_LOCALE),
+        ];
+        let violations = validate_manifests(&files);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file, "Package.Identifier.installer.yaml");
+        assert_eq!(violations[0].path, "Installers[0].InstallerUrl");
+    }
+
+    #[test]
+    fn test_mismatched_package_version() {
+        let installer = INSTALLER.replace("PackageVersion: 1.2.3", "PackageVersion: 1.2.4");
+        let files = [
+            ("Package.Identifier.yaml", VERSION),
+            ("Package.Identifier.installer.yaml", installer.as_str()),
+            ("Package.Identifier.locale.en-US.yaml", DEFAULT_LOCALE),
+        ];
+        let violations = validate_manifests(&files);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "PackageVersion");
+    }
+
+    #[test]
+    fn test_missing_default_locale() {
+        let files = [
+            ("Package.Identifier.yaml", VERSION),
+            ("Package.Identifier.installer.yaml", INSTALLER),
+        ];
+        let violations = validate_manifests(&files);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("No default locale manifest"));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::file_analyser::FileAnalyser;
 use crate::manifests::installer_manifest::{NestedInstallerFiles, NestedInstallerType};
+use crate::prompts::{missing_value, prompts_disabled};
 use crate::url_utils::VALID_FILE_EXTENSIONS;
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{bail, Result};
 use inquire::{min_length, MultiSelect};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::collections::{BTreeSet, HashMap};
 use std::io::{Read, Seek};
@@ -10,6 +12,7 @@ use std::path::Path;
 use std::{io, mem};
 use zip::ZipArchive;
 
+#[derive(Deserialize, Serialize)]
 pub struct Zip {
     pub nested_installer_type: Option<NestedInstallerType>,
     pub nested_installer_files: Option<BTreeSet<NestedInstallerFiles>>,
@@ -81,14 +84,33 @@ impl Zip {
         })
     }
 
-    pub fn prompt(&mut self) -> Result<()> {
+    /// Chooses the nested files that are in this zip from the given paths, which can cover several
+    /// zips, or prompts for them if none were given
+    pub fn prompt(&mut self, nested_installer_files: &[String]) -> Result<()> {
         if !&self.identified_files.is_empty() {
-            let chosen = MultiSelect::new(
-                "Select the nested files",
-                mem::take(&mut self.identified_files),
-            )
-            .with_validator(min_length!(1))
-            .prompt()?;
+            let chosen = if !nested_installer_files.is_empty() {
+                let chosen = nested_installer_files
+                    .iter()
+                    .filter(|path| self.identified_files.contains(path))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if chosen.is_empty() {
+                    bail!(
+                        "None of {} are installers in the zip",
+                        nested_installer_files.join(", ")
+                    );
+                }
+                chosen
+            } else if prompts_disabled() {
+                return Err(missing_value("Nested installer files"));
+            } else {
+                MultiSelect::new(
+                    "Select the nested files",
+                    mem::take(&mut self.identified_files),
+                )
+                .with_validator(min_length!(1))
+                .prompt()?
+            };
             self.nested_installer_files = Some(
                 chosen
                     .into_iter()
@@ -102,3 +124,40 @@ impl Zip {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::zip::Zip;
+
+    fn zip(identified_files: &[&str]) -> Zip {
+        Zip {
+            nested_installer_type: None,
+            nested_installer_files: None,
+            identified_files: identified_files.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_nested_installer_files_of_each_zip() {
+        let nested_installer_files = ["x64/setup.exe".to_owned(), "arm64/setup.exe".to_owned()];
+        for path in &nested_installer_files {
+            let mut zip = zip(&[path, "readme.txt.exe"]);
+            zip.prompt(&nested_installer_files).unwrap();
+            assert_eq!(
+                vec![path],
+                zip.nested_installer_files
+                    .unwrap()
+                    .iter()
+                    .map(|files| &files.relative_file_path)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_nested_installer_files_in_zip() {
+        assert!(zip(&["x86/setup.exe"])
+            .prompt(&["x64/setup.exe".to_owned()])
+            .is_err());
+    }
+}